@@ -0,0 +1,133 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Attribute, DeriveInput, Error, Ident, Path, Result};
+
+use crate::derive_data_struct;
+
+pub(crate) fn derive_bundle_impl(crate_name: Ident, input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(fields) => fields,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "Deriving bundle is only supported for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "Deriving bundle is only supported for structs",
+            ))
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "Deriving bundle for a generic struct is not supported",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let fetch_name = format_ident!("{name}Fetch");
+
+    let field_vis = fields.named.iter().map(|v| &v.vis);
+    let field_names = fields
+        .named
+        .iter()
+        .map(|v| v.ident.as_ref().expect("Named field"))
+        .collect::<Vec<_>>();
+    let field_types = fields.named.iter().map(|v| &v.ty);
+    let components = fields
+        .named
+        .iter()
+        .map(|v| {
+            // Default to a component with the same name as the field
+            Ok(BundleFieldAttrs::get(&v.attrs)?
+                .component
+                .unwrap_or_else(|| v.ident.clone().expect("Named field").into()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let msg = format!("Reads the components of {name}");
+    let fetch_input = syn::parse2::<DeriveInput>(quote! {
+        #[doc = #msg]
+        #vis struct #fetch_name {
+            #(#field_vis #field_names: #crate_name::Component<#field_types>,)*
+        }
+    })
+    .expect("Generated struct is always valid");
+
+    let fetch_data = match &fetch_input.data {
+        syn::Data::Struct(data) => data,
+        _ => unreachable!(),
+    };
+
+    let fetch_derive = derive_data_struct(crate_name.clone(), &fetch_input, fetch_data)?;
+
+    Ok(quote! {
+        #fetch_input
+
+        #fetch_derive
+
+        #[automatically_derived]
+        impl #crate_name::Bundle for #name {
+            type Fetch = #fetch_name;
+
+            fn fetch() -> Self::Fetch {
+                #fetch_name {
+                    #(#field_names: #components(),)*
+                }
+            }
+
+            fn components(dst: &mut Vec<#crate_name::component::ComponentDesc>) {
+                dst.extend([#(#components().desc(),)*]);
+            }
+
+            fn set_into(self, buffer: &mut #crate_name::buffer::ComponentBuffer) {
+                #(buffer.set(#components(), self.#field_names);)*
+            }
+
+            fn take_from(buffer: &mut #crate_name::buffer::ComponentBuffer) -> Option<Self> {
+                Some(Self {
+                    #(#field_names: buffer.remove(#components())?,)*
+                })
+            }
+        }
+    })
+}
+
+#[derive(Default)]
+struct BundleFieldAttrs {
+    component: Option<Path>,
+}
+
+impl BundleFieldAttrs {
+    fn get(input: &[Attribute]) -> Result<Self> {
+        let mut res = Self::default();
+
+        for attr in input {
+            if !attr.path().is_ident("bundle") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                // component = position
+                if meta.path.is_ident("component") {
+                    res.component = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(Error::new(
+                        meta.path.span(),
+                        "Unknown bundle field attribute",
+                    ))
+                }
+            })?;
+        }
+
+        Ok(res)
+    }
+}
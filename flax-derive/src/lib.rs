@@ -1,17 +1,20 @@
+mod bundle;
 mod maybe_fn;
 mod system;
 
 use std::collections::BTreeSet;
 
+use bundle::derive_bundle_impl;
+use heck::ToSnakeCase;
 use itertools::Itertools;
 use maybe_fn::MaybeItemFn;
 use proc_macro2::{Span, TokenStream};
 use proc_macro_crate::FoundCrate;
 use quote::{format_ident, quote};
 use syn::{
-    bracketed, parse::Parse, punctuated::Punctuated, spanned::Spanned, Attribute, DataStruct,
-    DeriveInput, Error, Field, GenericParam, Generics, Ident, ImplGenerics, Index, Lifetime,
-    LifetimeParam, Result, Token, Type, TypeGenerics, TypeParam, Visibility,
+    bracketed, parse::Parse, punctuated::Punctuated, spanned::Spanned, Attribute, DataEnum,
+    DataStruct, DeriveInput, Error, Field, GenericParam, Generics, Ident, ImplGenerics, Index,
+    Lifetime, LifetimeParam, Result, Token, Type, TypeGenerics, TypeParam, Visibility,
 };
 use system::{system_impl, SystemAttrs};
 
@@ -91,6 +94,42 @@ pub fn system(
 /// # Field Attributes
 /// - `ignore`: ignore slot-filtering and transformations for a field.
 ///     Useful for including a `Mutable` in a change query.
+///
+/// # Enums
+///
+/// Each variant of an enum wraps a single fetch. The item is the first variant, in declaration
+/// order, which matches the archetype. If that variant can not be prepared for the archetype, the
+/// archetype is not visited, rather than falling through to the next variant.
+///
+/// The enum itself only declares the variants, and is never constructed. Its variants hold the
+/// fetches, whereas the item holds what they fetch, which borrows from the world and thus requires
+/// a separate type. As a derive can not alter the declaration, the fetch and item are generated as
+/// `{Enum}Fetch` and `{Enum}Item` instead, and the declaration may need `#[allow(dead_code)]`.
+///
+/// ```rust,ignore
+/// #[allow(dead_code)]
+/// #[derive(Fetch)]
+/// #[fetch(item_derives = [Debug])]
+/// enum Shape {
+///     Circle(Component<f32>),
+///     Rect(Component<glam::Vec2>),
+/// }
+///
+/// // Generates `ShapeFetch` with a field for each variant, and the item `ShapeItem`
+/// let mut query = Query::new(ShapeFetch {
+///     circle: radius(),
+///     rect: extent(),
+/// });
+///
+/// for shape in &mut query.borrow(&world) {
+///     match shape {
+///         ShapeItem::Circle(radius) => {}
+///         ShapeItem::Rect(extent) => {}
+///     }
+/// }
+/// ```
+///
+/// Transforms are not supported for enums.
 #[proc_macro_derive(Fetch, attributes(fetch))]
 pub fn derive_fetch(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let crate_name = match proc_macro_crate::crate_name("flax").expect("Failed to get crate name") {
@@ -100,6 +139,50 @@ pub fn derive_fetch(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     do_derive_fetch(crate_name, input.into()).into()
 }
 
+/// Derive a group of components from a struct, where each field is the value of a component.
+///
+/// Implements `Bundle`, which allows setting and removing the components together, and generates
+/// `{Name}Fetch` which reads the components back.
+///
+/// ```rust,ignore
+/// component! {
+///     position: Vec3,
+///     health: f32,
+/// }
+///
+/// #[derive(Bundle)]
+/// struct Player {
+///     #[bundle(component = position)]
+///     pos: Vec3,
+///     health: f32,
+/// }
+///
+/// let id = Entity::builder()
+///     .set_bundle(Player { pos: Vec3::ZERO, health: 100.0 })
+///     .spawn(&mut world);
+///
+/// let mut query = Query::new(Player::fetch());
+///
+/// let player: Player = world.remove_bundle(id)?;
+/// ```
+///
+/// # Field Attributes
+/// - `component`: The component of the field. Defaults to the component with the same name as
+///   the field.
+#[proc_macro_derive(Bundle, attributes(bundle))]
+pub fn derive_bundle(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let crate_name = match proc_macro_crate::crate_name("flax").expect("Failed to get crate name") {
+        FoundCrate::Itself => Ident::new("crate", Span::call_site()),
+        FoundCrate::Name(name) => Ident::new(&name, Span::call_site()),
+    };
+
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    derive_bundle_impl(crate_name, &input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 fn do_derive_fetch(crate_name: Ident, input: TokenStream) -> TokenStream {
     let input = match syn::parse2::<DeriveInput>(input) {
         Ok(input) => input,
@@ -109,7 +192,9 @@ fn do_derive_fetch(crate_name: Ident, input: TokenStream) -> TokenStream {
     match input.data {
         syn::Data::Struct(ref data) => derive_data_struct(crate_name, &input, data)
             .unwrap_or_else(|err| err.to_compile_error()),
-        syn::Data::Enum(_) => todo!(),
+        syn::Data::Enum(ref data) => {
+            derive_data_enum(crate_name, &input, data).unwrap_or_else(|err| err.to_compile_error())
+        }
         syn::Data::Union(_) => todo!(),
     }
 }
@@ -154,6 +239,248 @@ fn derive_data_struct(
     }
 }
 
+/// Derives a fetch for an enum where each variant wraps a fetch.
+///
+/// A struct with a field for each variant is generated as the fetch, and the item is the first
+/// variant which matches the archetype.
+fn derive_data_enum(
+    crate_name: Ident,
+    input: &DeriveInput,
+    data: &DataEnum,
+) -> Result<TokenStream> {
+    let attrs = Attrs::get(&input.attrs)?;
+
+    if !attrs.transforms.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "Transforms are not supported for enums",
+        ));
+    }
+
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "Deriving fetch for an empty enum is not supported",
+        ));
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Ok((&variant.ident, &fields.unnamed[0].ty))
+            }
+            _ => Err(Error::new(
+                variant.span(),
+                "Expected a variant with a single fetch, e.g; `Circle(Component<Circle>)`",
+            )),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let vis = &input.vis;
+    let enum_name = &input.ident;
+    let fetch_name = format_ident!("{enum_name}Fetch");
+    let generics = &input.generics;
+    let where_clause = &input.generics.where_clause;
+
+    let fetch_fields = variants.iter().map(|(ident, ty)| {
+        let ident = format_ident!("{}", ident.to_string().to_snake_case());
+        quote! {
+            #vis #ident: #ty,
+        }
+    });
+
+    let msg = format!("The fetch for each variant of {enum_name}");
+    let fetch_struct = quote! {
+        #[doc = #msg]
+        #vis struct #fetch_name #generics #where_clause {
+            #(#fetch_fields)*
+        }
+    };
+
+    let fetch_input =
+        syn::parse2::<DeriveInput>(fetch_struct).expect("Generated struct is always valid");
+
+    let mut params = Params::new(&crate_name, vis, &fetch_input, &attrs)?;
+    params.item_name = format_ident!("{enum_name}Item");
+    params.prepared_name = format_ident!("Prepared{enum_name}");
+
+    let Params {
+        item_name,
+        prepared_name,
+        q_generics,
+        w_generics,
+        field_names,
+        field_types,
+        ..
+    } = &params;
+
+    let variant_names = variants.iter().map(|v| v.0).collect_vec();
+    let chunk_name = format_ident!("{prepared_name}Chunk");
+    // The chunk is generic over the chunk of each variant, as it may not outlive `'w`
+    let chunk_generics = ('A'..='Z')
+        .zip(&variant_names)
+        .map(|(c, _)| format_ident!("{}", c))
+        .collect_vec();
+
+    let item_ty = params.q_ty();
+    let item_impl = params.q_impl();
+    let fetch_impl = params.w_impl();
+    let fetch_ty = params.base_ty();
+    let prep_impl = params.wq_impl();
+    let prep_ty = params.w_ty();
+
+    let item_msg = format!("The item returned by {fetch_name}");
+    let prepared_msg = format!("The prepared fetch for {fetch_name}");
+
+    let extras = match &attrs.item_derives {
+        Some(extras) => {
+            quote! { #[derive(#extras)]}
+        }
+        None => quote! {},
+    };
+
+    let describe =
+        variant_names
+            .iter()
+            .zip(field_names)
+            .enumerate()
+            .map(|(i, (variant, field))| {
+                let sep = if i > 0 { " | " } else { "" };
+                let label = format!("{sep}{variant}(");
+                quote! {
+                    f.write_str(#label)?;
+                    #crate_name::Fetch::describe(&self.#field, f)?;
+                    f.write_str(")")?;
+                }
+            });
+
+    Ok(quote! {
+        #fetch_input
+
+        #[doc = #item_msg]
+        #extras
+        #vis enum #item_name #q_generics {
+            #(#variant_names(<#field_types as #crate_name::fetch::FetchItem<'q>>::Item),)*
+        }
+
+        #[doc = #prepared_msg]
+        #vis enum #prepared_name #w_generics {
+            #(#variant_names(<#field_types as #crate_name::Fetch<'w>>::Prepared),)*
+        }
+
+        #[doc(hidden)]
+        #vis enum #chunk_name<#(#chunk_generics),*> {
+            #(#variant_names(#chunk_generics),)*
+        }
+
+        #[automatically_derived]
+        impl #item_impl #crate_name::fetch::FetchItem<'q> for #fetch_name #fetch_ty {
+            type Item = #item_name #item_ty;
+        }
+
+        #[automatically_derived]
+        impl #fetch_impl #crate_name::Fetch<'w> for #fetch_name #fetch_ty
+            where #(#field_types: 'static,)*
+        {
+            const MUTABLE: bool = #(<#field_types as #crate_name::Fetch <'w>>::MUTABLE)||*;
+
+            type Prepared = #prepared_name #prep_ty;
+
+            #[inline]
+            fn prepare( &'w self, data: #crate_name::fetch::FetchPrepareData<'w>
+            ) -> Option<Self::Prepared> {
+                // The first matching variant is used for the whole archetype.
+                //
+                // The variant is selected the same way as in `access`, so that the declared access
+                // is the one that is borrowed.
+                #(
+                    if #crate_name::Fetch::filter_arch(&self.#field_names, data.into()) {
+                        return #crate_name::Fetch::prepare(&self.#field_names, data)
+                            .map(#prepared_name::#variant_names);
+                    }
+                )*
+
+                None
+            }
+
+            #[inline]
+            fn filter_arch(&self, data: #crate_name::fetch::FetchAccessData) -> bool {
+                #(#crate_name::Fetch::filter_arch(&self.#field_names, data))||*
+            }
+
+            fn describe(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #(#describe)*
+                Ok(())
+            }
+
+            fn access(&self, data: #crate_name::fetch::FetchAccessData, dst: &mut Vec<#crate_name::system::Access>) {
+                #(
+                    if #crate_name::Fetch::filter_arch(&self.#field_names, data) {
+                        return #crate_name::Fetch::access(&self.#field_names, data, dst);
+                    }
+                )*
+            }
+
+            // Any of the variants may match, so no component is required
+            fn searcher(&self, _: &mut #crate_name::query::ArchetypeSearcher) {}
+        }
+
+        #[automatically_derived]
+        impl #prep_impl #crate_name::fetch::PreparedFetch<'q> for #prepared_name #prep_ty
+            where #(#field_types: 'static,)*
+        {
+            type Item = #item_name #item_ty;
+            type Chunk = #chunk_name<#(<<#field_types as #crate_name::fetch::Fetch<'w>>::Prepared as #crate_name::fetch::PreparedFetch<'q>>::Chunk),*>;
+
+            const HAS_FILTER: bool = #(<<#field_types as #crate_name::fetch::Fetch<'w>>::Prepared as #crate_name::fetch::PreparedFetch<'q>>::HAS_FILTER)||*;
+
+            #[inline]
+            unsafe fn fetch_next(chunk: &mut Self::Chunk) -> Self::Item {
+                match chunk {
+                    #(#chunk_name::#variant_names(chunk) => #item_name::#variant_names(<<#field_types as #crate_name::fetch::Fetch<'w>>::Prepared as #crate_name::fetch::PreparedFetch<'q>>::fetch_next(chunk)),)*
+                }
+            }
+
+            #[inline]
+            unsafe fn filter_slots(&mut self, slots: #crate_name::archetype::Slice) -> #crate_name::archetype::Slice {
+                match self {
+                    #(Self::#variant_names(prepared) => #crate_name::fetch::PreparedFetch::filter_slots(prepared, slots),)*
+                }
+            }
+
+            #[inline]
+            unsafe fn create_chunk(&'q mut self, slots: #crate_name::archetype::Slice) -> Self::Chunk {
+                match self {
+                    #(Self::#variant_names(prepared) => #chunk_name::#variant_names(#crate_name::fetch::PreparedFetch::create_chunk(prepared, slots)),)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #prep_impl #crate_name::fetch::RandomFetch<'q> for #prepared_name #prep_ty
+            where #(#field_types: 'static,)*
+            #(<#field_types as #crate_name::fetch::Fetch<'w>>::Prepared: #crate_name::fetch::PreparedFetch<'q, Item = <#field_types as #crate_name::fetch::FetchItem<'q>>::Item>,)*
+            #(<#field_types as #crate_name::fetch::Fetch<'w>>::Prepared: #crate_name::fetch::RandomFetch<'q>,)*
+        {
+            #[inline]
+            unsafe fn fetch_shared(&'q self, slot: usize) -> Self::Item {
+                match self {
+                    #(Self::#variant_names(prepared) => #item_name::#variant_names(#crate_name::fetch::RandomFetch::fetch_shared(prepared, slot)),)*
+                }
+            }
+
+            #[inline]
+            unsafe fn fetch_shared_chunk(chunk: &Self::Chunk, slot: usize) -> Self::Item {
+                match chunk {
+                    #(#chunk_name::#variant_names(chunk) => #item_name::#variant_names(<<#field_types as #crate_name::fetch::Fetch<'w>>::Prepared as #crate_name::fetch::RandomFetch<'q>>::fetch_shared_chunk(chunk, slot)),)*
+                }
+            }
+        }
+    })
+}
+
 fn derive_fetch_struct(params: &Params) -> TokenStream {
     let Params {
         crate_name,
@@ -31,6 +31,10 @@ impl BatchSpawn {
         self.storage.values().map(|v| v.desc())
     }
 
+    pub(crate) fn has(&self, key: ComponentKey) -> bool {
+        self.storage.contains_key(&key)
+    }
+
     /// Returns the number of entities in the batch
     pub fn len(&self) -> usize {
         self.len
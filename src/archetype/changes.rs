@@ -384,6 +384,42 @@ impl ChangeList {
         }
     }
 
+    /// Removes the changes of `slots`, regardless of their tick
+    pub(crate) fn clear_slots(&mut self, slots: Slice) {
+        let mut i = 0;
+        while let Some(change) = self.inner.get_mut(i) {
+            match change.slice.subtract(&slots) {
+                Remainder::NoOverlap => {}
+                Remainder::FullOverlap => {
+                    self.inner.remove(i);
+                    continue;
+                }
+                Remainder::Left(v) | Remainder::Right(v) => change.slice = v,
+                Remainder::Split(l, r) => {
+                    change.slice = l;
+                    let tick = change.tick;
+                    self.inner.insert(i + 1, Change::new(r, tick));
+                    i += 1;
+                }
+            }
+
+            i += 1;
+        }
+    }
+
+    /// Returns the changes of a single slot, moved to slot 0
+    pub(crate) fn extract_slot(&self, slot: Slot) -> Self {
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .find(|v| v.slice.contains(slot))
+                .map(|v| Change::single(0, v.tick))
+                .into_iter()
+                .collect(),
+        }
+    }
+
     pub fn iter_collapsed(&self) -> impl Iterator<Item = (Slot, u32)> + '_ {
         self.inner.iter().flat_map(|v| {
             let tick = v.tick;
@@ -557,6 +593,28 @@ impl Changes {
         f(ChangeKind::Removed, &mut self.map[2], &mut other.map[2]);
     }
 
+    /// Returns a copy of the change lists
+    pub(crate) fn lists(&self) -> [ChangeList; 3] {
+        self.map.clone()
+    }
+
+    /// Replaces the changes of `slots` by `lists`, which are relative to the start of `slots`
+    pub(crate) fn restore(&mut self, slots: Slice, lists: &[ChangeList; 3]) {
+        for (dst, src) in self.map.iter_mut().zip(lists) {
+            dst.clear_slots(slots);
+
+            for &change in src.iter() {
+                let slice = Slice::new(
+                    change.slice.start + slots.start,
+                    change.slice.end + slots.start,
+                );
+                debug_assert!(slice.is_subset(&slots));
+
+                dst.set(Change::new(slice, change.tick));
+            }
+        }
+    }
+
     pub(crate) fn set_track_modified(&self) {
         self.track_modified
             .store(true, sync::atomic::Ordering::Relaxed)
@@ -631,6 +689,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clear_slots() {
+        let mut changes = ChangeList::default();
+
+        changes.set(Change::new(Slice::new(0, 5), 1));
+        changes.set(Change::new(Slice::new(5, 10), 2));
+        changes.set(Change::new(Slice::new(12, 20), 3));
+
+        changes.clear_slots(Slice::new(3, 7));
+        changes.clear_slots(Slice::new(14, 16));
+
+        assert_eq!(
+            changes.iter().copied().collect_vec(),
+            [
+                Change::new(Slice::new(0, 3), 1),
+                Change::new(Slice::new(7, 10), 2),
+                Change::new(Slice::new(12, 14), 3),
+                Change::new(Slice::new(16, 20), 3),
+            ]
+        );
+
+        assert_eq!(
+            changes.extract_slot(17).iter().copied().collect_vec(),
+            [Change::single(0, 3)]
+        );
+        assert!(changes.extract_slot(5).iter().next().is_none());
+    }
+
     #[test]
     fn changes_small() {
         let mut changes = ChangeList::default();
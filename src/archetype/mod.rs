@@ -115,6 +115,23 @@ impl CellData {
         }
     }
 
+    /// Sets the specified entities and slots as modified after their values were replaced, and
+    /// invokes subscribers
+    /// **Note**: `ids` must be the slice of entities pointed to by `slice`
+    pub(crate) fn set_replaced(&mut self, ids: &[Entity], slots: Slice, change_tick: u32) {
+        self.set_modified(ids, slots, change_tick);
+
+        let event = EventData {
+            ids,
+            slots,
+            key: self.key,
+        };
+
+        for handler in self.subscribers.iter() {
+            handler.on_replaced(&self.storage, &event);
+        }
+    }
+
     /// Sets the specified entities and slots as modified and invokes subscribers
     /// **Note**: `ids` must be the slice of entities pointed to by `slice`
     pub(crate) fn set_added(&mut self, ids: &[Entity], slots: Slice, change_tick: u32) {
@@ -888,6 +905,21 @@ impl Archetype {
         }
     }
 
+    /// Removes all entities, and returns them alongside the values of each cell.
+    ///
+    /// Unlike [`Self::drain`] the cells are kept.
+    pub(crate) fn take_all(&mut self) -> (Vec<Entity>, Vec<ArchetypeStorage>) {
+        let slots = self.slots();
+        let mut storages = Vec::with_capacity(self.cells.len());
+        for cell in &mut *self.cells {
+            let data = cell.data.get_mut();
+            data.set_removed(&self.entities[slots.as_range()], slots);
+            storages.push(cell.drain());
+        }
+
+        (mem::take(&mut self.entities), storages)
+    }
+
     pub(crate) fn entities_mut(&mut self) -> &mut [Entity] {
         &mut self.entities
     }
@@ -896,6 +928,13 @@ impl Archetype {
         self.cell(key).map(|v| v.desc)
     }
 
+    /// Tracks modifications of all cells
+    pub(crate) fn track_modified(&mut self) {
+        for cell in &mut *self.cells {
+            cell.data.get_mut().changes.set_track_modified();
+        }
+    }
+
     /// Add a new subscriber. The subscriber must be interested in this archetype
     pub(crate) fn add_handler(&mut self, s: Arc<dyn EventSubscriber>) {
         // For component changes
@@ -931,7 +970,6 @@ impl Archetype {
     }
 
     /// Borrow the change list mutably
-    #[cfg(test)]
     pub(crate) fn changes_mut(&mut self, component: ComponentKey) -> Option<&mut Changes> {
         Some(&mut self.cell_mut(component)?.data.get_mut().changes)
     }
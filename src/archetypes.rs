@@ -19,6 +19,8 @@ pub(crate) struct Archetypes {
 
     // These trickle down to the archetypes
     subscribers: Vec<Arc<dyn EventSubscriber>>,
+    /// Track modifications of all components, regardless of whether a query uses them
+    track_modified: bool,
     pub(crate) index: ArchetypeIndex,
 }
 
@@ -37,6 +39,7 @@ impl Archetypes {
             gen: 2,
             reserved,
             subscribers: Vec::new(),
+            track_modified: false,
             index: ArchetypeIndex::new(),
         }
     }
@@ -150,6 +153,10 @@ impl Archetypes {
                         }
                     }
 
+                    if self.track_modified {
+                        new.track_modified();
+                    }
+
                     // Increase gen
                     self.gen = self.gen.wrapping_add(1);
                     let new_id = self.inner.spawn(new);
@@ -227,6 +234,19 @@ impl Archetypes {
         self.subscribers.push(subscriber)
     }
 
+    /// Tracks modifications of all current and future archetypes
+    pub(crate) fn track_modified(&mut self) {
+        self.track_modified = true;
+        for (_, arch) in self.inner.iter_mut() {
+            arch.track_modified();
+        }
+    }
+
+    /// Returns true if modifications are tracked for deltas or replication
+    pub(crate) fn tracks_modified(&self) -> bool {
+        self.track_modified
+    }
+
     pub(crate) fn gen(&self) -> u32 {
         self.gen
     }
@@ -253,6 +253,10 @@ impl ComponentBuffer {
         self.entries.contains_key(&component.key())
     }
 
+    pub(crate) fn has_key(&self, key: ComponentKey) -> bool {
+        self.entries.contains_key(&key)
+    }
+
     /// Returns the components in the buffer
     pub fn components(&self) -> impl Iterator<Item = &ComponentDesc> {
         self.entries.values().map(|v| &v.0)
@@ -347,6 +351,19 @@ impl ComponentBuffer {
         }
     }
 
+    /// Set a component in the buffer only if it is missing, otherwise `value` is dropped.
+    ///
+    /// Returns true if the component was inserted
+    pub(crate) unsafe fn set_missing_dyn(&mut self, desc: ComponentDesc, value: *mut u8) -> bool {
+        if self.entries.contains_key(&desc.key()) {
+            desc.drop(value);
+            false
+        } else {
+            self.set_dyn(desc, value);
+            true
+        }
+    }
+
     /// Drains the components from the buffer>
     ///
     /// The returned pointers must be manually dropped
@@ -0,0 +1,34 @@
+use alloc::vec::Vec;
+
+use crate::{buffer::ComponentBuffer, component::ComponentDesc, Fetch};
+
+/// A group of components which are set and removed together.
+///
+/// Each field of the bundle is the value of a component.
+///
+/// Prefer `#[derive(Bundle)]` over implementing this trait manually.
+///
+/// See:
+/// - [`EntityBuilder::set_bundle`](crate::EntityBuilder::set_bundle)
+/// - [`World::set_bundle`](crate::World::set_bundle)
+/// - [`World::remove_bundle`](crate::World::remove_bundle)
+/// - [`CommandBuffer::set_bundle`](crate::CommandBuffer::set_bundle)
+pub trait Bundle: Sized {
+    /// Fetch which reads the components of the bundle
+    type Fetch: for<'w> Fetch<'w>;
+
+    /// Returns a fetch which reads the components of the bundle
+    fn fetch() -> Self::Fetch;
+
+    /// Adds the components of the bundle to `dst`
+    fn components(dst: &mut Vec<ComponentDesc>);
+
+    /// Sets the components of the bundle in `buffer`
+    fn set_into(self, buffer: &mut ComponentBuffer);
+
+    /// Takes the components of the bundle out of `buffer`.
+    ///
+    /// Returns `None` if a component is missing, in which case the present components may have
+    /// been taken.
+    fn take_from(buffer: &mut ComponentBuffer) -> Option<Self>;
+}
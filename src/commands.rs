@@ -7,7 +7,7 @@ use crate::{
     buffer::MultiComponentBuffer,
     component::{dummy, ComponentDesc, ComponentValue},
     writer::{MissingDyn, SingleComponentWriter, WriteDedupDyn},
-    BatchSpawn, Component, Entity, EntityBuilder, RelationExt, World,
+    BatchSpawn, Bundle, Component, Entity, EntityBuilder, RelationExt, World,
 };
 
 type DeferFn = Box<dyn FnOnce(&mut World) -> anyhow::Result<()> + Send + Sync>;
@@ -51,6 +51,95 @@ enum Command {
     Defer(DeferFn),
 }
 
+impl Command {
+    fn apply(self, world: &mut World, inserts: &mut MultiComponentBuffer) -> anyhow::Result<()> {
+        match self {
+            Command::Spawn(mut entity) => {
+                entity.spawn(world);
+            }
+            Command::SpawnAt(mut entity, id) => {
+                entity
+                    .spawn_at(world, id)
+                    .map_err(|v| v.into_anyhow())
+                    .context("Failed to spawn entity")?;
+            }
+            Command::AppendTo(mut entity, id) => {
+                entity
+                    .append_to(world, id)
+                    .map_err(|v| v.into_anyhow())
+                    .context("Failed to append to entity")?;
+            }
+            Command::SpawnBatch(mut batch) => {
+                batch.spawn(world);
+            }
+            Command::SpawnBatchAt(mut batch, ids) => {
+                batch
+                    .spawn_at(world, &ids)
+                    .map_err(|v| v.into_anyhow())
+                    .context("Failed to spawn entity")?;
+            }
+            Command::Set { id, desc, offset } => unsafe {
+                let value = inserts.take_dyn(offset);
+                world
+                    .set_dyn(id, desc, value)
+                    .map_err(|v| v.into_anyhow())
+                    .with_context(|| format!("Failed to set component {}", desc.name()))?;
+            },
+            Command::SetDedup {
+                id,
+                desc,
+                offset,
+                cmp,
+            } => unsafe {
+                let value = inserts.take_dyn(offset);
+                world
+                    .set_with_writer(
+                        id,
+                        SingleComponentWriter::new(desc, WriteDedupDyn { value, cmp }),
+                    )
+                    .map_err(|v| v.into_anyhow())
+                    .with_context(|| format!("Failed to set component {}", desc.name()))?;
+            },
+            Command::SetMissing { id, desc, offset } => unsafe {
+                let value = inserts.take_dyn(offset);
+                world
+                    .set_with_writer(id, SingleComponentWriter::new(desc, MissingDyn { value }))
+                    .map_err(|v| v.into_anyhow())
+                    .with_context(|| format!("Failed to set component {}", desc.name()))?;
+            },
+            Command::Despawn(id) => world
+                .despawn(id)
+                .map_err(|v| v.into_anyhow())
+                .context("Failed to despawn entity")?,
+            Command::DespawnRecursive(relation, id) => world
+                .despawn_recursive_untyped(id, relation.key().id())
+                .map_err(|v| v.into_anyhow())
+                .context("Failed to despawn entity")?,
+            Command::Remove { id, desc } => world
+                .remove_dyn(id, desc)
+                .map_err(|v| v.into_anyhow())
+                .with_context(|| format!("Failed to remove component {}", desc.name()))?,
+            Command::Defer(func) => func(world).context("Failed to execute deferred function")?,
+        }
+
+        Ok(())
+    }
+
+    /// Returns the existing entity the command operates on
+    fn target(&self) -> Option<Entity> {
+        match *self {
+            Command::AppendTo(_, id)
+            | Command::Set { id, .. }
+            | Command::SetDedup { id, .. }
+            | Command::SetMissing { id, .. }
+            | Command::Despawn(id)
+            | Command::DespawnRecursive(_, id)
+            | Command::Remove { id, .. } => Some(id),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Debug for Command {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -150,6 +239,11 @@ impl CommandBuffer {
         self
     }
 
+    /// Set the components of a bundle for `id`.
+    pub fn set_bundle(&mut self, id: Entity, bundle: impl Bundle) -> &mut Self {
+        self.append_to(id, EntityBuilder::new().set_bundle(bundle))
+    }
+
     /// Convenience function for only setting the component if Some.
     pub fn set_opt<T: ComponentValue>(
         &mut self,
@@ -297,81 +391,40 @@ impl CommandBuffer {
     /// The commandbuffer is cleared and can be reused.
     pub fn apply(&mut self, world: &mut World) -> anyhow::Result<()> {
         for cmd in self.commands.drain(..) {
-            match cmd {
-                Command::Spawn(mut entity) => {
-                    entity.spawn(world);
-                }
-                Command::SpawnAt(mut entity, id) => {
-                    entity
-                        .spawn_at(world, id)
-                        .map_err(|v| v.into_anyhow())
-                        .context("Failed to spawn entity")?;
-                }
-                Command::AppendTo(mut entity, id) => {
-                    entity
-                        .append_to(world, id)
-                        .map_err(|v| v.into_anyhow())
-                        .context("Failed to append to entity")?;
-                }
-                Command::SpawnBatch(mut batch) => {
-                    batch.spawn(world);
-                }
-                Command::SpawnBatchAt(mut batch, ids) => {
-                    batch
-                        .spawn_at(world, &ids)
-                        .map_err(|v| v.into_anyhow())
-                        .context("Failed to spawn entity")?;
-                }
-                Command::Set { id, desc, offset } => unsafe {
-                    let value = self.inserts.take_dyn(offset);
-                    world
-                        .set_dyn(id, desc, value)
-                        .map_err(|v| v.into_anyhow())
-                        .with_context(|| format!("Failed to set component {}", desc.name()))?;
-                },
-                Command::SetDedup {
-                    id,
-                    desc,
-                    offset,
-                    cmp,
-                } => unsafe {
-                    let value = self.inserts.take_dyn(offset);
-                    world
-                        .set_with_writer(
-                            id,
-                            SingleComponentWriter::new(desc, WriteDedupDyn { value, cmp }),
-                        )
-                        .map_err(|v| v.into_anyhow())
-                        .with_context(|| format!("Failed to set component {}", desc.name()))?;
-                },
-                Command::SetMissing { id, desc, offset } => unsafe {
-                    let value = self.inserts.take_dyn(offset);
-                    world
-                        .set_with_writer(id, SingleComponentWriter::new(desc, MissingDyn { value }))
-                        .map_err(|v| v.into_anyhow())
-                        .with_context(|| format!("Failed to set component {}", desc.name()))?;
-                },
-                Command::Despawn(id) => world
-                    .despawn(id)
-                    .map_err(|v| v.into_anyhow())
-                    .context("Failed to despawn entity")?,
-                Command::DespawnRecursive(relation, id) => world
-                    .despawn_recursive_untyped(id, relation.key().id())
-                    .map_err(|v| v.into_anyhow())
-                    .context("Failed to despawn entity")?,
-                Command::Remove { id, desc } => world
-                    .remove_dyn(id, desc)
-                    .map_err(|v| v.into_anyhow())
-                    .with_context(|| format!("Failed to remove component {}", desc.name()))?,
-                Command::Defer(func) => {
-                    func(world).context("Failed to execute deferred function")?
+            cmd.apply(world, &mut self.inserts)?;
+            world.flush_hooks();
+        }
+
+        self.inserts.clear();
+
+        Ok(())
+    }
+
+    /// Applies all contents of the command buffer to the world, continuing past commands which
+    /// fail.
+    ///
+    /// Used for commands recorded by hooks, which can not be propagated to the operation which
+    /// invoked the hook. Commands targeting an entity which has since been despawned are skipped,
+    /// and all other failures are recorded for [`World::take_hook_errors`].
+    pub(crate) fn apply_hooks(&mut self, world: &mut World) {
+        for cmd in self.commands.drain(..) {
+            let target = cmd.target();
+            if let Err(err) = cmd.apply(world, &mut self.inserts) {
+                if target.is_none_or(|id| world.is_alive(id)) {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("Failed to apply hook command: {err:?}");
+                    world.hook_errors.push(err);
                 }
             }
+
+            world.flush_hooks();
         }
 
         self.inserts.clear();
+    }
 
-        Ok(())
+    pub(crate) fn is_empty(&self) -> bool {
+        self.commands.is_empty()
     }
 
     /// Clears all values in the component buffer but keeps allocations around.
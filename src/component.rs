@@ -19,8 +19,9 @@ use crate::{
     buffer::ComponentBuffer,
     entity::EntityKind,
     fetch::MaybeMut,
-    filter::{ChangeFilter, With, WithRelation, Without, WithoutRelation},
+    filter::{ChangeFilter, IndexedEq, With, WithRelation, Without, WithoutRelation},
     metadata::Metadata,
+    query::Removed,
     relation::RelationExt,
     vtable::{ComponentVTable, UntypedVTable},
     ComponentMut, Entity,
@@ -294,6 +295,28 @@ impl<T: ComponentValue> Component<T> {
         }
     }
 
+    /// Construct a new filter yielding entities where the component is equal to `value`.
+    ///
+    /// The matching entities are resolved through the component's index rather than comparing
+    /// the value of every entity.
+    ///
+    /// # Panics
+    /// If the component is not [`Indexed`](crate::metadata::Indexed)
+    pub fn eq_indexed(self, value: T) -> IndexedEq<T> {
+        IndexedEq::new(self, value)
+    }
+
+    /// Track the removals of this component, yielding the entities and the last values of the
+    /// component since the previous borrow.
+    ///
+    /// This includes entities which were despawned.
+    pub fn removed(self) -> Removed<T>
+    where
+        T: Clone,
+    {
+        Removed::new(self)
+    }
+
     /// Get the component's name.
     #[must_use]
     #[inline(always)]
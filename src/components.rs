@@ -7,6 +7,7 @@ use crate::component;
 use crate::Exclusive;
 
 use crate::component::ComponentDesc;
+use crate::Cloneable;
 use crate::Debuggable;
 
 component! {
@@ -14,12 +15,15 @@ component! {
     /// kind of component.
     ///
     /// This name will be used in *Display* and *Debug* impls of entities to make them more readable, as opposed to just the id.
-    pub name: String => [ Debuggable ],
+    ///
+    /// Entities can be found by name using [`World::find_by_name`](crate::World::find_by_name)
+    /// and [`World::find_by_path`](crate::World::find_by_path).
+    pub name: String => [ Debuggable, Cloneable ],
     /// Exclusive parent-child relation ship.
     ///
     /// Only one parent can exist for an entity. Adding a second relationship will override the
     /// existing one, effectively moving the subtree.
-    pub child_of(parent): () => [ Debuggable, Exclusive ],
+    pub child_of(parent): () => [ Debuggable, Cloneable, Exclusive ],
 
     /// Contains type erased metadata.
     ///
@@ -29,4 +33,15 @@ component! {
 
     /// Added automatically to all STATIC entities
     pub is_static_entity: () => [ Debuggable ],
+
+    /// Marks an entity as a template, which is instantiated using
+    /// [`World::instantiate`](crate::World::instantiate).
+    ///
+    /// Prefabs are not visited by queries unless [`Query::with_prefabs`](crate::Query::with_prefabs)
+    /// is used.
+    ///
+    /// Children attached using [`EntityBuilder::attach`](crate::EntityBuilder::attach) inherit the
+    /// marker. Entities which are related to a prefab in other ways need to be marked
+    /// individually to be hidden.
+    pub prefab: () => [ Debuggable, Cloneable ],
 }
@@ -0,0 +1,180 @@
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use alloc::{
+    collections::BTreeMap,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use atomic_refcell::AtomicRefCell;
+
+use crate::{
+    archetype::{Archetype, ArchetypeStorage},
+    buffer::ComponentBuffer,
+    component::ComponentDesc,
+    components::{component_info, is_static_entity},
+    events::{EventData, EventSubscriber},
+    world::advance_tick,
+    Entity,
+};
+
+/// The changes of a world since a change tick.
+///
+/// Created by [`World::diff`](crate::World::diff) and applied to another world using
+/// [`World::apply_delta`](crate::World::apply_delta).
+#[derive(Default, Debug)]
+pub struct WorldDelta {
+    pub(crate) tick: u32,
+    pub(crate) spawned: Vec<Entity>,
+    pub(crate) despawned: Vec<Entity>,
+    pub(crate) entities: BTreeMap<Entity, EntityDelta>,
+}
+
+impl WorldDelta {
+    /// Returns the change tick at which the delta was created.
+    ///
+    /// Use this as the baseline of the next diff.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns the entities which were spawned since the baseline
+    pub fn spawned(&self) -> &[Entity] {
+        &self.spawned
+    }
+
+    /// Returns the entities which were despawned since the baseline
+    pub fn despawned(&self) -> &[Entity] {
+        &self.despawned
+    }
+
+    /// Returns the component changes of an entity
+    pub fn get(&self, id: Entity) -> Option<&EntityDelta> {
+        self.entities.get(&id)
+    }
+
+    /// Iterate the component changes of all entities
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &EntityDelta)> {
+        self.entities.iter().map(|(&id, v)| (id, v))
+    }
+
+    /// Returns true if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.despawned.is_empty() && self.entities.is_empty()
+    }
+}
+
+/// The component changes of a single entity
+#[derive(Default, Debug)]
+pub struct EntityDelta {
+    pub(crate) added: ComponentBuffer,
+    pub(crate) modified: ComponentBuffer,
+    pub(crate) removed: Vec<ComponentDesc>,
+}
+
+impl EntityDelta {
+    /// Returns the values of the added components
+    pub fn added(&self) -> &ComponentBuffer {
+        &self.added
+    }
+
+    /// Returns the new values of the modified components
+    pub fn modified(&self) -> &ComponentBuffer {
+        &self.modified
+    }
+
+    /// Returns the removed components
+    pub fn removed(&self) -> &[ComponentDesc] {
+        &self.removed
+    }
+}
+
+/// Records the components removed from dynamic entities, as these are no longer present in the
+/// archetype change lists.
+pub(crate) struct DeltaTracker {
+    change_tick: Arc<AtomicU32>,
+    /// Removals are discarded while paused
+    paused: AtomicBool,
+    removed: AtomicRefCell<Vec<(u32, Entity, ComponentDesc)>>,
+}
+
+impl DeltaTracker {
+    pub(crate) fn new(change_tick: Arc<AtomicU32>) -> Self {
+        Self {
+            change_tick,
+            paused: AtomicBool::new(false),
+            removed: AtomicRefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the components removed after `tick`
+    pub(crate) fn removed_since(&self, tick: u32) -> Vec<(Entity, ComponentDesc)> {
+        let removed = self.removed.borrow();
+        let start = removed.partition_point(|v| v.0 <= tick);
+
+        removed[start..]
+            .iter()
+            .map(|&(_, id, desc)| (id, desc))
+            .collect()
+    }
+
+    /// Discards the removals up to and including `tick`
+    pub(crate) fn prune(&self, tick: u32) {
+        let mut removed = self.removed.borrow_mut();
+        let end = removed.partition_point(|v| v.0 <= tick);
+        removed.drain(..end);
+    }
+
+    /// Stops or resumes recording removals
+    #[cfg(feature = "serde")]
+    pub(crate) fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+}
+
+impl EventSubscriber for DeltaTracker {
+    fn on_added(&self, _: &ArchetypeStorage, _: &EventData) {}
+
+    fn on_modified(&self, _: &EventData) {}
+
+    fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
+        if self.paused.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let tick = advance_tick(&self.change_tick);
+        let desc = storage.desc();
+
+        self.removed
+            .borrow_mut()
+            .extend(event.ids.iter().map(|&id| (tick, id, desc)));
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn matches_arch(&self, arch: &Archetype) -> bool {
+        !arch.has(is_static_entity().key()) && !arch.has(component_info().key())
+    }
+}
+
+/// Forwards the events to a tracker for as long as it is alive
+impl EventSubscriber for Weak<DeltaTracker> {
+    fn on_added(&self, _: &ArchetypeStorage, _: &EventData) {}
+
+    fn on_modified(&self, _: &EventData) {}
+
+    fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
+        if let Some(tracker) = self.upgrade() {
+            tracker.on_removed(storage, event)
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.strong_count() > 0
+    }
+
+    fn matches_arch(&self, arch: &Archetype) -> bool {
+        !arch.has(is_static_entity().key()) && !arch.has(component_info().key())
+    }
+}
@@ -1,9 +1,6 @@
 use crate::{
-    buffer::ComponentBuffer,
-    component::{ComponentDesc, ComponentValue},
-    error::Result,
-    relation::RelationExt,
-    CommandBuffer, Component, Entity, World,
+    buffer::ComponentBuffer, component::ComponentValue, components::prefab, error::Result,
+    relation::RelationExt, Bundle, CommandBuffer, Component, Entity, World,
 };
 use alloc::{boxed::Box, vec::Vec};
 
@@ -38,6 +35,12 @@ impl core::fmt::Debug for ChildEntityBuilder {
 impl ChildEntityBuilder {
     fn spawn(mut self, world: &mut World, parent: Entity) -> Entity {
         (self.modify)(parent, &mut self.builder);
+
+        // Children of a prefab are part of the template and are hidden as well
+        if world.has(parent, prefab()) {
+            self.builder.tag(prefab());
+        }
+
         self.builder.spawn(world)
     }
 }
@@ -84,8 +87,9 @@ impl EntityBuilder {
         self
     }
 
-    pub(crate) unsafe fn set_dyn(&mut self, desc: ComponentDesc, value: *mut u8) -> &mut Self {
-        self.buffer.set_dyn(desc, value);
+    /// Sets the components of a bundle
+    pub fn set_bundle(&mut self, bundle: impl Bundle) -> &mut Self {
+        bundle.set_into(&mut self.buffer);
         self
     }
 
@@ -129,6 +133,11 @@ impl EntityBuilder {
         self.buffer.has(component)
     }
 
+    #[cfg(feature = "serde")]
+    pub(crate) fn buffer_mut(&mut self) -> &mut ComponentBuffer {
+        &mut self.buffer
+    }
+
     /// Remove a component from the component buffer
     pub fn remove<T: ComponentValue>(&mut self, component: Component<T>) -> Option<T> {
         self.buffer.remove(component)
@@ -136,6 +145,8 @@ impl EntityBuilder {
 
     /// Attach a child with the provided relation and value.
     /// The child is taken and cleared
+    ///
+    /// Children attached to a [`prefab`](crate::components::prefab) are marked as prefabs as well.
     pub fn attach_with<T: ComponentValue>(
         &mut self,
         relation: impl RelationExt<T> + ComponentValue,
@@ -175,6 +186,7 @@ impl EntityBuilder {
     pub fn spawn(&mut self, world: &mut World) -> Entity {
         profile_function!();
         let id = world.spawn_with(&mut self.buffer);
+        world.flush_hooks();
 
         self.children.drain(..).for_each(|child| {
             child.spawn(world, id);
@@ -190,6 +202,7 @@ impl EntityBuilder {
     /// Fails if an entity with the same index already exists.
     pub fn spawn_at(&mut self, world: &mut World, id: Entity) -> Result<Entity> {
         let (id, _) = world.spawn_at_with(id, &mut self.buffer)?;
+        world.flush_hooks();
 
         self.children.drain(..).for_each(|child| {
             child.spawn(world, id);
@@ -252,6 +265,15 @@ impl From<&mut EntityBuilder> for EntityBuilder {
     }
 }
 
+impl From<ComponentBuffer> for EntityBuilder {
+    fn from(buffer: ComponentBuffer) -> Self {
+        Self {
+            buffer,
+            children: Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{component, components::name, error::MissingComponent, Entity, Error, World};
@@ -453,6 +453,37 @@ impl Default for EntityStore {
     }
 }
 
+impl<V: Clone> Clone for EntityStore<V> {
+    fn clone(&self) -> Self {
+        let slots = self
+            .slots
+            .iter()
+            .map(|slot| {
+                let value = if slot.is_alive() {
+                    SlotValue {
+                        occupied: unsafe { slot.value.occupied.clone() },
+                    }
+                } else {
+                    SlotValue { vacant: Vacant }
+                };
+
+                Slot {
+                    value,
+                    gen: slot.gen,
+                }
+            })
+            .collect();
+
+        Self {
+            slots,
+            free: self.free.clone(),
+            kind: self.kind,
+            cursor: AtomicI64::new(self.cursor.load(Relaxed)),
+            len: self.len,
+        }
+    }
+}
+
 impl<V> Drop for EntityStore<V> {
     fn drop(&mut self) {
         for slot in &mut self.slots {
@@ -18,7 +18,7 @@ use crate::{
     query::QueryOne,
     relation::{RelationExt, RelationIter, RelationIterMut},
     writer::{EntityWriter, FnWriter, Missing, Replace, SingleComponentWriter, WriteDedup},
-    Component, Entity, Fetch, World,
+    Component, Entity, EntityBuilder, Fetch, World,
 };
 
 /// Borrow all the components of an entity at once.
@@ -199,7 +199,7 @@ impl<'a> EntityRefMut<'a> {
     /// Set a component for the entity
     pub(crate) fn set_with_writer<W: EntityWriter>(&mut self, writer: W) -> W::Output {
         let (loc, res) = self.world.set_with_writer(self.id, writer).unwrap();
-        self.loc = OnceCell::with_value(loc);
+        self.update_loc(loc);
         res
     }
 
@@ -219,13 +219,24 @@ impl<'a> EntityRefMut<'a> {
             (res.assume_init(), loc)
         };
 
-        self.loc = OnceCell::with_value(loc);
+        self.update_loc(loc);
         Ok(old)
     }
 
     /// Retain only the components specified by the predicate
     pub fn retain(&mut self, f: impl FnMut(ComponentKey) -> bool) {
-        self.loc = OnceCell::with_value(self.world.retain_entity_components(self.id, self.loc(), f))
+        let loc = self.world.retain_entity_components(self.id, self.loc(), f);
+        self.update_loc(loc);
+    }
+
+    /// Updates the location of the entity after a structural change
+    fn update_loc(&mut self, loc: EntityLocation) {
+        // Hooks may move the entity
+        self.loc = if self.world.flush_hooks() {
+            OnceCell::new()
+        } else {
+            OnceCell::with_value(loc)
+        };
     }
 
     /// See: [`crate::World::clear`]
@@ -476,6 +487,15 @@ impl<'a> EntityRef<'a> {
     pub fn arch(&self) -> &'a Archetype {
         self.arch
     }
+
+    /// Returns a builder with a clone of each component of the entity.
+    ///
+    /// Fails if a component is not [`Cloneable`](crate::metadata::Cloneable).
+    pub fn to_builder(&self) -> crate::error::Result<EntityBuilder> {
+        self.world
+            .clone_components(self.id, |target| target)
+            .map(Into::into)
+    }
 }
 
 impl Debug for EntityRef<'_> {
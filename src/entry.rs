@@ -25,7 +25,7 @@ pub struct VacantEntry<'a, T: ComponentValue> {
 impl<'a, T: ComponentValue> VacantEntry<'a, T> {
     /// Insert a value into the entry, returning a mutable reference to it
     pub fn insert(self, value: T) -> RefMut<'a, T> {
-        let (loc, _) = self
+        let (mut loc, _) = self
             .world
             .set_with_writer(
                 self.id,
@@ -33,6 +33,10 @@ impl<'a, T: ComponentValue> VacantEntry<'a, T> {
             )
             .expect("Entry is valid");
 
+        if self.world.flush_hooks() {
+            loc = self.world.location(self.id).expect("Entry is valid");
+        }
+
         self.world.get_mut_at(loc, self.component).unwrap()
     }
 }
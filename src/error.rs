@@ -1,5 +1,7 @@
 use core::fmt::Display;
 
+use alloc::{string::String, vec::Vec};
+
 use crate::{component::ComponentDesc, Entity};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -18,6 +20,14 @@ pub enum Error {
     IncompleteBatch,
     /// Attempt to spawn entity with occupied entity id
     EntityOccupied(Entity),
+    /// The world did not contain the requested resource
+    MissingResource(&'static str),
+    /// The ordering constraints of the systems in a schedule form a cycle
+    CyclicSchedule(Vec<String>),
+    /// The component does not have an index
+    NotIndexed(ComponentDesc),
+    /// The component can not be cloned
+    NotCloneable(ComponentDesc),
 }
 
 impl Error {
@@ -78,6 +88,21 @@ impl Display for Error {
             Error::EntityOccupied(current) => {
                 write!(f, "Attempt to spawn new entity occupied id {current}")
             }
+            Error::MissingResource(name) => {
+                write!(f, "World does not contain the resource {name}")
+            }
+            Error::CyclicSchedule(systems) => {
+                write!(f, "Cyclic system ordering: ")?;
+                for (i, name) in systems.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " -> ")?;
+                    }
+                    write!(f, "{name}")?;
+                }
+                Ok(())
+            }
+            Error::NotIndexed(desc) => write!(f, "Component {desc:?} is not indexed"),
+            Error::NotCloneable(desc) => write!(f, "Component {desc:?} is not cloneable"),
         }
     }
 }
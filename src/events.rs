@@ -87,6 +87,12 @@ pub trait EventSubscriber: ComponentValue {
     /// Prefer to use this for cache validation and alike, as it *will* be called for intermediate
     /// events.
     fn on_modified(&self, event: &EventData);
+    /// Handle a component value being replaced by a new value, such as through
+    /// [`World::set`](crate::World::set).
+    ///
+    /// The storage contains the new values. This is invoked in addition to
+    /// [`EventSubscriber::on_modified`].
+    fn on_replaced(&self, _storage: &ArchetypeStorage, _event: &EventData) {}
     /// Handle an incoming event
     fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData);
 
@@ -292,6 +298,10 @@ where
         self.subscriber.on_modified(event);
     }
 
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.subscriber.on_replaced(storage, event)
+    }
+
     fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
         self.subscriber.on_removed(storage, event)
     }
@@ -335,6 +345,12 @@ where
         }
     }
 
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        if (self.filter)(EventKind::Modified, event) {
+            self.subscriber.on_replaced(storage, event)
+        }
+    }
+
     fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
         if (self.filter)(EventKind::Removed, event) {
             self.subscriber.on_removed(storage, event)
@@ -375,6 +391,10 @@ where
         self.subscriber.on_modified(event)
     }
 
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.subscriber.on_replaced(storage, event)
+    }
+
     fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
         self.subscriber.on_removed(storage, event)
     }
@@ -413,6 +433,10 @@ where
         self.subscriber.on_modified(event)
     }
 
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.subscriber.on_replaced(storage, event)
+    }
+
     fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
         self.subscriber.on_removed(storage, event)
     }
@@ -460,6 +484,12 @@ where
         }
     }
 
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        if self.event_kind.contains(EventKindFilter::MODIFIED) {
+            self.subscriber.on_replaced(storage, event)
+        }
+    }
+
     fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
         if self.event_kind.contains(EventKindFilter::REMOVED) {
             self.subscriber.on_removed(storage, event)
@@ -217,7 +217,7 @@ pub trait FetchExt: Sized {
     where
         F: for<'x> Fetch<'x>,
     {
-        Filtered::new(self, filter, true)
+        Filtered::new(self, filter, true, true)
     }
 
     /// Expect the query to match, panic otherwise
@@ -0,0 +1,122 @@
+use core::fmt::{self, Formatter};
+
+use alloc::vec::Vec;
+
+use crate::{
+    archetype::{Slice, Slot},
+    component::ComponentValue,
+    fetch::{FetchAccessData, FetchPrepareData, PreparedFetch},
+    system::{Access, AccessKind},
+    ArchetypeSearcher, Component, Fetch, FetchItem,
+};
+
+/// Filter yielding entities which have a specific value of an indexed component.
+///
+/// See [`Component::eq_indexed`]
+#[derive(Debug, Clone)]
+pub struct IndexedEq<T: ComponentValue> {
+    component: Component<T>,
+    value: T,
+}
+
+impl<T: ComponentValue> IndexedEq<T> {
+    pub(crate) fn new(component: Component<T>, value: T) -> Self {
+        Self { component, value }
+    }
+}
+
+impl<T: ComponentValue> FetchItem<'_> for IndexedEq<T> {
+    type Item = ();
+}
+
+impl<'w, T: ComponentValue + Clone> Fetch<'w> for IndexedEq<T> {
+    const MUTABLE: bool = false;
+
+    type Prepared = PreparedIndexedEq;
+
+    fn prepare(&'w self, data: FetchPrepareData<'w>) -> Option<Self::Prepared> {
+        if !data.arch.has(self.component.key()) {
+            return None;
+        }
+
+        let ids = match data.world.lookup(self.component, &self.value) {
+            Ok(v) => v,
+            Err(err) => panic!("{err}"),
+        };
+
+        let mut slots = ids
+            .into_iter()
+            .filter_map(|id| data.world.location(id).ok())
+            .filter(|loc| loc.arch_id == data.arch_id)
+            .map(|loc| loc.slot)
+            .collect::<Vec<_>>();
+
+        if slots.is_empty() {
+            return None;
+        }
+
+        slots.sort_unstable();
+
+        Some(PreparedIndexedEq { slots })
+    }
+
+    fn filter_arch(&self, data: FetchAccessData) -> bool {
+        data.arch.has(self.component.key())
+    }
+
+    fn access(&self, data: FetchAccessData, dst: &mut Vec<Access>) {
+        if data.arch.has(self.component.key()) {
+            dst.push(Access {
+                kind: AccessKind::Archetype {
+                    id: data.arch_id,
+                    component: self.component.key(),
+                },
+                mutable: false,
+            })
+        }
+    }
+
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} == <indexed>", self.component.name())
+    }
+
+    fn searcher(&self, searcher: &mut ArchetypeSearcher) {
+        searcher.add_required(self.component.key())
+    }
+}
+
+#[doc(hidden)]
+pub struct PreparedIndexedEq {
+    /// The matching slots of the archetype, in ascending order
+    slots: Vec<Slot>,
+}
+
+impl<'q> PreparedFetch<'q> for PreparedIndexedEq {
+    type Item = ();
+    type Chunk = ();
+
+    const HAS_FILTER: bool = true;
+
+    unsafe fn filter_slots(&mut self, slots: Slice) -> Slice {
+        let first = self.slots.partition_point(|&v| v < slots.start);
+
+        let Some(&start) = self.slots.get(first).filter(|&&v| v < slots.end) else {
+            return Slice::new(slots.end, slots.end);
+        };
+
+        // Extend the run of consecutive slots
+        let count = self.slots[first..]
+            .iter()
+            .zip(start..slots.end)
+            .take_while(|(&a, b)| a == *b)
+            .count();
+
+        Slice::new(start, start + count)
+    }
+
+    #[inline]
+    unsafe fn create_chunk(&'q mut self, _: Slice) -> Self::Chunk {}
+
+    #[inline]
+    unsafe fn fetch_next(_: &mut Self::Chunk) -> Self::Item {}
+}
@@ -1,6 +1,7 @@
 mod change;
 mod cmp;
 mod constant;
+mod index;
 mod set;
 
 use alloc::vec::Vec;
@@ -14,7 +15,7 @@ use core::{
 use crate::{
     archetype::{Archetype, Slice, Slot},
     component::ComponentKey,
-    components::component_info,
+    components::{component_info, prefab},
     fetch::{FetchAccessData, FetchPrepareData, PreparedFetch},
     system::Access,
     ArchetypeSearcher, Entity, Fetch, FetchItem,
@@ -24,6 +25,7 @@ pub use change::ChangeFilter;
 pub use cmp::{Cmp, Equal, Greater, GreaterEq, Less, LessEq, NotEqual};
 pub(crate) use constant::NoEntities;
 pub use constant::{All, Nothing};
+pub use index::IndexedEq;
 pub use set::{And, Not, Or, Union};
 
 macro_rules! gen_bitops {
@@ -70,14 +72,21 @@ pub struct Filtered<Q, F> {
     pub(crate) fetch: Q,
     pub(crate) filter: F,
     pub(crate) include_components: bool,
+    pub(crate) include_prefabs: bool,
 }
 
 impl<Q, F> Filtered<Q, F> {
-    pub(crate) fn new(fetch: Q, filter: F, include_components: bool) -> Self {
+    pub(crate) fn new(
+        fetch: Q,
+        filter: F,
+        include_components: bool,
+        include_prefabs: bool,
+    ) -> Self {
         Self {
             fetch,
             filter,
             include_components,
+            include_prefabs,
         }
     }
 }
@@ -105,6 +114,7 @@ where
             fetch: self.fetch.prepare(data)?,
             filter: self.filter.prepare(data)?,
             include_components: self.include_components,
+            include_prefabs: self.include_prefabs,
         })
     }
 
@@ -113,6 +123,7 @@ where
         self.fetch.filter_arch(data)
             && self.filter.filter_arch(data)
             && (!data.arch.has(component_info().key()) || self.include_components)
+            && (!data.arch.has(prefab().key()) || self.include_prefabs)
     }
 
     #[inline]
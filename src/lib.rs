@@ -206,10 +206,14 @@ mod macros;
 pub mod archetype;
 /// Provides a buffer for holding multiple types simultaneously
 pub mod buffer;
+/// Groups of components which are set and removed together
+pub mod bundle;
 /// Contains a commandbuffer
 pub mod commands;
 /// Low level component construction
 pub mod component;
+/// Changes of the world since a change tick
+pub mod delta;
 /// Provides entity identifiers
 pub mod entity;
 /// Filter items yielded queries
@@ -239,6 +243,7 @@ pub mod metadata;
 pub mod query;
 /// Low level relation construction
 pub mod relation;
+mod resources;
 /// System execution
 pub mod schedule;
 
@@ -250,6 +255,8 @@ pub mod serialize;
 
 /// Provides a sink trait for sending events
 pub mod sink;
+/// Copies of the world for rollback
+pub mod snapshot;
 /// Provides tuple utilities like `cloned`
 mod util;
 /// vtable implementation for dynamic dispatching
@@ -258,6 +265,7 @@ mod writer;
 
 // Required due to macro
 pub use archetype::{BatchSpawn, RefMut};
+pub use bundle::Bundle;
 pub use commands::CommandBuffer;
 pub use component::Component;
 pub use entity::{entity_ids, Entity, EntityBuilder};
@@ -268,15 +276,22 @@ pub use fetch::{
     relations_like, ComponentMut, EntityIds, Fetch, FetchExt, FetchItem, Opt, OptOr, Relations,
 };
 
-pub use metadata::{Debuggable, Exclusive};
+pub use metadata::{
+    Cascade, Cloneable, ComponentHooks, Debuggable, EntityMappable, Exclusive, Hooks, Indexed,
+    MapEntities, NoRollback, OnTargetDespawn, PanicOnTargetDespawn, RequiredComponents, Requires,
+    Symmetric,
+};
+
+#[cfg(feature = "serde")]
+pub use metadata::Replicated;
 
 pub use query::{
-    Children, Dfs, DfsBorrow, DfsIter, EntityBorrow, EntityQuery, Planar, Query, QueryBorrow,
-    QueryIter, Topo,
+    Cached, Children, Dfs, DfsBorrow, DfsIter, EntityBorrow, EntityQuery, Planar, Query,
+    QueryBorrow, QueryIter, Removed, Sorted, Topo,
 };
 pub use relation::RelationExt;
-pub use schedule::{Schedule, ScheduleBuilder, SystemInfo};
-pub use system::{BoxedSystem, SharedResource, System, SystemBuilder};
+pub use schedule::{Schedule, ScheduleBuilder, SystemInfo, Timestep, TimestepSchedule};
+pub use system::{BoxedCondition, BoxedSystem, SharedResource, System, SystemBuilder, SystemStats};
 pub use world::World;
 
 pub(crate) use query::ArchetypeSearcher;
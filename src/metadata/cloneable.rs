@@ -0,0 +1,67 @@
+use core::mem::ManuallyDrop;
+
+use crate::{
+    archetype::{ArchetypeStorage, Slot},
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentValue},
+};
+
+use super::Metadata;
+
+component! {
+    /// Allows cloning the component, such as when instantiating a prefab
+    pub cloneable: Cloneable,
+}
+
+#[derive(Clone)]
+/// Clones a component value using [`Clone`]
+pub struct Cloneable {
+    pub(crate) clone_storage: fn(&ArchetypeStorage, Slot, ComponentDesc, &mut ComponentBuffer),
+    pub(crate) clone_column: fn(&ArchetypeStorage) -> ArchetypeStorage,
+}
+
+impl Cloneable {
+    /// Clones the value at `slot` into `buffer` as `desc`.
+    ///
+    /// `desc` may differ from the component of the storage by the relation target.
+    pub(crate) fn clone_storage(
+        &self,
+        storage: &ArchetypeStorage,
+        slot: Slot,
+        desc: ComponentDesc,
+        buffer: &mut ComponentBuffer,
+    ) {
+        (self.clone_storage)(storage, slot, desc, buffer)
+    }
+
+    /// Clones all values of `storage`
+    pub(crate) fn clone_column(&self, storage: &ArchetypeStorage) -> ArchetypeStorage {
+        (self.clone_column)(storage)
+    }
+}
+
+impl<T> Metadata<T> for Cloneable
+where
+    T: Clone + ComponentValue,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(
+            cloneable(),
+            Cloneable {
+                clone_storage: |storage, slot, desc, buffer| {
+                    let mut value = ManuallyDrop::new(storage.downcast_ref::<T>()[slot].clone());
+                    // Ownership of the value is moved to the buffer
+                    unsafe { buffer.set_dyn(desc, &mut *value as *mut T as *mut u8) }
+                },
+                clone_column: |storage| {
+                    let mut column = ArchetypeStorage::with_capacity(storage.desc(), storage.len());
+                    for value in storage.downcast_ref::<T>() {
+                        unsafe { column.push(value.clone()) }
+                    }
+
+                    column
+                },
+            },
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use core::{marker::PhantomData, mem};
+
+use atomic_refcell::{AtomicRefCell, AtomicRefMut};
+use itertools::Itertools;
+
+use crate::{
+    archetype::{ArchetypeStorage, Slot},
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentValue},
+    events::{EventData, EventSubscriber},
+    CommandBuffer, Entity,
+};
+
+use super::Metadata;
+
+component! {
+    /// Lifecycle hooks invoked when the component is added, replaced, or removed
+    pub hooks: HooksVTable,
+}
+
+/// Callbacks invoked when a component is added to, replaced on, or removed from an entity.
+///
+/// The hooks are invoked in the middle of structural changes, and can therefore not access the
+/// world directly. Instead, commands are recorded into the provided [`CommandBuffer`] which is
+/// applied to the world once the operation which triggered the hook completes.
+///
+/// Attach the hooks to a component using [`Hooks`].
+pub trait ComponentHooks<T>: 'static {
+    /// Invoked after the component is added to an entity
+    fn on_add(_id: Entity, _value: &T, _cmd: &mut CommandBuffer) {}
+
+    /// Invoked after an existing value of the component is replaced, such as through
+    /// [`World::set`](crate::World::set).
+    ///
+    /// **Note**: Modifications in place, such as through queries, are not considered replacements.
+    fn on_replace(_id: Entity, _value: &T, _cmd: &mut CommandBuffer) {}
+
+    /// Invoked before the component is removed from an entity, which includes despawning the
+    /// entity.
+    fn on_remove(_id: Entity, _value: &T, _cmd: &mut CommandBuffer) {}
+}
+
+/// Invokes the [`ComponentHooks`] of `H` for the lifecycle of the component.
+///
+/// ```rust
+/// # use flax::*;
+/// struct ColliderHooks;
+///
+/// impl ComponentHooks<f32> for ColliderHooks {
+///     fn on_add(id: Entity, value: &f32, cmd: &mut CommandBuffer) {
+///         cmd.set(id, collider_handle(), (*value * 100.0) as u32);
+///     }
+///
+///     fn on_remove(id: Entity, _: &f32, cmd: &mut CommandBuffer) {
+///         cmd.remove(id, collider_handle());
+///     }
+/// }
+///
+/// component! {
+///     collider_radius: f32 => [ Hooks<ColliderHooks> ],
+///     collider_handle: u32,
+/// }
+///
+/// let mut world = World::new();
+///
+/// let id = Entity::builder()
+///     .set(collider_radius(), 0.5)
+///     .spawn(&mut world);
+///
+/// assert_eq!(world.get_copy(id, collider_handle()), Ok(50));
+///
+/// world.remove(id, collider_radius()).unwrap();
+/// assert!(!world.has(id, collider_handle()));
+/// ```
+pub struct Hooks<H> {
+    _marker: PhantomData<H>,
+}
+
+impl<T, H> Metadata<T> for Hooks<H>
+where
+    T: ComponentValue,
+    H: ComponentHooks<T>,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(
+            hooks(),
+            HooksVTable {
+                on_add: |id, storage, slot, cmd| {
+                    H::on_add(id, &storage.downcast_ref::<T>()[slot], cmd)
+                },
+                on_replace: |id, storage, slot, cmd| {
+                    H::on_replace(id, &storage.downcast_ref::<T>()[slot], cmd)
+                },
+                on_remove: |id, storage, slot, cmd| {
+                    H::on_remove(id, &storage.downcast_ref::<T>()[slot], cmd)
+                },
+            },
+        );
+    }
+}
+
+type HookFn = fn(Entity, &ArchetypeStorage, Slot, &mut CommandBuffer);
+
+/// Type erased [`ComponentHooks`]
+#[derive(Clone)]
+pub struct HooksVTable {
+    on_add: HookFn,
+    on_replace: HookFn,
+    on_remove: HookFn,
+}
+
+/// Invokes the hooks of components and records their commands until they are applied to the
+/// world.
+#[derive(Default)]
+pub(crate) struct HookDispatcher {
+    cmd: AtomicRefCell<CommandBuffer>,
+}
+
+impl HookDispatcher {
+    /// Takes the commands recorded by the hooks
+    pub(crate) fn take(&self) -> CommandBuffer {
+        mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> AtomicRefMut<'_, CommandBuffer> {
+        // Replacements can occur through a shared world reference, such as
+        // `World::update_dedup`, and may thus race each other.
+        loop {
+            if let Ok(cmd) = self.cmd.try_borrow_mut() {
+                break cmd;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+
+    fn dispatch(
+        &self,
+        storage: &ArchetypeStorage,
+        event: &EventData,
+        hook: impl Fn(&HooksVTable) -> HookFn,
+    ) {
+        let Some(hook) = storage.desc().meta_ref().get(hooks()).map(hook) else {
+            return;
+        };
+
+        let mut cmd = self.lock();
+        for (&id, slot) in event.ids.iter().zip_eq(event.slots.iter()) {
+            hook(id, storage, slot, &mut cmd);
+        }
+    }
+}
+
+impl EventSubscriber for HookDispatcher {
+    fn on_added(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.dispatch(storage, event, |v| v.on_add)
+    }
+
+    fn on_modified(&self, _: &EventData) {}
+
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.dispatch(storage, event, |v| v.on_replace)
+    }
+
+    fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.dispatch(storage, event, |v| v.on_remove)
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn matches_component(&self, desc: ComponentDesc) -> bool {
+        desc.meta_ref().has(hooks())
+    }
+}
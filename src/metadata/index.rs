@@ -0,0 +1,461 @@
+use core::{
+    any::Any,
+    mem,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use atomic_refcell::{AtomicRefCell, AtomicRefMut};
+use itertools::Itertools;
+use smallvec::SmallVec;
+
+use crate::{
+    archetype::{Archetype, ArchetypeStorage, Slot},
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentKey, ComponentValue},
+    components::name,
+    error::Result,
+    events::{EventData, EventSubscriber},
+    Component, Entity, Error, World,
+};
+
+use super::Metadata;
+
+component! {
+    /// Maintains a secondary index from the values of the component to the entities
+    pub index: IndexVTable,
+}
+
+/// Maintains an ordered index from the values of the component to the entities which have them.
+///
+/// This allows finding the entities with a specific value in `O(log n)` using
+/// [`World::lookup`](crate::World::lookup) or
+/// [`Component::eq_indexed`](crate::Component::eq_indexed), rather than visiting every entity.
+///
+/// ```rust
+/// # use flax::*;
+/// component! {
+///     network_id: u64 => [ Indexed ],
+/// }
+///
+/// let mut world = World::new();
+///
+/// let id = Entity::builder()
+///     .set(network_id(), 42)
+///     .spawn(&mut world);
+///
+/// assert_eq!(world.lookup(network_id(), &42), Ok(vec![id]));
+/// ```
+pub struct Indexed;
+
+impl<T> Metadata<T> for Indexed
+where
+    T: ComponentValue + Ord + Clone,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(
+            index(),
+            IndexVTable {
+                create: || Box::new(ValueIndex::<T>::new(BTreeMap::new())),
+            },
+        );
+    }
+}
+
+/// Maintains a hashed index from the values of the component to the entities which have them.
+///
+/// Same as [`Indexed`], but for values which are `Hash + Eq` and resolved in `O(1)`.
+#[cfg(feature = "std")]
+pub struct HashIndexed;
+
+#[cfg(feature = "std")]
+impl<T> Metadata<T> for HashIndexed
+where
+    T: ComponentValue + core::hash::Hash + Eq + Clone,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(
+            index(),
+            IndexVTable {
+                create: || Box::new(ValueIndex::<T>::new(std::collections::HashMap::new())),
+            },
+        );
+    }
+}
+
+/// Type erased constructor of a component's index
+#[derive(Clone)]
+pub struct IndexVTable {
+    create: fn() -> Box<dyn DynIndex>,
+}
+
+type Entities = SmallVec<[Entity; 1]>;
+
+/// Maps values to the entities which have them
+trait IndexMap<T>: Send + Sync {
+    fn insert(&mut self, value: T, id: Entity);
+    fn remove(&mut self, value: &T, id: Entity);
+    fn get(&self, value: &T) -> &[Entity];
+}
+
+impl<T> IndexMap<T> for BTreeMap<T, Entities>
+where
+    T: Ord + Send + Sync,
+{
+    fn insert(&mut self, value: T, id: Entity) {
+        self.entry(value).or_default().push(id)
+    }
+
+    fn remove(&mut self, value: &T, id: Entity) {
+        if let Some(ids) = self.get_mut(value) {
+            ids.retain(|v| *v != id);
+            if ids.is_empty() {
+                BTreeMap::remove(self, value);
+            }
+        }
+    }
+
+    fn get(&self, value: &T) -> &[Entity] {
+        BTreeMap::get(self, value)
+            .map(|v| &v[..])
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> IndexMap<T> for std::collections::HashMap<T, Entities>
+where
+    T: core::hash::Hash + Eq + Send + Sync,
+{
+    fn insert(&mut self, value: T, id: Entity) {
+        self.entry(value).or_default().push(id)
+    }
+
+    fn remove(&mut self, value: &T, id: Entity) {
+        if let Some(ids) = self.get_mut(value) {
+            ids.retain(|v| *v != id);
+            if ids.is_empty() {
+                std::collections::HashMap::remove(self, value);
+            }
+        }
+    }
+
+    fn get(&self, value: &T) -> &[Entity] {
+        std::collections::HashMap::get(self, value)
+            .map(|v| &v[..])
+            .unwrap_or_default()
+    }
+}
+
+/// Type erased index of a single component
+trait DynIndex: Send + Sync {
+    fn insert(&mut self, id: Entity, storage: &ArchetypeStorage, slot: Slot);
+    fn remove(&mut self, id: Entity);
+    fn mark_dirty(&mut self, id: Entity);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct ValueIndex<T> {
+    values: Box<dyn IndexMap<T>>,
+    /// The indexed value of each entity
+    entities: BTreeMap<Entity, T>,
+    /// Entities which values may have been modified in place since they were indexed
+    dirty: BTreeSet<Entity>,
+}
+
+impl<T: ComponentValue + Clone> ValueIndex<T> {
+    fn new(values: impl IndexMap<T> + 'static) -> Self {
+        Self {
+            values: Box::new(values),
+            entities: BTreeMap::new(),
+            dirty: BTreeSet::new(),
+        }
+    }
+
+    fn set(&mut self, id: Entity, value: &T) {
+        self.unset(id);
+        self.values.insert(value.clone(), id);
+        self.entities.insert(id, value.clone());
+    }
+
+    fn unset(&mut self, id: Entity) {
+        if let Some(old) = self.entities.remove(&id) {
+            self.values.remove(&old, id);
+        }
+    }
+
+    /// Re-indexes the values which were modified in place.
+    ///
+    /// Values which are currently borrowed mutably, and are thus being written to, remain dirty.
+    fn refresh(&mut self, world: &World, component: Component<T>) {
+        if self.dirty.is_empty() {
+            return;
+        }
+
+        for id in mem::take(&mut self.dirty) {
+            let Ok(loc) = world.location(id) else {
+                self.unset(id);
+                continue;
+            };
+
+            match world
+                .archetypes
+                .get(loc.arch_id)
+                .try_get(loc.slot, component)
+            {
+                Ok(Some(value)) => {
+                    let value = value.clone();
+                    self.set(id, &value)
+                }
+                Ok(None) => self.unset(id),
+                Err(_) => {
+                    self.dirty.insert(id);
+                }
+            }
+        }
+    }
+}
+
+impl<T: ComponentValue + Clone> DynIndex for ValueIndex<T> {
+    fn insert(&mut self, id: Entity, storage: &ArchetypeStorage, slot: Slot) {
+        self.set(id, &storage.downcast_ref::<T>()[slot]);
+        self.dirty.remove(&id);
+    }
+
+    fn remove(&mut self, id: Entity) {
+        self.unset(id);
+        self.dirty.remove(&id);
+    }
+
+    fn mark_dirty(&mut self, id: Entity) {
+        if self.entities.contains_key(&id) {
+            self.dirty.insert(id);
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Keeps the indexes of all indexed components up to date as they are added, replaced, modified,
+/// or removed.
+#[derive(Default)]
+pub(crate) struct IndexDispatcher {
+    indexes: AtomicRefCell<BTreeMap<ComponentKey, Box<dyn DynIndex>>>,
+}
+
+fn lock<T>(cell: &AtomicRefCell<T>) -> AtomicRefMut<'_, T> {
+    // Events may be emitted concurrently by queries running in parallel
+    loop {
+        if let Ok(value) = cell.try_borrow_mut() {
+            break value;
+        }
+
+        core::hint::spin_loop();
+    }
+}
+
+impl IndexDispatcher {
+    fn lock(&self) -> AtomicRefMut<'_, BTreeMap<ComponentKey, Box<dyn DynIndex>>> {
+        lock(&self.indexes)
+    }
+
+    /// Returns the entities which have `value`
+    pub(crate) fn lookup<T: ComponentValue + Clone>(
+        &self,
+        world: &World,
+        component: Component<T>,
+        value: &T,
+    ) -> Result<Vec<Entity>> {
+        if !component.desc().meta_ref().has(index()) {
+            return Err(Error::NotIndexed(component.desc()));
+        }
+
+        let mut indexes = self.lock();
+        let Some(index) = indexes.get_mut(&component.key()) else {
+            return Ok(Vec::new());
+        };
+
+        let index = index
+            .as_any_mut()
+            .downcast_mut::<ValueIndex<T>>()
+            .expect("Mismatched index type");
+
+        index.refresh(world, component);
+
+        Ok(index.values.get(value).to_vec())
+    }
+
+    /// Removes all indexed values
+    pub(crate) fn clear(&self) {
+        self.lock().clear()
+    }
+
+    fn insert(&self, storage: &ArchetypeStorage, event: &EventData) {
+        let desc = storage.desc();
+        let Some(vtable) = desc.meta_ref().get(index()) else {
+            return;
+        };
+
+        let mut indexes = self.lock();
+        let index = indexes
+            .entry(event.key)
+            .or_insert_with(|| (vtable.create)());
+
+        for (&id, slot) in event.ids.iter().zip_eq(event.slots.iter()) {
+            index.insert(id, storage, slot);
+        }
+    }
+}
+
+impl EventSubscriber for IndexDispatcher {
+    fn on_added(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.insert(storage, event)
+    }
+
+    fn on_modified(&self, event: &EventData) {
+        if let Some(index) = self.lock().get_mut(&event.key) {
+            for &id in event.ids {
+                index.mark_dirty(id);
+            }
+        }
+    }
+
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.insert(storage, event)
+    }
+
+    fn on_removed(&self, _: &ArchetypeStorage, event: &EventData) {
+        if let Some(index) = self.lock().get_mut(&event.key) {
+            for &id in event.ids {
+                index.remove(id);
+            }
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn matches_component(&self, desc: ComponentDesc) -> bool {
+        desc.meta_ref().has(index())
+    }
+}
+
+/// Index of the entity [`name`]s, used by [`World::find_by_name`] and [`World::find_by_path`].
+///
+/// The index is only built on the first lookup, so worlds which never resolve names do not pay
+/// for maintaining it.
+///
+/// Components, while named, are not indexed.
+#[derive(Default)]
+pub(crate) struct NameIndex {
+    built: AtomicBool,
+    index: AtomicRefCell<Option<ValueIndex<String>>>,
+}
+
+impl NameIndex {
+    /// Returns the entities with the given name
+    pub(crate) fn lookup(&self, world: &World, value: &str) -> Vec<Entity> {
+        let mut index = lock(&self.index);
+
+        let index = index.get_or_insert_with(|| {
+            // Events which arrive from now on wait for the index to be built
+            self.built.store(true, Ordering::Release);
+            Self::build(world)
+        });
+
+        index.refresh(world, name());
+        index.values.get(&value.into()).to_vec()
+    }
+
+    fn build(world: &World) -> ValueIndex<String> {
+        let mut index = ValueIndex::new(BTreeMap::new());
+        for (_, arch) in world.archetypes.iter() {
+            let Some(cell) = arch.cell(name().key()) else {
+                continue;
+            };
+
+            let entities = arch.entities().iter().enumerate();
+            match cell.data.try_borrow() {
+                Ok(data) => {
+                    for (slot, &id) in entities.filter(|v| !v.1.is_component()) {
+                        index.insert(id, &data.storage, slot);
+                    }
+                }
+                // Currently being written to, and resolved once the borrow is released
+                Err(_) => index
+                    .dirty
+                    .extend(entities.map(|v| *v.1).filter(|v| !v.is_component())),
+            }
+        }
+
+        index
+    }
+
+    /// Discards the index, which is rebuilt on the next lookup
+    pub(crate) fn clear(&self) {
+        *lock(&self.index) = None;
+        self.built.store(false, Ordering::Release);
+    }
+
+    fn with_index(&self, f: impl FnOnce(&mut ValueIndex<String>)) {
+        if !self.built.load(Ordering::Acquire) {
+            return;
+        }
+
+        if let Some(index) = &mut *lock(&self.index) {
+            f(index)
+        }
+    }
+}
+
+impl EventSubscriber for NameIndex {
+    fn on_added(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.with_index(|index| {
+            for (&id, slot) in event.ids.iter().zip_eq(event.slots.iter()) {
+                if !id.is_component() {
+                    index.insert(id, storage, slot);
+                }
+            }
+        })
+    }
+
+    fn on_modified(&self, event: &EventData) {
+        self.with_index(|index| {
+            for &id in event.ids {
+                index.mark_dirty(id);
+            }
+        })
+    }
+
+    fn on_replaced(&self, storage: &ArchetypeStorage, event: &EventData) {
+        self.on_added(storage, event)
+    }
+
+    fn on_removed(&self, _: &ArchetypeStorage, event: &EventData) {
+        self.with_index(|index| {
+            for &id in event.ids {
+                index.remove(id);
+            }
+        })
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn matches_component(&self, desc: ComponentDesc) -> bool {
+        desc.key() == name().key()
+    }
+
+    fn matches_arch(&self, arch: &Archetype) -> bool {
+        arch.has(name().key())
+    }
+}
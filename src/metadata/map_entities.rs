@@ -0,0 +1,141 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    archetype::ArchetypeStorage,
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentValue},
+    Entity,
+};
+
+use super::Metadata;
+
+component! {
+    /// Remaps the entity ids contained in the component when entities are migrated
+    pub entity_mappable: EntityMappable,
+}
+
+/// Rewrites the entity ids contained in a value.
+///
+/// Relation targets are remapped automatically, but ids stored inside component values are not.
+/// Implement this trait and add [`EntityMappable`] to the component's metadata to have the ids
+/// remapped by [`World::merge_with`](crate::World::merge_with),
+/// [`World::instantiate`](crate::World::instantiate) and
+/// [`World::apply_delta`](crate::World::apply_delta).
+///
+/// ```rust
+/// # use flax::*;
+/// struct Inventory {
+///     items: Vec<Entity>,
+///     equipped: Option<Entity>,
+/// }
+///
+/// impl MapEntities for Inventory {
+///     fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+///         self.items.map_entities(map);
+///         self.equipped.map_entities(map);
+///     }
+/// }
+///
+/// component! {
+///     inventory: Inventory => [ EntityMappable ],
+/// }
+/// ```
+pub trait MapEntities {
+    /// Replaces each contained entity id with `map(id)`
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity);
+}
+
+impl MapEntities for Entity {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        *self = map(*self);
+    }
+}
+
+impl<T: MapEntities> MapEntities for Option<T> {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        if let Some(value) = self {
+            value.map_entities(map);
+        }
+    }
+}
+
+impl<T: MapEntities> MapEntities for Box<T> {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        (**self).map_entities(map);
+    }
+}
+
+impl<T: MapEntities> MapEntities for [T] {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        for value in self {
+            value.map_entities(map);
+        }
+    }
+}
+
+impl<T: MapEntities, const N: usize> MapEntities for [T; N] {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        self.as_mut_slice().map_entities(map);
+    }
+}
+
+impl<T: MapEntities> MapEntities for Vec<T> {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        self.as_mut_slice().map_entities(map);
+    }
+}
+
+#[derive(Clone)]
+/// Remaps the entity ids of a component value using [`MapEntities`]
+pub struct EntityMappable {
+    map_storage: fn(&mut ArchetypeStorage, &dyn Fn(Entity) -> Entity),
+    map_value: unsafe fn(*mut u8, &dyn Fn(Entity) -> Entity),
+}
+
+impl EntityMappable {
+    /// Remaps all values of `storage`
+    pub(crate) fn map_storage(
+        &self,
+        storage: &mut ArchetypeStorage,
+        map: &dyn Fn(Entity) -> Entity,
+    ) {
+        (self.map_storage)(storage, map)
+    }
+
+    /// Remaps the value pointed to by `value`
+    ///
+    /// # Safety
+    /// `value` must point to a valid value of the component type
+    pub(crate) unsafe fn map_value(&self, value: *mut u8, map: &dyn Fn(Entity) -> Entity) {
+        (self.map_value)(value, map)
+    }
+}
+
+impl<T> Metadata<T> for EntityMappable
+where
+    T: MapEntities + ComponentValue,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(
+            entity_mappable(),
+            EntityMappable {
+                map_storage: |storage, map| storage.downcast_mut::<T>().map_entities(map),
+                map_value: |value, map| unsafe { (*value.cast::<T>()).map_entities(map) },
+            },
+        );
+    }
+}
+
+/// Remaps the entity ids contained in the values of `buffer`
+pub(crate) fn map_buffer_entities(buffer: &mut ComponentBuffer, map: &dyn Fn(Entity) -> Entity) {
+    // Safety: all values are retained
+    unsafe {
+        buffer.retain(|desc, value| {
+            if let Some(mappable) = desc.meta_ref().get(entity_mappable()) {
+                mappable.map_value(value, map);
+            }
+
+            true
+        })
+    }
+}
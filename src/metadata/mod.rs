@@ -4,11 +4,27 @@ use crate::{
     components::name,
 };
 
+mod cloneable;
 mod debuggable;
+mod hooks;
+mod index;
+mod map_entities;
 mod relation;
+#[cfg(feature = "serde")]
+mod replicated;
+mod required;
+mod rollback;
 
+pub use cloneable::*;
 pub use debuggable::*;
+pub use hooks::*;
+pub use index::*;
+pub use map_entities::*;
 pub use relation::*;
+#[cfg(feature = "serde")]
+pub use replicated::*;
+pub use required::*;
+pub use rollback::*;
 
 /// Additional data that can attach itself to a component
 ///
@@ -1,4 +1,9 @@
-use crate::component::{ComponentDesc, ComponentValue};
+use crate::{
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentValue},
+    relation::RelationExt,
+    Entity, World,
+};
 
 use super::Metadata;
 
@@ -8,11 +13,16 @@ component! {
     /// Ensures only one pair of the relation exists.
     pub exclusive: Exclusive,
 
-    ///// Ensures that for every relation `A => B` the relation `B => A` exists.
-    /////
-    ///// This creates a bidirectional graph.
-    //pub symmetric: Symmetric,
+    /// Ensures that for every relation `A => B` the relation `B => A` exists.
+    ///
+    /// This creates a bidirectional graph.
+    pub symmetric: Symmetric,
 
+    /// Determines what happens to the relation pairs targeting an entity when the entity is
+    /// despawned.
+    ///
+    /// Read from the relation component's entity, which means it can be changed at runtime.
+    pub on_target_despawn: OnTargetDespawn,
 }
 
 /// Mutually exclusive relation.
@@ -20,22 +30,92 @@ component! {
 /// Ensures only one pair exists of the relation exists.
 pub struct Exclusive;
 
-///// Ensures that for every relation `A => B` the relation `B => A` exists.
-/////
-///// This creates a bidirectional graph.
-//pub struct Symmetric;
+/// Ensures that for every relation `A => B` the relation `B => A` exists.
+///
+/// This creates a bidirectional graph.
+///
+/// The mirrored relation receives a clone of the relation value whenever the relation is set, and
+/// is removed alongside it.
+///
+/// **Note**: Modifications through queries or [`World::get_mut`] are not mirrored.
+#[derive(Clone)]
+pub struct Symmetric {
+    /// Clones the relation of `id` into `buffer` as a relation pointing back to `id`
+    pub(crate) mirror: fn(&World, Entity, ComponentDesc, &mut ComponentBuffer),
+}
+
+impl Symmetric {
+    pub(crate) fn mirror(&self, world: &World, id: Entity, desc: ComponentDesc) -> ComponentBuffer {
+        let mut buffer = ComponentBuffer::new();
+        (self.mirror)(world, id, desc, &mut buffer);
+        buffer
+    }
+}
+
+/// Policy applied to the subjects of a relation when the target of the relation is despawned.
+///
+/// Defaults to [`OnTargetDespawn::Remove`] when not specified.
+///
+/// Use [`Cascade`] or [`PanicOnTargetDespawn`] to attach a policy through
+/// [`component`](crate::component), or set [`on_target_despawn`] on the relation directly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OnTargetDespawn {
+    /// Remove the relation from the subjects
+    #[default]
+    Remove,
+    /// Despawn the subjects alongside the target
+    Cascade,
+    /// Panic, as the target is not allowed to be despawned while referenced
+    Panic,
+    /// Move the relation to another entity, keeping the relation value.
+    ///
+    /// Falls back to [`OnTargetDespawn::Remove`] if the new target is not alive.
+    Retarget(Entity),
+}
+
+/// Despawns the subjects of the relation when the target is despawned.
+///
+/// See: [`OnTargetDespawn::Cascade`]
+pub struct Cascade;
+
+/// Panics when the target of the relation is despawned while still referenced.
+///
+/// See: [`OnTargetDespawn::Panic`]
+pub struct PanicOnTargetDespawn;
 
 impl<T: ComponentValue> Metadata<T> for Exclusive {
-    fn attach(_: ComponentDesc, buffer: &mut crate::buffer::ComponentBuffer) {
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
         buffer.set(exclusive(), Exclusive);
     }
 }
 
-// impl<T: ComponentValue> Metadata<T> for Symmetric {
-//     fn attach(_: crate::ComponentInfo, buffer: &mut crate::buffer::ComponentBuffer) {
-//         buffer.set(exclusive(), Exclusive);
-//     }
-// }
+impl<T: ComponentValue> Metadata<T> for Cascade {
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(on_target_despawn(), OnTargetDespawn::Cascade);
+    }
+}
+
+impl<T: ComponentValue> Metadata<T> for PanicOnTargetDespawn {
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(on_target_despawn(), OnTargetDespawn::Panic);
+    }
+}
+
+impl<T: ComponentValue + Clone> Metadata<T> for Symmetric {
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(
+            symmetric(),
+            Symmetric {
+                mirror: |world, id, desc, buffer| {
+                    let relation = desc.downcast::<T>();
+                    if let Ok(value) = world.get(id, relation) {
+                        buffer.set(relation.of(id), value.clone());
+                    }
+                },
+            },
+        );
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -45,6 +125,11 @@ mod test {
 
     component! {
         a(id): Arc<()> => [ Exclusive ],
+        neighbour(id): i32 => [ Symmetric ],
+        partner(id): () => [ Exclusive, Symmetric ],
+        owned_by(id): () => [ Cascade ],
+        pinned_to(id): () => [ PanicOnTargetDespawn ],
+        attached_to(id): Arc<()> => [ Exclusive ],
     }
 
     #[test]
@@ -219,4 +304,208 @@ mod test {
         // Ensure relations where dropped
         assert_eq!(Arc::strong_count(&shared), 1);
     }
+
+    #[test]
+    fn symmetric_set() {
+        use crate::{CommandBuffer, Entity, World};
+        use alloc::vec::Vec;
+        use itertools::Itertools;
+
+        let mut world = World::new();
+
+        let neighbours = |world: &World, id: Entity| {
+            world
+                .entity(id)
+                .unwrap()
+                .relations(neighbour)
+                .map(|(target, value)| (target, *value))
+                .sorted()
+                .collect_vec()
+        };
+
+        let id1 = world.spawn();
+        let id2 = world.spawn();
+        let id3 = Entity::builder()
+            .set(neighbour(id1), 1)
+            .set(neighbour(id2), 2)
+            .spawn(&mut world);
+
+        assert_eq!(neighbours(&world, id1), [(id3, 1)]);
+        assert_eq!(neighbours(&world, id2), [(id3, 2)]);
+        assert_eq!(neighbours(&world, id3), [(id1, 1), (id2, 2)]);
+
+        world.set(id1, neighbour(id2), 5).unwrap();
+        assert_eq!(neighbours(&world, id1), [(id2, 5), (id3, 1)]);
+        assert_eq!(neighbours(&world, id2), [(id1, 5), (id3, 2)]);
+
+        // Setting the mirror updates the original
+        world.set(id2, neighbour(id1), 6).unwrap();
+        assert_eq!(neighbours(&world, id1), [(id2, 6), (id3, 1)]);
+
+        world.remove(id3, neighbour(id1)).unwrap();
+        assert_eq!(neighbours(&world, id1), [(id2, 6)]);
+        assert_eq!(neighbours(&world, id3), [(id2, 2)]);
+
+        let mut cmd = CommandBuffer::new();
+        cmd.set(id3, neighbour(id1), 7).remove(id2, neighbour(id3));
+        cmd.apply(&mut world).unwrap();
+
+        assert_eq!(neighbours(&world, id1), [(id2, 6), (id3, 7)]);
+        assert_eq!(neighbours(&world, id2), [(id1, 6)]);
+        assert_eq!(neighbours(&world, id3), [(id1, 7)]);
+
+        world.despawn(id1).unwrap();
+        assert_eq!(neighbours(&world, id2), []);
+        assert_eq!(neighbours(&world, id3), []);
+
+        // Self relations are their own mirror
+        world.set(id2, neighbour(id2), 8).unwrap();
+        assert_eq!(neighbours(&world, id2), [(id2, 8)]);
+        world.clear(id2).unwrap();
+        assert_eq!(neighbours(&world, id2), Vec::<(Entity, i32)>::new());
+    }
+
+    #[test]
+    fn symmetric_exclusive() {
+        use crate::{Entity, World};
+
+        let mut world = World::new();
+
+        let partner_of = |world: &World, id: Entity| {
+            let entity = world.entity(id).unwrap();
+            let mut relations = entity.relations(partner);
+            let res = relations.next().map(|v| v.0);
+            assert!(relations.next().is_none());
+            res
+        };
+
+        let id1 = world.spawn();
+        let id2 = world.spawn();
+        let id3 = world.spawn();
+        let id4 = world.spawn();
+
+        world.set(id1, partner(id2), ()).unwrap();
+        world.set(id3, partner(id4), ()).unwrap();
+
+        assert_eq!(partner_of(&world, id1), Some(id2));
+        assert_eq!(partner_of(&world, id2), Some(id1));
+        assert_eq!(partner_of(&world, id3), Some(id4));
+        assert_eq!(partner_of(&world, id4), Some(id3));
+
+        // Displaces both `id1 => id2` and `id3 => id4`
+        world.set(id1, partner(id3), ()).unwrap();
+
+        assert_eq!(partner_of(&world, id1), Some(id3));
+        assert_eq!(partner_of(&world, id2), None);
+        assert_eq!(partner_of(&world, id3), Some(id1));
+        assert_eq!(partner_of(&world, id4), None);
+    }
+
+    #[test]
+    fn symmetric_merge() {
+        use crate::{Entity, World};
+
+        let mut world = World::new();
+        let existing = world.spawn();
+
+        let mut other = World::new();
+        let collides = other.spawn();
+        let id = Entity::builder()
+            .set(neighbour(collides), 1)
+            .spawn(&mut other);
+
+        let migrated = world.merge_with(&mut other);
+        let collides = migrated.get(collides);
+        let id = migrated.get(id);
+
+        assert_ne!(collides, existing);
+        assert_eq!(world.get(id, neighbour(collides)).as_deref(), Ok(&1));
+        assert_eq!(world.get(collides, neighbour(id)).as_deref(), Ok(&1));
+        assert!(!world.has(existing, neighbour(id)));
+    }
+
+    #[test]
+    fn target_despawn_cascade() {
+        use crate::{components::child_of, World};
+
+        let mut world = World::new();
+
+        let root = world.spawn();
+        let a = Entity::builder().set(owned_by(root), ()).spawn(&mut world);
+        let b = Entity::builder().set(owned_by(a), ()).spawn(&mut world);
+        let c = Entity::builder()
+            .set(owned_by(a), ())
+            .set(owned_by(b), ())
+            .spawn(&mut world);
+        let other = Entity::builder().set(child_of(a), ()).spawn(&mut world);
+
+        world.despawn(root).unwrap();
+
+        assert!(!world.is_alive(a));
+        assert!(!world.is_alive(b));
+        assert!(!world.is_alive(c));
+        // Relations without a policy are removed
+        assert!(world.is_alive(other));
+        assert!(!world.has(other, child_of(a)));
+
+        // The policy can be changed at runtime
+        world
+            .set(child_of.id(), on_target_despawn(), OnTargetDespawn::Cascade)
+            .unwrap();
+
+        let parent = world.spawn();
+        let child = Entity::builder()
+            .set(child_of(parent), ())
+            .spawn(&mut world);
+
+        world.despawn(parent).unwrap();
+        assert!(!world.is_alive(child));
+    }
+
+    #[test]
+    #[should_panic(expected = "pinned_to")]
+    fn target_despawn_panic() {
+        let mut world = World::new();
+
+        let target = world.spawn();
+        Entity::builder()
+            .set(pinned_to(target), ())
+            .spawn(&mut world);
+
+        let _ = world.despawn(target);
+    }
+
+    #[test]
+    fn target_despawn_retarget() {
+        let mut world = World::new();
+
+        let fallback = world.spawn();
+        world
+            .set(
+                attached_to.id(),
+                on_target_despawn(),
+                OnTargetDespawn::Retarget(fallback),
+            )
+            .unwrap();
+
+        let value = Arc::new(());
+        let target = world.spawn();
+        let id = Entity::builder()
+            .set(attached_to(target), value.clone())
+            .spawn(&mut world);
+
+        world.despawn(target).unwrap();
+
+        assert!(!world.has(id, attached_to(target)));
+        assert!(Arc::ptr_eq(
+            &*world.get(id, attached_to(fallback)).unwrap(),
+            &value
+        ));
+
+        // The relation is removed if the new target is gone
+        world.despawn(fallback).unwrap();
+        assert!(world.is_alive(id));
+        assert!(!world.has(id, attached_to(fallback)));
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
 }
@@ -0,0 +1,46 @@
+use serde::Serialize;
+
+use crate::{
+    archetype::ArchetypeStorage,
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentValue},
+};
+
+use super::Metadata;
+
+component! {
+    /// Replicates the component to clients
+    pub replicated: Replicated,
+}
+
+/// Replicates the component to the clients of a
+/// [`ReplicationServer`](crate::serialize::replication::ReplicationServer).
+///
+/// The component is sent using its name, which must be registered in the
+/// [`DeserializeContext`](crate::serialize::DeserializeContext) of the client.
+#[derive(Clone)]
+pub struct Replicated {
+    pub(crate) serialize:
+        for<'x> fn(&'x ArchetypeStorage, usize) -> &'x dyn erased_serde::Serialize,
+}
+
+impl<T> Metadata<T> for Replicated
+where
+    T: ComponentValue + Serialize,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        fn ser<T: ComponentValue + Serialize>(
+            storage: &ArchetypeStorage,
+            slot: usize,
+        ) -> &dyn erased_serde::Serialize {
+            &storage.downcast_ref::<T>()[slot]
+        }
+
+        buffer.set(
+            replicated(),
+            Replicated {
+                serialize: ser::<T>,
+            },
+        );
+    }
+}
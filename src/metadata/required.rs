@@ -0,0 +1,170 @@
+use core::marker::PhantomData;
+
+use alloc::collections::BTreeMap;
+use itertools::Itertools;
+use smallvec::{smallvec, SmallVec};
+
+use crate::{
+    archetype::{ArchetypeStorage, BatchSpawn},
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentKey, ComponentValue},
+};
+
+use super::Metadata;
+
+component! {
+    /// Components which are inserted alongside the component if missing
+    pub requires: Requirements,
+}
+
+/// A set of components which another component depends on.
+///
+/// Attach to a component using [`Requires`].
+pub trait RequiredComponents: 'static {
+    /// Inserts the required components with their initial values into `buffer`.
+    ///
+    /// Components which already exist on the entity are discarded.
+    fn insert(buffer: &mut ComponentBuffer);
+}
+
+/// Ensures the [`RequiredComponents`] of `R` exist whenever the component is added to an entity.
+///
+/// Missing components are inserted with the values provided by `R`, which in turn insert their
+/// own requirements.
+///
+/// ```rust
+/// # use flax::{*, buffer::ComponentBuffer};
+/// struct RigidBody;
+///
+/// impl RequiredComponents for RigidBody {
+///     fn insert(buffer: &mut ComponentBuffer) {
+///         buffer.set(velocity(), 0.0);
+///         buffer.set(mass(), 1.0);
+///     }
+/// }
+///
+/// component! {
+///     rigid_body: () => [ Requires<RigidBody> ],
+///     velocity: f32,
+///     mass: f32,
+/// }
+///
+/// let mut world = World::new();
+///
+/// let id = Entity::builder()
+///     .set_default(rigid_body())
+///     .set(mass(), 5.0)
+///     .spawn(&mut world);
+///
+/// assert_eq!(world.get_copy(id, velocity()), Ok(0.0));
+/// assert_eq!(world.get_copy(id, mass()), Ok(5.0));
+/// ```
+pub struct Requires<R> {
+    _marker: PhantomData<R>,
+}
+
+impl<T, R> Metadata<T> for Requires<R>
+where
+    T: ComponentValue,
+    R: RequiredComponents,
+{
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        match buffer.get_mut(requires()) {
+            Some(requirements) => requirements.inserts.push(R::insert),
+            None => {
+                buffer.set(
+                    requires(),
+                    Requirements {
+                        inserts: smallvec![R::insert as fn(&mut ComponentBuffer)],
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// The type erased [`RequiredComponents`] of a component
+#[derive(Clone)]
+pub struct Requirements {
+    inserts: SmallVec<[fn(&mut ComponentBuffer); 1]>,
+}
+
+impl Requirements {
+    fn insert(&self, buffer: &mut ComponentBuffer) {
+        for insert in &self.inserts {
+            (insert)(buffer)
+        }
+    }
+}
+
+/// Returns true if the component requires other components
+pub(crate) fn has_requirements(desc: ComponentDesc) -> bool {
+    desc.meta_ref().has(requires())
+}
+
+/// Collects the components transitively required by `components` which are not satisfied by
+/// `has`.
+pub(crate) fn required_components(
+    components: impl IntoIterator<Item = ComponentDesc>,
+    has: impl Fn(ComponentKey) -> bool,
+) -> ComponentBuffer {
+    let mut stack = components
+        .into_iter()
+        .filter(|&v| has_requirements(v))
+        .collect_vec();
+
+    let mut required = ComponentBuffer::new();
+
+    while let Some(desc) = stack.pop() {
+        let Some(requirements) = desc.meta_ref().get(requires()) else {
+            continue;
+        };
+
+        let mut buffer = ComponentBuffer::new();
+        requirements.insert(&mut buffer);
+
+        for (desc, value) in buffer.drain() {
+            unsafe {
+                if has(desc.key()) {
+                    desc.drop(value);
+                } else if required.set_missing_dyn(desc, value) {
+                    stack.push(desc);
+                }
+            }
+        }
+    }
+
+    required
+}
+
+/// Inserts the components required by the components of `buffer` which are missing
+pub(crate) fn append_required(buffer: &mut ComponentBuffer) {
+    let mut required = required_components(buffer.components().copied(), |key| buffer.has_key(key));
+
+    buffer.append(&mut required);
+}
+
+/// Inserts the components required by the components of `batch` which are missing
+pub(crate) fn append_required_batch(batch: &mut BatchSpawn) {
+    if !batch.components().any(has_requirements) {
+        return;
+    }
+
+    let mut columns = BTreeMap::new();
+    // Each entity receives its own values
+    for _ in 0..batch.len() {
+        let mut required = required_components(batch.components(), |key| batch.has(key));
+
+        for (desc, value) in required.drain() {
+            let column = columns
+                .entry(desc.key())
+                .or_insert_with(|| ArchetypeStorage::with_capacity(desc, batch.len()));
+
+            unsafe { column.extend(value, 1) }
+        }
+    }
+
+    for (_, column) in columns {
+        batch.append(column).expect("Batch is complete");
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{
+    buffer::ComponentBuffer,
+    component::{ComponentDesc, ComponentValue},
+};
+
+use super::Metadata;
+
+component! {
+    /// Excludes the component from world snapshots
+    pub no_rollback: NoRollback,
+}
+
+/// Excludes the component from [`WorldSnapshot`](crate::snapshot::WorldSnapshot)s.
+///
+/// The current value is kept when a snapshot is restored, as long as the entity exists in the
+/// snapshot.
+pub struct NoRollback;
+
+impl<T: ComponentValue> Metadata<T> for NoRollback {
+    fn attach(_: ComponentDesc, buffer: &mut ComponentBuffer) {
+        buffer.set(no_rollback(), NoRollback);
+    }
+}
@@ -0,0 +1,461 @@
+use core::{fmt::Debug, sync::atomic::AtomicU32};
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use atomic_refcell::{AtomicRefCell, AtomicRefMut};
+
+use crate::{
+    archetype::{Archetype, ArchetypeId, ArchetypeStorage, ChangeKind, Slice, Slot},
+    component::ComponentKey,
+    events::{EventData, EventSubscriber},
+    fetch::{FetchAccessData, PreparedFetch},
+    filter::{next_slice, Filtered},
+    system::{Access, AccessKind},
+    Entity, Fetch, FetchItem, World,
+};
+
+use super::{
+    borrow::QueryBorrowState,
+    located::{LocatedIter, PreparedArchetypes},
+    ArchetypeSearcher, QueryStrategy,
+};
+
+/// Caches the entities matched by the query, and only re-evaluates the filters of the entities
+/// which changed since the last borrow.
+///
+/// This makes iterating a sparse set of entities matched by a value filter, such as
+/// `health().le(0.0)`, proportional to the number of matches rather than the number of entities.
+///
+/// Entities are re-evaluated when a component accessed by the query is modified, which is detected
+/// through the change lists, or when a component is added or removed, which is detected through an
+/// [`EventSubscriber`] registered in the world.
+///
+/// Only the archetypes which contain the components required by the query are subscribed to, and
+/// only additions and removals of components which may affect whether an entity is matched are
+/// recorded.
+///
+/// **Note**: the query must only be used with the world it was created for.
+///
+/// See [`Query::cached`](crate::Query::cached)
+pub struct Cached {
+    subscriber: Arc<Dirty>,
+    world: Weak<AtomicU32>,
+    state: State,
+}
+
+impl Cached {
+    /// Creates a new cached query strategy for `fetch`, subscribing to the structural changes of
+    /// `world`
+    pub fn new<'w>(world: &mut World, fetch: &impl Fetch<'w>) -> Self {
+        let mut searcher = ArchetypeSearcher::default();
+        fetch.searcher(&mut searcher);
+
+        let subscriber = Arc::new(Dirty::default());
+        world.subscribe(DirtySubscriber {
+            dirty: subscriber.clone(),
+            required: searcher.required,
+        });
+
+        Self {
+            subscriber,
+            world: world.handle(),
+            state: Default::default(),
+        }
+    }
+}
+
+impl Debug for Cached {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Cached")
+            .field("matches", &self.state.matches.len())
+            .finish()
+    }
+}
+
+/// Entities which had components added or removed, and may have moved to another archetype
+#[derive(Default)]
+struct Dirty {
+    ids: AtomicRefCell<BTreeSet<Entity>>,
+    /// The components which may change whether an entity is matched when added or removed.
+    ///
+    /// Only updated when borrowing, which can not happen at the same time as a structural change.
+    keys: AtomicRefCell<BTreeSet<ComponentKey>>,
+}
+
+impl Dirty {
+    fn lock(&self) -> AtomicRefMut<'_, BTreeSet<Entity>> {
+        loop {
+            if let Ok(ids) = self.ids.try_borrow_mut() {
+                break ids;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+}
+
+struct DirtySubscriber {
+    dirty: Arc<Dirty>,
+    /// The components required by the query, which a matched archetype, and any archetype an
+    /// entity can move to a matched archetype from by removing components, contains.
+    required: Vec<ComponentKey>,
+}
+
+impl DirtySubscriber {
+    fn mark(&self, event: &EventData) {
+        if self.dirty.keys.borrow().contains(&event.key) {
+            self.dirty.lock().extend(event.ids.iter().copied())
+        }
+    }
+}
+
+impl EventSubscriber for DirtySubscriber {
+    fn on_added(&self, _: &ArchetypeStorage, event: &EventData) {
+        self.mark(event)
+    }
+
+    fn on_modified(&self, _: &EventData) {}
+
+    fn on_removed(&self, _: &ArchetypeStorage, event: &EventData) {
+        self.mark(event)
+    }
+
+    fn is_connected(&self) -> bool {
+        Arc::strong_count(&self.dirty) > 1
+    }
+
+    fn matches_arch(&self, arch: &Archetype) -> bool {
+        self.required.iter().all(|&key| arch.has(key))
+    }
+}
+
+#[derive(Default, Debug)]
+struct State {
+    archetypes: Vec<ArchetypeId>,
+    archetypes_index: BTreeMap<ArchetypeId, usize>,
+    /// The components accessed by the query in each archetype
+    components: Vec<Vec<ComponentKey>>,
+    /// The components which may change whether an entity is matched when added or removed
+    keys: BTreeSet<ComponentKey>,
+    /// Entities which matched the filters when last evaluated
+    matches: BTreeSet<Entity>,
+    /// The archetype index and slot of each match, in archetype order
+    locations: Vec<(usize, Slot)>,
+    /// Changes up to and including this tick have been evaluated
+    tick: Option<u32>,
+}
+
+impl State {
+    fn find_archetypes<'w, Q: Fetch<'w>>(&mut self, world: &World, fetch: &'w Q) {
+        profile_function!();
+        self.archetypes.clear();
+        self.archetypes_index.clear();
+        self.components.clear();
+
+        let mut searcher = ArchetypeSearcher::default();
+        fetch.searcher(&mut searcher);
+
+        // Adding a required component may move an entity into a matched archetype
+        self.keys = searcher.required.iter().copied().collect();
+
+        // Archetypes which contain the required components, but are rejected by the filters
+        let mut rejected = Vec::new();
+
+        searcher.find_archetypes(&world.archetypes, |arch_id, arch| {
+            let data = FetchAccessData {
+                world,
+                arch,
+                arch_id,
+            };
+
+            if !fetch.filter_arch(data) {
+                rejected.push(arch);
+                return false;
+            }
+
+            let mut accesses = Vec::new();
+            fetch.access(data, &mut accesses);
+
+            let components = accesses
+                .into_iter()
+                .filter_map(|v| match v.kind {
+                    AccessKind::Archetype { id, component } if id == arch_id => Some(component),
+                    _ => None,
+                })
+                .collect::<BTreeSet<_>>();
+
+            // Modifications need to be known to re-evaluate the filters
+            for &key in &components {
+                if let Some(cell) = arch.cell(key) {
+                    cell.data().borrow().changes.set_track_modified();
+                }
+            }
+
+            let idx = self.archetypes.len();
+            self.keys.extend(components.iter().copied());
+            self.archetypes.push(arch_id);
+            self.components.push(components.into_iter().collect());
+
+            let existing = self.archetypes_index.insert(arch_id, idx);
+            debug_assert_eq!(existing, None, "duplicate archetype");
+
+            false
+        });
+
+        // An entity moving between a rejected and a matched archetype adds or removes each
+        // component which differs between them. Moving to a new archetype invalidates the state
+        // as a whole.
+        for &arch_id in &self.archetypes {
+            let matched = world.archetypes.get(arch_id).components();
+            for arch in &rejected {
+                let rejected = arch.components();
+                self.keys.extend(
+                    matched
+                        .keys()
+                        .filter(|v| !rejected.contains_key(v))
+                        .chain(rejected.keys().filter(|v| !matched.contains_key(v))),
+                );
+            }
+        }
+    }
+
+    /// Evaluates the filters of all entities in the matched archetypes
+    fn evaluate_all<'w, Q: Fetch<'w>, F: Fetch<'w>>(&mut self, state: &QueryBorrowState<'w, Q, F>) {
+        profile_function!();
+        self.matches.clear();
+
+        for &arch_id in &self.archetypes {
+            let arch = state.world.archetypes.get(arch_id);
+            let Some(mut p) = state.prepare_fetch(arch_id, arch) else {
+                continue;
+            };
+
+            let mut slots = arch.slots();
+            while let Some(slice) = next_slice(&mut slots, &mut p.fetch) {
+                self.matches
+                    .extend(arch.entities()[slice.as_range()].iter().copied());
+            }
+        }
+    }
+
+    /// Re-evaluates the filters of the entities which were added, removed, or modified since the
+    /// last evaluation.
+    fn evaluate_changed<'w, Q: Fetch<'w>, F: Fetch<'w>>(
+        &mut self,
+        state: &QueryBorrowState<'w, Q, F>,
+        dirty: BTreeSet<Entity>,
+        tick: u32,
+    ) {
+        profile_function!();
+        let world = state.world;
+
+        let mut candidates: BTreeMap<usize, BTreeSet<Slot>> = BTreeMap::new();
+
+        for id in dirty {
+            self.matches.remove(&id);
+
+            let Ok(loc) = world.location(id) else {
+                continue;
+            };
+
+            if let Some(&arch_index) = self.archetypes_index.get(&loc.arch_id) {
+                candidates.entry(arch_index).or_default().insert(loc.slot);
+            }
+        }
+
+        for (arch_index, &arch_id) in self.archetypes.iter().enumerate() {
+            let arch = world.archetypes.get(arch_id);
+
+            for &key in &self.components[arch_index] {
+                let Some(cell) = arch.cell(key) else {
+                    continue;
+                };
+
+                let data = cell.data().borrow();
+                for change in data.changes.get(ChangeKind::Modified).iter() {
+                    if change.tick <= tick {
+                        continue;
+                    }
+
+                    let slots = candidates.entry(arch_index).or_default();
+                    for slot in change.slice.iter() {
+                        self.matches.remove(&arch.entities()[slot]);
+                        slots.insert(slot);
+                    }
+                }
+            }
+        }
+
+        for (arch_index, slots) in candidates {
+            let arch_id = self.archetypes[arch_index];
+            let arch = world.archetypes.get(arch_id);
+            let Some(mut p) = state.prepare_fetch(arch_id, arch) else {
+                continue;
+            };
+
+            for slot in slots {
+                // Safety: the fetch is not borrowed
+                if !unsafe { p.fetch.filter_slots(Slice::single(slot)) }.is_empty() {
+                    self.matches.insert(arch.entities()[slot]);
+                }
+            }
+        }
+    }
+
+    /// Resolves the current location of each match
+    fn resolve(&mut self, world: &World) {
+        self.locations.clear();
+        self.locations.extend(self.matches.iter().filter_map(|&id| {
+            let loc = world.location(id).ok()?;
+            let &arch_index = self.archetypes_index.get(&loc.arch_id)?;
+            Some((arch_index, loc.slot))
+        }));
+
+        self.locations.sort_unstable();
+    }
+}
+
+impl<'w, Q, F> QueryStrategy<'w, Q, F> for Cached
+where
+    Q: 'w + Fetch<'w>,
+    F: 'w + Fetch<'w>,
+{
+    type Borrow = CachedBorrow<'w, Q, F>;
+
+    fn borrow(&'w mut self, query_state: QueryBorrowState<'w, Q, F>, dirty: bool) -> Self::Borrow {
+        assert!(
+            self.world.ptr_eq(&query_state.world.handle()),
+            "Cached query used with a different world than it was created for"
+        );
+
+        let ids = core::mem::take(&mut *self.subscriber.lock());
+
+        match self.state.tick {
+            Some(tick) if !dirty => self.state.evaluate_changed(&query_state, ids, tick),
+            _ => {
+                self.state
+                    .find_archetypes(query_state.world, query_state.fetch);
+                self.state.evaluate_all(&query_state);
+
+                *self.subscriber.keys.borrow_mut() = self.state.keys.clone();
+            }
+        }
+
+        // Modifications made through this borrow are tagged with the new tick, and must be
+        // evaluated next time.
+        self.state.tick = Some(if Q::MUTABLE {
+            query_state.new_tick.saturating_sub(1)
+        } else {
+            query_state.new_tick
+        });
+
+        self.state.resolve(query_state.world);
+
+        CachedBorrow {
+            cached: &self.state,
+            state: query_state,
+            prepared: Default::default(),
+        }
+    }
+
+    fn access(&self, world: &'w World, fetch: &'w Filtered<Q, F>, dst: &mut Vec<Access>) {
+        let mut state = State::default();
+        state.find_archetypes(world, fetch);
+
+        state.archetypes.iter().for_each(|&arch_id| {
+            let arch = world.archetypes.get(arch_id);
+            let data = FetchAccessData {
+                world,
+                arch,
+                arch_id,
+            };
+
+            fetch.access(data, dst);
+        });
+
+        dst.push(Access {
+            kind: AccessKind::World,
+            mutable: false,
+        });
+    }
+}
+
+/// Borrowed state for [`Cached`] strategy
+pub struct CachedBorrow<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    cached: &'w State,
+    state: QueryBorrowState<'w, Q, F>,
+    prepared: PreparedArchetypes<'w, Q, F>,
+}
+
+impl<'w, 'q, Q, F> IntoIterator for &'q mut CachedBorrow<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    type Item = <Q as FetchItem<'q>>::Item;
+
+    type IntoIter = CachedIter<'w, 'q, Q, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'w, Q, F> CachedBorrow<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    /// Iterate all items matched by query and filter.
+    pub fn iter<'q>(&'q mut self) -> CachedIter<'w, 'q, Q, F> {
+        CachedIter {
+            iter: self
+                .prepared
+                .iter(&self.state, &self.cached.archetypes, &self.cached.locations),
+        }
+    }
+
+    /// Returns the number of cached matches.
+    ///
+    /// Filters which depend on the borrow, such as change filters, are applied during iteration
+    /// and may yield fewer items.
+    pub fn len(&self) -> usize {
+        self.cached.locations.len()
+    }
+
+    /// Returns true if there are no cached matches
+    pub fn is_empty(&self) -> bool {
+        self.cached.locations.is_empty()
+    }
+}
+
+/// Iterates the cached matches of a query.
+pub struct CachedIter<'w, 'q, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+    'w: 'q,
+{
+    iter: LocatedIter<'w, 'q, Q, F>,
+}
+
+impl<'w, 'q, Q, F> Iterator for CachedIter<'w, 'q, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+    'w: 'q,
+{
+    type Item = <Q::Prepared as PreparedFetch<'q>>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
@@ -0,0 +1,104 @@
+use core::slice;
+
+use smallvec::SmallVec;
+
+use crate::{
+    archetype::{ArchetypeId, Slice, Slot},
+    fetch::PreparedFetch,
+    Fetch,
+};
+
+use super::{borrow::QueryBorrowState, PreparedArchetype};
+
+type Prepared<'w, Q, F> =
+    Option<PreparedArchetype<'w, <Q as Fetch<'w>>::Prepared, <F as Fetch<'w>>::Prepared>>;
+
+/// Prepared archetypes, by the archetype index, for strategies which visit single entities by
+/// their location rather than whole archetypes.
+pub(crate) struct PreparedArchetypes<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    prepared: SmallVec<[Prepared<'w, Q, F>; 8]>,
+}
+
+impl<'w, Q, F> Default for PreparedArchetypes<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    fn default() -> Self {
+        Self {
+            prepared: Default::default(),
+        }
+    }
+}
+
+impl<'w, Q, F> PreparedArchetypes<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    /// Iterate the entities at `locations`, which refer to an index in `archetypes` and a slot.
+    ///
+    /// The archetypes are prepared on first use.
+    pub(crate) fn iter<'q>(
+        &'q mut self,
+        state: &QueryBorrowState<'w, Q, F>,
+        archetypes: &[ArchetypeId],
+        locations: &'q [(usize, Slot)],
+    ) -> LocatedIter<'w, 'q, Q, F> {
+        if self.prepared.len() != archetypes.len() {
+            self.prepared = archetypes
+                .iter()
+                .map(|&arch_id| {
+                    let arch = state.world.archetypes.get(arch_id);
+                    state.prepare_fetch(arch_id, arch)
+                })
+                .collect();
+        }
+
+        LocatedIter {
+            locations: locations.iter(),
+            prepared: &mut self.prepared[..],
+        }
+    }
+}
+
+/// Iterates entities in the order of their locations
+pub(crate) struct LocatedIter<'w, 'q, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+    'w: 'q,
+{
+    locations: slice::Iter<'q, (usize, Slot)>,
+    prepared: &'q mut [Prepared<'w, Q, F>],
+}
+
+impl<'w, 'q, Q, F> Iterator for LocatedIter<'w, 'q, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+    'w: 'q,
+{
+    type Item = <Q::Prepared as PreparedFetch<'q>>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let &(arch_index, slot) = self.locations.next()?;
+            let Some(p) = &mut self.prepared[arch_index] else {
+                continue;
+            };
+
+            // Promote the borrow of the fetch to 'q
+            // This is safe because each slot is only visited once
+            let p = unsafe { &mut *(p as *mut PreparedArchetype<_, _>) };
+
+            if let Some(mut chunk) = unsafe { p.create_chunk(Slice::single(slot)) } {
+                return chunk.next();
+            }
+        }
+    }
+}
@@ -1,18 +1,22 @@
 mod borrow;
+mod cached;
 mod data;
 mod dfs;
 mod difference;
 mod entity;
 mod iter;
+mod located;
 mod one;
 mod planar;
+mod removed;
 mod searcher;
+mod sorted;
 mod topo;
 mod walk;
 use itertools::Itertools;
 pub use walk::{Children, DfsIter, GraphBorrow, GraphQuery, Node};
 
-use core::fmt::Debug;
+use core::{cmp::Ordering, fmt::Debug};
 
 use crate::{
     archetype::Slot,
@@ -28,13 +32,17 @@ use alloc::vec::Vec;
 
 use self::borrow::QueryBorrowState;
 pub(crate) use borrow::*;
+pub use cached::{Cached, CachedBorrow, CachedIter};
 pub use data::*;
 pub use dfs::*;
 pub use entity::EntityBorrow;
 pub(crate) use iter::*;
 pub use one::QueryOne;
 pub use planar::*;
+pub(crate) use removed::RemovedDispatcher;
+pub use removed::{Removed, RemovedData};
 pub use searcher::ArchetypeSearcher;
+pub use sorted::{Sorted, SortedBorrow, SortedIter};
 pub use topo::{Topo, TopoBorrow, TopoIter};
 
 /// Similar to [`Query`], except optimized to only fetch a single entity.
@@ -130,7 +138,7 @@ impl<Q> Query<Q, All, Planar> {
         Q: for<'x> Fetch<'x>,
     {
         Self {
-            fetch: Filtered::new(fetch, All, false),
+            fetch: Filtered::new(fetch, All, false, false),
             change_tick: 0,
             strategy: Planar::new(),
             archetype_gen: 0,
@@ -145,6 +153,15 @@ impl<Q> Query<Q, All, Planar> {
         self.archetype_gen = 0;
         self
     }
+
+    /// Include [`prefab`](crate::components::prefab) entities in a planar query.
+    ///
+    /// **Note**: only relevant for the `planar` strategy
+    pub fn with_prefabs(mut self) -> Self {
+        self.fetch.include_prefabs = true;
+        self.archetype_gen = 0;
+        self
+    }
 }
 
 impl<Q, F> Query<Q, F, Planar>
@@ -183,6 +200,95 @@ where
         self.with_strategy(Topo::new(relation))
     }
 
+    /// Transform the query into a query which visits entities in the order of `component`, as
+    /// determined by `cmp`.
+    ///
+    /// The order is cached, and only re-sorted when the sort key changes or entities are added or
+    /// removed, rather than sorting the items on each iteration.
+    ///
+    /// Entities without `component` are not visited.
+    ///
+    /// ```rust
+    /// # use flax::*;
+    /// component! {
+    ///     layer: i32,
+    ///     label: &'static str,
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// for (v, l) in [(2, "foreground"), (0, "background"), (1, "ui")] {
+    ///     Entity::builder().set(layer(), v).set(label(), l).spawn(&mut world);
+    /// }
+    ///
+    /// let mut query = Query::new(label().copied()).sort_by(layer(), |a, b| b.cmp(a));
+    ///
+    /// let labels = query.borrow(&world).iter().collect::<Vec<_>>();
+    /// assert_eq!(labels, ["foreground", "ui", "background"]);
+    /// ```
+    pub fn sort_by<T: ComponentValue>(
+        self,
+        component: Component<T>,
+        cmp: impl Fn(&T, &T) -> Ordering + Send + Sync + 'static,
+    ) -> Query<Q, F, Sorted<T>>
+    where
+        Sorted<T>: for<'w> QueryStrategy<'w, Q, F>,
+    {
+        self.with_strategy(Sorted::new(component, cmp))
+    }
+
+    /// Transform the query into a query which visits entities in ascending order of `component`.
+    ///
+    /// See [`Self::sort_by`]
+    pub fn sort_by_key<T: ComponentValue + Ord>(
+        self,
+        component: Component<T>,
+    ) -> Query<Q, F, Sorted<T>>
+    where
+        Sorted<T>: for<'w> QueryStrategy<'w, Q, F>,
+    {
+        self.sort_by(component, Ord::cmp)
+    }
+
+    /// Transform the query into a query which caches the matched entities, and only re-evaluates
+    /// the filters of entities which changed since the last borrow.
+    ///
+    /// This is useful for value filters matching a small subset of the entities, as iteration
+    /// does not need to visit the entities which are not matched.
+    ///
+    /// The query must only be used with `world`, and panics otherwise.
+    ///
+    /// ```rust
+    /// # use flax::*;
+    /// component! {
+    ///     health: f32,
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let ids = (0..100)
+    ///     .map(|i| Entity::builder().set(health(), i as f32).spawn(&mut world))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let mut query = Query::new(entity_ids())
+    ///     .with_filter(health().le(0.0))
+    ///     .cached(&mut world);
+    ///
+    /// assert_eq!(query.borrow(&world).iter().collect::<Vec<_>>(), [ids[0]]);
+    ///
+    /// world.set(ids[5], health(), -10.0).unwrap();
+    /// world.despawn(ids[0]).unwrap();
+    ///
+    /// assert_eq!(query.borrow(&world).iter().collect::<Vec<_>>(), [ids[5]]);
+    /// ```
+    pub fn cached(self, world: &mut World) -> Query<Q, F, Cached>
+    where
+        Cached: for<'w> QueryStrategy<'w, Q, F>,
+    {
+        let strategy = Cached::new(world, &self.fetch);
+        self.with_strategy(strategy)
+    }
+
     /// Collect all elements in the query into a vector
     pub fn collect_vec<'w, T>(&'w mut self, world: &'w World) -> Vec<T>
     where
@@ -220,6 +326,7 @@ where
                 self.fetch.fetch,
                 self.fetch.filter.push_right(filter),
                 self.fetch.include_components,
+                self.fetch.include_prefabs,
             ),
             change_tick: self.change_tick,
             archetype_gen: 0,
@@ -270,6 +270,14 @@ where
         self.iter_batched().map(|v| v.slots().len()).sum()
     }
 
+    /// Returns true if the query matches no entities
+    pub fn is_empty<'q>(&'q mut self) -> bool
+    where
+        'w: 'q,
+    {
+        self.iter_batched().all(|v| v.slots().is_empty())
+    }
+
     fn prepare_archetype(&mut self, arch_id: ArchetypeId) -> Option<usize> {
         let prepared = &mut self.prepared;
 
@@ -0,0 +1,293 @@
+use core::{
+    any::Any,
+    fmt::{self, Formatter},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+use itertools::Itertools;
+
+use crate::{
+    archetype::ArchetypeStorage,
+    component::{ComponentKey, ComponentValue},
+    events::{EventData, EventSubscriber},
+    system::{Access, AccessKind, AsBorrowed, SystemAccess, SystemContext, SystemData},
+    world::advance_tick,
+    Component, Entity, World,
+};
+
+/// Yields the entities and last values of a component which was removed since the last borrow,
+/// including when the entity was despawned.
+///
+/// Removals are tracked from the first borrow, which thus yields nothing.
+///
+/// Each removal is stamped with the world change tick, and a borrow yields the removals after the
+/// [`World::change_tick`] of the previous borrow, which is returned by [`Self::tick`]. This is the
+/// same baseline as used by [`World::diff`] and the change filters of queries.
+///
+/// See [`Component::removed`]
+///
+/// ```rust
+/// # use flax::*;
+/// component! {
+///     health: f32,
+/// }
+///
+/// let mut world = World::new();
+///
+/// let id = Entity::builder().set(health(), 25.0).spawn(&mut world);
+///
+/// let mut removed = health().removed();
+/// assert_eq!(removed.borrow(&world), []);
+///
+/// world.despawn(id).unwrap();
+///
+/// assert_eq!(removed.borrow(&world), [(id, 25.0)]);
+/// assert_eq!(removed.borrow(&world), []);
+/// ```
+pub struct Removed<T> {
+    component: Component<T>,
+    /// The change tick of the last borrow
+    cursor: Option<Arc<AtomicU32>>,
+}
+
+impl<T: ComponentValue + Clone> Removed<T> {
+    pub(crate) fn new(component: Component<T>) -> Self {
+        Self {
+            component,
+            cursor: None,
+        }
+    }
+
+    /// Returns the entities and values of the component which were removed since the last borrow
+    pub fn borrow(&mut self, world: &World) -> Vec<(Entity, T)> {
+        world.removed.read(world, self.component, &mut self.cursor)
+    }
+
+    /// Returns the change tick of the last borrow, after which the next borrow yields removals.
+    ///
+    /// Returns `None` before the first borrow.
+    pub fn tick(&self) -> Option<u32> {
+        Some(self.cursor.as_ref()?.load(Ordering::Relaxed))
+    }
+}
+
+impl<T> fmt::Debug for Removed<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Removed").field(&self.component).finish()
+    }
+}
+
+impl<T: ComponentValue + Clone> SystemAccess for Removed<T> {
+    fn access(&self, _: &World, dst: &mut Vec<Access>) {
+        dst.push(Access {
+            kind: AccessKind::World,
+            mutable: false,
+        });
+    }
+}
+
+/// Combined reference to a [`Removed`] and a world.
+pub struct RemovedData<'a, T> {
+    world: AtomicRef<'a, World>,
+    removed: &'a mut Removed<T>,
+}
+
+impl<'a, T: ComponentValue + Clone> SystemData<'a> for Removed<T> {
+    type Value = RemovedData<'a, T>;
+
+    fn acquire(&'a mut self, ctx: &'a SystemContext<'_, '_, '_>) -> Self::Value {
+        RemovedData {
+            world: ctx.world(),
+            removed: self,
+        }
+    }
+
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Removed<{}>", self.component.name())
+    }
+}
+
+impl<T: ComponentValue + Clone> RemovedData<'_, T> {
+    /// Returns the entities and values of the component which were removed since the last borrow
+    pub fn borrow(&mut self) -> Vec<(Entity, T)> {
+        self.removed.borrow(&self.world)
+    }
+}
+
+impl<'a, T: ComponentValue + Clone> AsBorrowed<'a> for RemovedData<'_, T> {
+    type Borrowed = Vec<(Entity, T)>;
+
+    fn as_borrowed(&'a mut self) -> Self::Borrowed {
+        self.borrow()
+    }
+}
+
+/// Type erased log of removed values of a single component
+trait DynRemovedLog: Send + Sync {
+    fn record(&mut self, tick: u32, storage: &ArchetypeStorage, event: &EventData);
+    /// Discards the values removed after `tick`
+    fn discard(&mut self, tick: u32);
+    fn is_connected(&self) -> bool;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct RemovedLog<T> {
+    values: VecDeque<(u32, Entity, T)>,
+    readers: Vec<Weak<AtomicU32>>,
+}
+
+impl<T: Clone> RemovedLog<T> {
+    fn new() -> Self {
+        Self {
+            values: VecDeque::new(),
+            readers: Vec::new(),
+        }
+    }
+
+    /// Drops the values which have been seen by all readers
+    fn prune(&mut self) {
+        self.readers.retain(|v| v.strong_count() > 0);
+
+        let Some(seen) = self
+            .readers
+            .iter()
+            .filter_map(|v| Some(v.upgrade()?.load(Ordering::Relaxed)))
+            .min()
+        else {
+            self.values.clear();
+            return;
+        };
+
+        while self.values.front().is_some_and(|v| v.0 <= seen) {
+            self.values.pop_front();
+        }
+    }
+}
+
+impl<T: ComponentValue + Clone> DynRemovedLog for RemovedLog<T> {
+    fn record(&mut self, tick: u32, storage: &ArchetypeStorage, event: &EventData) {
+        let values = storage.downcast_ref::<T>();
+        self.values.extend(
+            event
+                .ids
+                .iter()
+                .zip_eq(event.slots.iter())
+                .map(|(&id, slot)| (tick, id, values[slot].clone())),
+        );
+    }
+
+    fn discard(&mut self, tick: u32) {
+        self.values.retain(|v| v.0 <= tick)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.readers.iter().any(|v| v.strong_count() > 0)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Records the values of removed components for [`Removed`]
+pub(crate) struct RemovedDispatcher {
+    change_tick: Arc<AtomicU32>,
+    logs: AtomicRefCell<BTreeMap<ComponentKey, Box<dyn DynRemovedLog>>>,
+}
+
+impl RemovedDispatcher {
+    pub(crate) fn new(change_tick: Arc<AtomicU32>) -> Self {
+        Self {
+            change_tick,
+            logs: AtomicRefCell::new(BTreeMap::new()),
+        }
+    }
+
+    fn lock(&self) -> AtomicRefMut<'_, BTreeMap<ComponentKey, Box<dyn DynRemovedLog>>> {
+        // Removals may be read concurrently by systems running in parallel
+        loop {
+            if let Ok(logs) = self.logs.try_borrow_mut() {
+                break logs;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Returns the values removed since `cursor`, and advances it.
+    ///
+    /// Starts tracking the component if `cursor` is not yet initialized.
+    fn read<T: ComponentValue + Clone>(
+        &self,
+        world: &World,
+        component: Component<T>,
+        cursor: &mut Option<Arc<AtomicU32>>,
+    ) -> Vec<(Entity, T)> {
+        let mut logs = self.lock();
+        // Removals after this are stamped with a later tick
+        let tick = world.change_tick();
+
+        let log = logs
+            .entry(component.key())
+            .or_insert_with(|| Box::new(RemovedLog::<T>::new()))
+            .as_any_mut()
+            .downcast_mut::<RemovedLog<T>>()
+            .expect("Mismatched removed component type");
+
+        let Some(cursor) = cursor else {
+            let reader = Arc::new(AtomicU32::new(tick));
+            log.readers.push(Arc::downgrade(&reader));
+            *cursor = Some(reader);
+            return Vec::new();
+        };
+
+        let seen = cursor.swap(tick, Ordering::Relaxed);
+        let values = log
+            .values
+            .iter()
+            .filter(|v| v.0 > seen)
+            .map(|(_, id, value)| (*id, value.clone()))
+            .collect_vec();
+
+        log.prune();
+        values
+    }
+
+    /// Discards the values removed after `tick`
+    pub(crate) fn discard(&self, tick: u32) {
+        self.lock().values_mut().for_each(|log| log.discard(tick))
+    }
+}
+
+impl EventSubscriber for RemovedDispatcher {
+    fn on_added(&self, _: &ArchetypeStorage, _: &EventData) {}
+
+    fn on_modified(&self, _: &EventData) {}
+
+    fn on_removed(&self, storage: &ArchetypeStorage, event: &EventData) {
+        let mut logs = self.lock();
+
+        let Some(log) = logs.get_mut(&event.key) else {
+            return;
+        };
+
+        // All readers were dropped
+        if !log.is_connected() {
+            logs.remove(&event.key);
+            return;
+        }
+
+        log.record(advance_tick(&self.change_tick), storage, event);
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,320 @@
+use core::{cmp::Ordering, fmt::Debug};
+
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use itertools::Itertools;
+
+use crate::{
+    archetype::{ArchetypeId, ChangeKind, Slot},
+    component::ComponentValue,
+    fetch::{FetchAccessData, PreparedFetch},
+    filter::Filtered,
+    system::{Access, AccessKind},
+    Component, Entity, Fetch, FetchItem, World,
+};
+
+use super::{
+    borrow::QueryBorrowState,
+    located::{LocatedIter, PreparedArchetypes},
+    ArchetypeSearcher, QueryStrategy,
+};
+
+type Comparator<T> = Arc<dyn Fn(&T, &T) -> Ordering + Send + Sync>;
+
+/// Visit entities across all matched archetypes in the order of a component.
+///
+/// The order is cached and only re-sorted when the sort key is added, modified, or when the set of
+/// matched entities changes.
+///
+/// Entities with an equal sort key are visited in the order of their ids.
+///
+/// See [`Query::sort_by`](crate::Query::sort_by)
+pub struct Sorted<T> {
+    component: Component<T>,
+    cmp: Comparator<T>,
+    state: State,
+}
+
+impl<T: ComponentValue> Sorted<T> {
+    /// Sort the entities by `component` using `cmp`
+    pub fn new(
+        component: Component<T>,
+        cmp: impl Fn(&T, &T) -> Ordering + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            component,
+            cmp: Arc::new(cmp),
+            state: Default::default(),
+        }
+    }
+}
+
+impl<T: ComponentValue> Debug for Sorted<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Sorted").field(&self.component).finish()
+    }
+}
+
+#[derive(Default, Debug)]
+struct State {
+    archetypes: Vec<ArchetypeId>,
+    archetypes_index: BTreeMap<ArchetypeId, usize>,
+    /// The matched entities, in sorted order
+    order: Vec<Entity>,
+    /// The archetype index and slot of each entity in `order`
+    locations: Vec<(usize, Slot)>,
+    /// The most recent change to the sort key when the entities were last sorted
+    sorted_tick: Option<u32>,
+}
+
+impl State {
+    fn find_archetypes<'w, Q: Fetch<'w>, T: ComponentValue>(
+        &mut self,
+        world: &World,
+        fetch: &'w Q,
+        component: Component<T>,
+    ) {
+        profile_function!();
+        self.archetypes.clear();
+        self.archetypes_index.clear();
+
+        let mut searcher = ArchetypeSearcher::default();
+        fetch.searcher(&mut searcher);
+        searcher.add_required(component.key());
+
+        searcher.find_archetypes(&world.archetypes, |arch_id, arch| {
+            if !fetch.filter_arch(FetchAccessData {
+                world,
+                arch,
+                arch_id,
+            }) {
+                return false;
+            }
+
+            // Modifications of the sort key need to be known to invalidate the order
+            if let Some(values) = arch.borrow::<T>(component.key()) {
+                values.changes().set_track_modified();
+            }
+
+            let idx = self.archetypes.len();
+            self.archetypes.push(arch_id);
+
+            let existing = self.archetypes_index.insert(arch_id, idx);
+            debug_assert_eq!(existing, None, "duplicate archetype");
+
+            false
+        });
+    }
+
+    /// Makes sure the order is up to date and resolves the current location of each entity
+    fn update<T: ComponentValue>(
+        &mut self,
+        world: &World,
+        component: Component<T>,
+        cmp: &dyn Fn(&T, &T) -> Ordering,
+    ) {
+        // Addition and modification of the sort key are both recorded as modifications
+        let latest = self
+            .archetypes
+            .iter()
+            .filter_map(|&arch_id| world.archetypes.get(arch_id).borrow::<T>(component.key()))
+            .flat_map(|values| {
+                values
+                    .changes()
+                    .get(ChangeKind::Modified)
+                    .iter()
+                    .map(|v| v.tick)
+                    .max()
+            })
+            .max()
+            .unwrap_or_default();
+
+        if self.sorted_tick.is_none_or(|tick| latest > tick) || !self.resolve(world) {
+            self.sort(world, component, cmp);
+            self.sorted_tick = Some(latest);
+
+            let resolved = self.resolve(world);
+            debug_assert!(resolved, "sorted entities do not match the archetypes");
+        }
+    }
+
+    fn sort<T: ComponentValue>(
+        &mut self,
+        world: &World,
+        component: Component<T>,
+        cmp: &dyn Fn(&T, &T) -> Ordering,
+    ) {
+        profile_function!();
+        let values = self
+            .archetypes
+            .iter()
+            .map(|&arch_id| {
+                let arch = world.archetypes.get(arch_id);
+                let values = arch
+                    .borrow::<T>(component.key())
+                    .expect("Sort key is required");
+
+                (arch.entities(), values)
+            })
+            .collect_vec();
+
+        let mut keys = values
+            .iter()
+            .flat_map(|(ids, values)| ids.iter().zip(values.get()))
+            .collect_vec();
+
+        keys.sort_by(|a, b| cmp(a.1, b.1).then_with(|| a.0.cmp(b.0)));
+
+        self.order.clear();
+        self.order.extend(keys.into_iter().map(|(&id, _)| id));
+    }
+
+    /// Resolves the locations of the sorted entities.
+    ///
+    /// Returns false if the sorted entities are no longer the same as the matched entities.
+    fn resolve(&mut self, world: &World) -> bool {
+        self.locations.clear();
+
+        let len: usize = self
+            .archetypes
+            .iter()
+            .map(|&arch_id| world.archetypes.get(arch_id).len())
+            .sum();
+
+        if len != self.order.len() {
+            return false;
+        }
+
+        for &id in &self.order {
+            let Ok(loc) = world.location(id) else {
+                return false;
+            };
+
+            let Some(&arch_index) = self.archetypes_index.get(&loc.arch_id) else {
+                return false;
+            };
+
+            self.locations.push((arch_index, loc.slot));
+        }
+
+        true
+    }
+}
+
+impl<'w, Q, F, T> QueryStrategy<'w, Q, F> for Sorted<T>
+where
+    Q: 'w + Fetch<'w>,
+    F: 'w + Fetch<'w>,
+    T: ComponentValue,
+{
+    type Borrow = SortedBorrow<'w, Q, F>;
+
+    fn borrow(&'w mut self, query_state: QueryBorrowState<'w, Q, F>, dirty: bool) -> Self::Borrow {
+        if dirty {
+            self.state
+                .find_archetypes(query_state.world, query_state.fetch, self.component);
+        }
+
+        self.state
+            .update(query_state.world, self.component, &*self.cmp);
+
+        SortedBorrow {
+            sorted: &self.state,
+            state: query_state,
+            prepared: Default::default(),
+        }
+    }
+
+    fn access(&self, world: &'w World, fetch: &'w Filtered<Q, F>, dst: &mut Vec<Access>) {
+        let mut state = State::default();
+        state.find_archetypes(world, fetch, self.component);
+
+        state.archetypes.iter().for_each(|&arch_id| {
+            let arch = world.archetypes.get(arch_id);
+            let data = FetchAccessData {
+                world,
+                arch,
+                arch_id,
+            };
+
+            fetch.access(data, dst);
+
+            // The sort key is read to sort the entities
+            dst.push(Access {
+                kind: AccessKind::Archetype {
+                    id: arch_id,
+                    component: self.component.key(),
+                },
+                mutable: false,
+            });
+        });
+
+        dst.push(Access {
+            kind: AccessKind::World,
+            mutable: false,
+        });
+    }
+}
+
+/// Borrowed state for [`Sorted`] strategy
+pub struct SortedBorrow<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    sorted: &'w State,
+    state: QueryBorrowState<'w, Q, F>,
+    prepared: PreparedArchetypes<'w, Q, F>,
+}
+
+impl<'w, 'q, Q, F> IntoIterator for &'q mut SortedBorrow<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    type Item = <Q as FetchItem<'q>>::Item;
+
+    type IntoIter = SortedIter<'w, 'q, Q, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'w, Q, F> SortedBorrow<'w, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+{
+    /// Iterate all items matched by query and filter in sorted order.
+    pub fn iter<'q>(&'q mut self) -> SortedIter<'w, 'q, Q, F> {
+        SortedIter {
+            iter: self
+                .prepared
+                .iter(&self.state, &self.sorted.archetypes, &self.sorted.locations),
+        }
+    }
+}
+
+/// Iterates entities in sorted order.
+pub struct SortedIter<'w, 'q, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+    'w: 'q,
+{
+    iter: LocatedIter<'w, 'q, Q, F>,
+}
+
+impl<'w, 'q, Q, F> Iterator for SortedIter<'w, 'q, Q, F>
+where
+    Q: Fetch<'w>,
+    F: Fetch<'w>,
+    'w: 'q,
+{
+    type Item = <Q::Prepared as PreparedFetch<'q>>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
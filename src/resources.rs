@@ -0,0 +1,59 @@
+use core::any::{type_name, Any, TypeId};
+
+use alloc::{boxed::Box, collections::BTreeMap};
+use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+
+use crate::{component::ComponentValue, error::Result, Error};
+
+type ResourceCell = AtomicRefCell<Box<dyn Any + Send + Sync>>;
+
+/// Type keyed storage of the singleton values of a world
+#[derive(Default)]
+pub(crate) struct Resources {
+    inner: BTreeMap<TypeId, ResourceCell>,
+}
+
+impl Resources {
+    /// Inserts a resource, returning the previous value
+    pub(crate) fn insert<T: ComponentValue>(&mut self, value: T) -> Option<T> {
+        let prev = self
+            .inner
+            .insert(TypeId::of::<T>(), AtomicRefCell::new(Box::new(value)))?;
+
+        Some(*prev.into_inner().downcast().unwrap())
+    }
+
+    pub(crate) fn remove<T: ComponentValue>(&mut self) -> Option<T> {
+        let cell = self.inner.remove(&TypeId::of::<T>())?;
+        Some(*cell.into_inner().downcast().unwrap())
+    }
+
+    pub(crate) fn has<T: ComponentValue>(&self) -> bool {
+        self.inner.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Borrows a type erased resource
+    #[cfg(feature = "serde")]
+    pub(crate) fn get_dyn(&self, ty: TypeId) -> Option<AtomicRef<'_, dyn Any + Send + Sync>> {
+        let cell = self.inner.get(&ty)?;
+        Some(AtomicRef::map(cell.borrow(), |v| &**v))
+    }
+
+    pub(crate) fn get<T: ComponentValue>(&self) -> Result<AtomicRef<'_, T>> {
+        let cell = self.cell::<T>()?;
+        Ok(AtomicRef::map(cell.borrow(), |v| v.downcast_ref().unwrap()))
+    }
+
+    pub(crate) fn get_mut<T: ComponentValue>(&self) -> Result<AtomicRefMut<'_, T>> {
+        let cell = self.cell::<T>()?;
+        Ok(AtomicRefMut::map(cell.borrow_mut(), |v| {
+            v.downcast_mut().unwrap()
+        }))
+    }
+
+    fn cell<T: ComponentValue>(&self) -> Result<&ResourceCell> {
+        self.inner
+            .get(&TypeId::of::<T>())
+            .ok_or(Error::MissingResource(type_name::<T>()))
+    }
+}
@@ -0,0 +1,266 @@
+use core::fmt::Write;
+
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use itertools::Itertools;
+
+use crate::{
+    system::{Access, AccessKind},
+    World,
+};
+
+/// A dependency between two systems, by their position in the sequential execution order
+pub(crate) struct Dependency {
+    pub(crate) src: usize,
+    pub(crate) dst: usize,
+    /// Pairs of incompatible accesses of `src` and `dst`
+    pub(crate) conflicts: Vec<(Access, Access)>,
+    /// The systems are explicitly ordered using labels
+    pub(crate) ordered: bool,
+}
+
+/// The accesses and ordering constraints of the systems in sequential order, and the resulting
+/// batches of system indices
+pub(crate) struct Partition {
+    pub(crate) accesses: Vec<Vec<Access>>,
+    pub(crate) constraints: Vec<BTreeSet<usize>>,
+    pub(crate) batches: Vec<Vec<usize>>,
+}
+
+/// Describes why a system depends on another, and the partitioning of the systems into batches.
+///
+/// Can be rendered using [Graphviz](https://graphviz.org) or [Mermaid](https://mermaid.js.org) to
+/// find out why two systems do not run in parallel.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    pub(crate) systems: Vec<String>,
+    pub(crate) batches: Vec<Vec<usize>>,
+    pub(crate) edges: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    /// Returns the names of the systems.
+    ///
+    /// Systems are referred to by their index into this list.
+    pub fn systems(&self) -> &[String] {
+        &self.systems
+    }
+
+    /// Returns the systems of each batch, in execution order
+    pub fn batches(&self) -> &[Vec<usize>] {
+        &self.batches
+    }
+
+    /// Returns the dependencies between the systems
+    pub fn edges(&self) -> &[DependencyEdge] {
+        &self.edges
+    }
+
+    /// Returns the edge from system `from` to system `to`, if any
+    pub fn edge(&self, from: &str, to: &str) -> Option<&DependencyEdge> {
+        self.edges
+            .iter()
+            .find(|v| self.systems[v.from] == from && self.systems[v.to] == to)
+    }
+
+    /// Renders the graph in the Graphviz DOT language
+    pub fn to_dot(&self) -> String {
+        let mut s = String::from("digraph schedule {\n    rankdir=LR;\n");
+
+        for (batch_idx, batch) in self.batches.iter().enumerate() {
+            writeln!(s, "    subgraph cluster_{batch_idx} {{").unwrap();
+            writeln!(s, "        label=\"batch {batch_idx}\";").unwrap();
+            for &idx in batch {
+                writeln!(
+                    s,
+                    "        s{idx} [label=\"{}\"];",
+                    escape_dot(&self.systems[idx])
+                )
+                .unwrap();
+            }
+            s.push_str("    }\n");
+        }
+
+        for edge in &self.edges {
+            let label = edge.labels().map(|v| escape_dot(&v)).join("\\n");
+            writeln!(s, "    s{} -> s{} [label=\"{label}\"];", edge.from, edge.to).unwrap();
+        }
+
+        s.push_str("}\n");
+        s
+    }
+
+    /// Renders the graph as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut s = String::from("flowchart LR\n");
+
+        for (batch_idx, batch) in self.batches.iter().enumerate() {
+            writeln!(s, "    subgraph batch_{batch_idx} [\"batch {batch_idx}\"]").unwrap();
+            for &idx in batch {
+                writeln!(
+                    s,
+                    "        s{idx}[\"{}\"]",
+                    escape_mermaid(&self.systems[idx])
+                )
+                .unwrap();
+            }
+            s.push_str("    end\n");
+        }
+
+        for edge in &self.edges {
+            let label = edge.labels().map(|v| escape_mermaid(&v)).join("<br>");
+            writeln!(s, "    s{} -->|\"{label}\"| s{}", edge.from, edge.to).unwrap();
+        }
+
+        s
+    }
+}
+
+/// A dependency which requires a system to run after another
+#[derive(Debug, Clone)]
+pub struct DependencyEdge {
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    pub(crate) conflicts: Vec<AccessConflict>,
+    pub(crate) ordered: bool,
+}
+
+impl DependencyEdge {
+    /// Returns the index of the system which runs first
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Returns the index of the system which depends on [`Self::from`]
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    /// Returns the accesses of the two systems which conflict
+    pub fn conflicts(&self) -> &[AccessConflict] {
+        &self.conflicts
+    }
+
+    /// Returns true if the systems are explicitly ordered using labels
+    pub fn ordered(&self) -> bool {
+        self.ordered
+    }
+
+    fn labels(&self) -> impl Iterator<Item = String> + '_ {
+        self.ordered
+            .then(|| "ordering".to_string())
+            .into_iter()
+            .chain(self.conflicts.iter().map(|v| v.desc.clone()))
+    }
+}
+
+/// Two accesses of the same resource which can not happen at the same time, as at least one of
+/// them is mutable.
+#[derive(Debug, Clone)]
+pub struct AccessConflict {
+    kind: AccessKind,
+    src_mutable: bool,
+    dst_mutable: bool,
+    desc: String,
+}
+
+impl AccessConflict {
+    pub(crate) fn new(src: &Access, dst: &Access, world: &World) -> Self {
+        let (name, location) = match dst.kind {
+            AccessKind::Archetype { id, component } => {
+                let arch = world.archetypes.get(id);
+                let name = arch
+                    .component(component)
+                    .map(|v| v.name().to_string())
+                    .unwrap_or_else(|| component.to_string());
+                (name, format!(" in archetype {id}"))
+            }
+            AccessKind::External(_) => ("External".into(), String::new()),
+            AccessKind::World => ("World".into(), String::new()),
+            AccessKind::CommandBuffer => ("CommandBuffer".into(), String::new()),
+            AccessKind::Input(_) => ("Input".into(), String::new()),
+            AccessKind::Resource(_) => ("Resource".into(), String::new()),
+        };
+
+        let borrow = |mutable| if mutable { "&mut " } else { "&" };
+
+        Self {
+            kind: dst.kind,
+            src_mutable: src.mutable,
+            dst_mutable: dst.mutable,
+            desc: format!(
+                "{}{name} -> {}{name}{location}",
+                borrow(src.mutable),
+                borrow(dst.mutable)
+            ),
+        }
+    }
+
+    /// Returns the accessed resource
+    pub fn kind(&self) -> &AccessKind {
+        &self.kind
+    }
+
+    /// Returns true if the system which runs first accesses the resource mutably
+    pub fn src_mutable(&self) -> bool {
+        self.src_mutable
+    }
+
+    /// Returns true if the dependent system accesses the resource mutably
+    pub fn dst_mutable(&self) -> bool {
+        self.dst_mutable
+    }
+
+    /// Returns a human readable description of the conflict
+    pub fn desc(&self) -> &str {
+        &self.desc
+    }
+}
+
+/// Finds the dependencies between systems, given their accesses in sequential order.
+///
+/// `constraints` contains the systems each system is explicitly ordered after.
+pub(crate) fn dependencies(
+    accesses: &[Vec<Access>],
+    constraints: &[BTreeSet<usize>],
+) -> Vec<Dependency> {
+    let mut result = Vec::new();
+    for (dst_idx, dst) in accesses.iter().enumerate() {
+        for (src_idx, src) in accesses.iter().take(dst_idx).enumerate() {
+            let conflicts = dst
+                .iter()
+                .flat_map(|dst_access| {
+                    src.iter()
+                        .filter(|src_access| !src_access.is_compatible_with(dst_access))
+                        .map(|src_access| (src_access.clone(), dst_access.clone()))
+                })
+                .unique()
+                .collect_vec();
+
+            let ordered = constraints[dst_idx].contains(&src_idx);
+
+            if ordered || !conflicts.is_empty() {
+                result.push(Dependency {
+                    src: src_idx,
+                    dst: dst_idx,
+                    conflicts,
+                    ordered,
+                })
+            }
+        }
+    }
+
+    result
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(s: &str) -> String {
+    s.replace('&', "#amp;").replace('"', "#quot;")
+}
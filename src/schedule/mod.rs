@@ -1,16 +1,28 @@
-use core::{mem, ops::Deref};
+mod graph;
+mod timestep;
 
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{mem, ops::Deref, time::Duration};
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
 
 use anyhow::Context;
 use itertools::Itertools;
 
 use crate::{
-    system::{access_info, AccessInfo, IntoInput, SystemContext},
+    error::Result,
+    system::{access_info, matched_entities, AccessInfo, IntoInput, SystemContext, SystemStats},
     util::Verbatim,
-    BoxedSystem, CommandBuffer, System, World,
+    BoxedSystem, CommandBuffer, Error, System, World,
 };
 
+use graph::Partition;
+pub use graph::{AccessConflict, DependencyEdge, DependencyGraph};
+pub use timestep::{Timestep, TimestepMode, TimestepSchedule};
+
 fn flush_system() -> BoxedSystem {
     System::builder()
         .with_name("flush")
@@ -60,6 +72,8 @@ pub struct SystemInfo {
     name: String,
     desc: Verbatim,
     access: AccessInfo,
+    entities: usize,
+    stats: SystemStats,
 }
 
 impl SystemInfo {
@@ -77,6 +91,16 @@ impl SystemInfo {
     pub fn access(&self) -> &AccessInfo {
         &self.access
     }
+
+    /// Returns the number of entities currently matched by the system's queries
+    pub fn entities(&self) -> usize {
+        self.entities
+    }
+
+    /// Returns the execution statistics of the system
+    pub fn stats(&self) -> &SystemStats {
+        &self.stats
+    }
 }
 
 /// A schedule of systems to execute with automatic parallelization.
@@ -86,6 +110,8 @@ pub struct Schedule {
     cmd: CommandBuffer,
 
     archetype_gen: u32,
+    /// True if the systems satisfy the ordering constraints
+    ordered: bool,
 }
 
 /// Holds information regarding a schedule's batches
@@ -183,6 +209,7 @@ impl Schedule {
         Self {
             systems: alloc::vec![systems.into()],
             archetype_gen: 0,
+            ordered: false,
             cmd: CommandBuffer::new(),
         }
     }
@@ -190,6 +217,7 @@ impl Schedule {
     /// Append one schedule onto another
     pub fn append(&mut self, other: Self) {
         self.archetype_gen = 0;
+        self.ordered = false;
         self.systems.extend(other.systems)
     }
 
@@ -197,6 +225,7 @@ impl Schedule {
     /// Respects order.
     pub fn with_system(mut self, system: impl Into<BoxedSystem>) -> Self {
         self.archetype_gen = 0;
+        self.ordered = false;
         let v = match self.systems.first_mut() {
             Some(v) => v,
             None => {
@@ -213,6 +242,7 @@ impl Schedule {
     /// Respects order.
     pub fn add_system(&mut self, system: impl Into<BoxedSystem>) -> &mut Self {
         self.archetype_gen = 0;
+        self.ordered = false;
         let v = match self.systems.first_mut() {
             Some(v) => v,
             None => {
@@ -230,9 +260,96 @@ impl Schedule {
         self.with_system(flush_system())
     }
 
+    /// Runs the schedule once for every `step` of elapsed time, when added as a system to an outer
+    /// schedule.
+    ///
+    /// See [`TimestepSchedule::fixed`]
+    pub fn fixed_timestep(self, step: Duration) -> TimestepSchedule {
+        TimestepSchedule::fixed(self, step)
+    }
+
+    /// Runs the schedule at most once every `interval`, when added as a system to an outer
+    /// schedule.
+    ///
+    /// See [`TimestepSchedule::rate_limited`]
+    pub fn rate_limited(self, interval: Duration) -> TimestepSchedule {
+        TimestepSchedule::rate_limited(self, interval)
+    }
+
+    /// Returns the execution statistics of each system in the schedule, in execution order.
+    ///
+    /// Use [`Self::batch_info`] to also retrieve the matched entities and accesses.
+    pub fn stats(&self) -> impl Iterator<Item = (&str, &SystemStats)> {
+        self.systems
+            .iter()
+            .flatten()
+            .map(|system| (system.name(), system.stats()))
+    }
+
+    /// Clears the collected execution statistics of all systems
+    pub fn reset_stats(&mut self) {
+        self.systems
+            .iter_mut()
+            .flatten()
+            .for_each(BoxedSystem::reset_stats)
+    }
+
+    /// Returns the dependencies between the systems, along with the conflicting accesses which
+    /// caused them, and the resulting batches.
+    ///
+    /// Fails if the ordering constraints of the systems form a cycle.
+    pub fn dependency_graph(&mut self, world: &World) -> Result<DependencyGraph> {
+        let Partition {
+            accesses,
+            constraints,
+            batches,
+        } = self.build_dependencies(world)?;
+
+        let mut systems = alloc::vec![String::new(); batches.iter().map(Vec::len).sum()];
+        for (batch, indices) in self.systems.iter().zip(&batches) {
+            for (system, &idx) in batch.iter().zip(indices) {
+                systems[idx] = system.name().into();
+            }
+        }
+
+        // Collecting every conflict is only needed for the graph, rather than when scheduling
+        let edges = graph::dependencies(&accesses, &constraints)
+            .into_iter()
+            .map(|dep| DependencyEdge {
+                from: dep.src,
+                to: dep.dst,
+                conflicts: dep
+                    .conflicts
+                    .iter()
+                    .map(|(src, dst)| AccessConflict::new(src, dst, world))
+                    .collect(),
+                ordered: dep.ordered,
+            })
+            .collect();
+
+        Ok(DependencyGraph {
+            systems,
+            batches,
+            edges,
+        })
+    }
+
     /// Returns information about the current multithreaded batch partioning and system accesses.
+    ///
+    /// # Panics
+    /// If the ordering constraints of the systems form a cycle. See [`Self::try_batch_info`].
     pub fn batch_info(&mut self, world: &World) -> BatchInfos {
-        self.systems = Self::build_dependencies(mem::take(&mut self.systems), world);
+        match self.try_batch_info(world) {
+            Ok(v) => v,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns information about the current multithreaded batch partioning and system accesses.
+    ///
+    /// Fails if the ordering constraints of the systems form a cycle.
+    pub fn try_batch_info(&mut self, world: &World) -> Result<BatchInfos> {
+        self.build_dependencies(world)?;
 
         let batches = self
             .systems
@@ -247,6 +364,8 @@ impl Schedule {
                             name: system.name().into(),
                             desc: Verbatim(alloc::format!("{system:#?}")),
                             access: access_info(&access, world),
+                            entities: matched_entities(&access, world),
+                            stats: system.stats().clone(),
                         }
                     })
                     .collect_vec();
@@ -254,7 +373,7 @@ impl Schedule {
             })
             .collect_vec();
 
-        BatchInfos(batches)
+        Ok(BatchInfos(batches))
     }
 
     /// Same as [`Self::execute_seq`] but allows supplying short lived input available to the systems
@@ -266,6 +385,10 @@ impl Schedule {
         input: impl IntoInput<'a>,
     ) -> anyhow::Result<()> {
         profile_function!();
+        if !self.ordered {
+            self.order_systems().map_err(Error::into_anyhow)?;
+        }
+
         let input = input.into_input();
         let ctx = SystemContext::new(world, &mut self.cmd, &input);
 
@@ -296,9 +419,9 @@ impl Schedule {
 
         let w_gen = world.archetype_gen();
         // New archetypes
-        if self.archetype_gen != w_gen {
+        if self.archetype_gen != w_gen || !self.ordered {
+            self.build_dependencies(world).map_err(Error::into_anyhow)?;
             self.archetype_gen = w_gen;
-            self.systems = Self::build_dependencies(mem::take(&mut self.systems), world);
         }
 
         let input = input.into_input();
@@ -306,10 +429,10 @@ impl Schedule {
 
         let mut batches = self.systems.iter_mut();
 
-        for batch in &mut batches {
+        for (batch_idx, batch) in (&mut batches).enumerate() {
             batch
                 .par_iter_mut()
-                .try_for_each(|system| system.execute(&ctx))?;
+                .try_for_each(|system| system.execute_in(&ctx, Some(batch_idx)))?;
 
             // If the archetype generation changed the batches are invalidated
             //
@@ -339,8 +462,39 @@ impl Schedule {
             .context("Failed to apply commandbuffer")
     }
 
-    fn build_dependencies(systems: Vec<Vec<BoxedSystem>>, world: &World) -> Vec<Vec<BoxedSystem>> {
+    /// Reorders the systems sequentially to satisfy the ordering constraints.
+    ///
+    /// Returns the systems each system must run after, by their new position.
+    fn order_systems(&mut self) -> Result<Vec<BTreeSet<usize>>> {
+        let (order, constraints) = constraint_order(&self.systems.iter().flatten().collect_vec())?;
+
+        let mut systems = mem::take(&mut self.systems)
+            .into_iter()
+            .flatten()
+            .map(Some)
+            .collect_vec();
+
+        self.systems = alloc::vec![order
+            .iter()
+            .map(|&idx| systems[idx].take().unwrap())
+            .collect_vec()];
+
+        // The batches are invalidated
+        self.ordered = true;
+        self.archetype_gen = 0;
+
+        Ok(constraints)
+    }
+
+    /// Partitions the systems into batches which can run in parallel.
+    ///
+    /// Returns the accesses and constraints of the systems, and the batches of system indices, by
+    /// their position in the sequential order.
+    fn build_dependencies(&mut self, world: &World) -> Result<Partition> {
         profile_function!();
+        let constraints = self.order_systems()?;
+        let systems = mem::take(&mut self.systems);
+
         let accesses = systems
             .iter()
             .flatten()
@@ -368,32 +522,34 @@ impl Schedule {
                             },
                         )
                     })
+                    .chain(constraints[dst_idx].iter().copied())
                     .dedup()
                     .collect_vec();
 
             deps.insert(dst_idx, dst_deps);
         }
 
-        // let mut current_access = BTreeMap::new();
-        // let mut batches = Vec::new();
+        let batches = topo_sort(alloc::vec![(0..accesses.len()).collect_vec()], &deps);
 
-        // let mut current_batch = Vec::new();
-        // for (system, (name, accesses)) in systems.into_iter().zip(accesses) {
-        //     if !update_compatible(&accesses, &mut current_access) {
-        //         eprintln!("Pushing new batch with");
-        //         batches.push(mem::take(&mut current_batch));
-        //     }
-        //     current_batch.push(system);
-        //     eprintln!("system: {name}");
-        // }
-
-        // batches.push(current_batch);
-
-        // batches
+        let mut systems = systems.into_iter().flatten().map(Some).collect_vec();
+        self.systems = batches
+            .iter()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .map(|&idx| systems[idx].take().unwrap())
+                    .collect_vec()
+            })
+            .collect_vec();
 
-        topo_sort(systems, &deps)
+        Ok(Partition {
+            accesses,
+            constraints,
+            batches,
+        })
     }
 }
+
 ///// Insert accesses checking for compatibility.
 /////
 ///// If the new system's accesses are not compatible, the current acceses are replaced with the new
@@ -422,6 +578,109 @@ impl Schedule {
 //     true
 // }
 
+/// Returns the order of the systems which satisfies their ordering constraints, otherwise
+/// preserving the insertion order, as well as the systems each system must run after.
+fn constraint_order(systems: &[&BoxedSystem]) -> Result<(Vec<usize>, Vec<BTreeSet<usize>>)> {
+    let mut labels = BTreeMap::<&str, Vec<usize>>::new();
+    for (idx, system) in systems.iter().enumerate() {
+        for label in system.labels() {
+            labels.entry(label).or_default().push(idx);
+        }
+    }
+
+    let labeled = |label: &String| labels.get(label.as_str()).into_iter().flatten().copied();
+
+    let mut preds = alloc::vec![BTreeSet::new(); systems.len()];
+    for (idx, system) in systems.iter().enumerate() {
+        for other in system.after_labels().iter().flat_map(labeled) {
+            if other != idx {
+                preds[idx].insert(other);
+            }
+        }
+
+        for other in system.before_labels().iter().flat_map(labeled) {
+            if other != idx {
+                preds[other].insert(idx);
+            }
+        }
+    }
+
+    let mut succs = alloc::vec![Vec::new(); systems.len()];
+    for (idx, preds) in preds.iter().enumerate() {
+        for &pred in preds {
+            succs[pred].push(idx);
+        }
+    }
+
+    // Kahn's algorithm, preferring the earliest inserted system
+    let mut indegree = preds.iter().map(|v| v.len()).collect_vec();
+    let mut ready = (0..systems.len())
+        .filter(|&idx| indegree[idx] == 0)
+        .collect::<BTreeSet<_>>();
+
+    let mut order = Vec::with_capacity(systems.len());
+    while let Some(idx) = ready.pop_first() {
+        order.push(idx);
+        for &succ in &succs[idx] {
+            indegree[succ] -= 1;
+            if indegree[succ] == 0 {
+                ready.insert(succ);
+            }
+        }
+    }
+
+    if order.len() != systems.len() {
+        let cycle = find_cycle(&preds, &indegree);
+        return Err(Error::CyclicSchedule(
+            cycle
+                .iter()
+                .map(|&idx| systems[idx].name().into())
+                .collect(),
+        ));
+    }
+
+    // Map the constraints to the new order
+    let mut position = alloc::vec![0; systems.len()];
+    for (i, &idx) in order.iter().enumerate() {
+        position[idx] = i;
+    }
+
+    let constraints = order
+        .iter()
+        .map(|&idx| preds[idx].iter().map(|&pred| position[pred]).collect())
+        .collect_vec();
+
+    Ok((order, constraints))
+}
+
+/// Finds a cycle among the systems which could not be ordered.
+///
+/// Each unordered system has at least one unordered predecessor, so following them eventually
+/// revisits a system.
+fn find_cycle(preds: &[BTreeSet<usize>], indegree: &[usize]) -> Vec<usize> {
+    let mut current = indegree.iter().position(|&v| v > 0).unwrap();
+    let mut path = Vec::new();
+
+    loop {
+        if let Some(start) = path.iter().position(|&v| v == current) {
+            let mut cycle = path.split_off(start);
+            // Predecessors were followed, so reverse to get the execution order
+            cycle.reverse();
+            // Start at the earliest inserted system for a stable report
+            let first = cycle.iter().position_min().unwrap();
+            cycle.rotate_left(first);
+            cycle.push(cycle[0]);
+            return cycle;
+        }
+
+        path.push(current);
+        current = *preds[current]
+            .iter()
+            .find(|&&pred| indegree[pred] > 0)
+            .unwrap();
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum VisitedState {
     Pending,
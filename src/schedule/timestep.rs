@@ -0,0 +1,266 @@
+use core::{
+    any::TypeId,
+    fmt::{self, Formatter},
+    time::Duration,
+};
+
+use alloc::vec::Vec;
+
+use crate::{
+    system::{Access, AccessKind, DynSystem, InputChain, IntoInput, NestedInput, SystemContext},
+    World,
+};
+
+use super::Schedule;
+
+/// Input provided to the systems of a [`TimestepSchedule`].
+///
+/// Access using [`SystemBuilder::with_input`](crate::SystemBuilder::with_input).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timestep {
+    /// The time advanced by this run
+    pub delta: Duration,
+    /// The fraction of a step left in the accumulator after this run.
+    ///
+    /// Used to interpolate between the previous and current state.
+    pub alpha: f32,
+}
+
+/// Determines how often a [`TimestepSchedule`] runs its schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestepMode {
+    /// Run once for every whole step of accumulated time, advancing by exactly the step each run.
+    Fixed(Duration),
+    /// Run at most once per tick, and only when at least the interval has elapsed since the last
+    /// run. Advances by all accumulated time.
+    RateLimited(Duration),
+}
+
+/// Runs a nested schedule zero or more times per tick of an outer schedule, driven by the elapsed
+/// time.
+///
+/// This allows running physics or networking at a fixed rate independent of the frame rate.
+///
+/// The timestep schedule is added as a system to the outer schedule, and reads the time elapsed
+/// since the last tick from the [`Duration`] input of the outer schedule, as supplied to
+/// [`Schedule::execute_seq_with`] or [`Schedule::execute_par_with`].
+///
+/// The systems of the nested schedule receive a [`Timestep`] as input, in addition to the input of
+/// the outer schedule.
+///
+/// ```rust
+/// # use flax::{*, schedule::Timestep};
+/// # use core::time::Duration;
+/// let steps = SharedResource::new(0);
+///
+/// let physics = System::builder()
+///     .with_input::<Timestep>()
+///     .with_resource(steps.clone())
+///     .build(|step: &Timestep, steps: &mut usize| {
+///         assert_eq!(step.delta, Duration::from_millis(20));
+///         *steps += 1;
+///     });
+///
+/// let mut schedule = Schedule::new()
+///     .with_system(Schedule::new().with_system(physics).fixed_timestep(Duration::from_millis(20)));
+///
+/// let mut world = World::new();
+/// let mut delta = Duration::from_millis(50);
+/// schedule.execute_seq_with(&mut world, &mut delta).unwrap();
+///
+/// assert_eq!(*steps.borrow(), 2);
+/// ```
+#[derive(Debug)]
+pub struct TimestepSchedule {
+    schedule: Schedule,
+    mode: TimestepMode,
+    accumulator: Duration,
+    max_steps: Option<usize>,
+    #[cfg(feature = "rayon")]
+    parallel: bool,
+}
+
+impl TimestepSchedule {
+    /// Creates a schedule which runs once for every `step` of elapsed time.
+    ///
+    /// # Panics
+    /// If `step` is zero
+    pub fn fixed(schedule: Schedule, step: Duration) -> Self {
+        assert!(!step.is_zero(), "Fixed timestep must be non-zero");
+        Self::new(schedule, TimestepMode::Fixed(step))
+    }
+
+    /// Creates a schedule which runs at most once every `interval`
+    pub fn rate_limited(schedule: Schedule, interval: Duration) -> Self {
+        Self::new(schedule, TimestepMode::RateLimited(interval))
+    }
+
+    fn new(schedule: Schedule, mode: TimestepMode) -> Self {
+        Self {
+            schedule,
+            mode,
+            accumulator: Duration::ZERO,
+            max_steps: None,
+            #[cfg(feature = "rayon")]
+            parallel: false,
+        }
+    }
+
+    /// Limit the number of fixed steps executed in a single tick.
+    ///
+    /// Time in excess of the limit is discarded, which prevents falling further and further behind
+    /// when a step takes longer to execute than the time it advances.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Execute the nested schedule in parallel rather than sequentially
+    pub fn with_parallel(mut self) -> Self {
+        self.parallel = true;
+        self
+    }
+
+    fn advance(
+        &mut self,
+        delta: Duration,
+        mut execute: impl FnMut(&mut Schedule, Timestep) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        self.accumulator += delta;
+
+        match self.mode {
+            TimestepMode::Fixed(step) => {
+                let mut count = 0;
+                while self.accumulator >= step {
+                    if self.max_steps.is_some_and(|max| count >= max) {
+                        self.accumulator = remainder(self.accumulator, step);
+                        break;
+                    }
+
+                    self.accumulator -= step;
+                    count += 1;
+
+                    let alpha = self.alpha();
+                    execute(&mut self.schedule, Timestep { delta: step, alpha })?;
+                }
+            }
+            TimestepMode::RateLimited(interval) => {
+                if self.accumulator >= interval {
+                    let delta = core::mem::take(&mut self.accumulator);
+                    execute(&mut self.schedule, Timestep { delta, alpha: 0.0 })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fraction of a step or interval which is currently accumulated.
+    ///
+    /// For a fixed timestep this is the interpolation factor between the previous and the current
+    /// step.
+    pub fn alpha(&self) -> f32 {
+        let period = match self.mode {
+            TimestepMode::Fixed(v) | TimestepMode::RateLimited(v) => v,
+        };
+
+        if period.is_zero() {
+            return 0.0;
+        }
+
+        (self.accumulator.as_secs_f64() / period.as_secs_f64()).min(1.0) as f32
+    }
+
+    /// Returns the time accumulated towards the next run
+    pub fn accumulator(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// Returns how often the schedule runs
+    pub fn mode(&self) -> TimestepMode {
+        self.mode
+    }
+
+    /// Returns the nested schedule
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the nested schedule mutably
+    pub fn schedule_mut(&mut self) -> &mut Schedule {
+        &mut self.schedule
+    }
+}
+
+impl DynSystem for TimestepSchedule {
+    fn name(&self) -> &str {
+        match self.mode {
+            TimestepMode::Fixed(_) => "fixed_timestep",
+            TimestepMode::RateLimited(_) => "rate_limited",
+        }
+    }
+
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let period = match self.mode {
+            TimestepMode::Fixed(v) | TimestepMode::RateLimited(v) => v,
+        };
+
+        write!(f, "{}({period:?}) ", self.name())?;
+        fmt::Debug::fmt(&self.schedule, f)
+    }
+
+    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<bool> {
+        profile_function!(self.name());
+
+        let delta = match ctx.input::<Duration>() {
+            Some(v) => *v,
+            None => anyhow::bail!("Input does not contain the elapsed `Duration`"),
+        };
+
+        let mut world = ctx.world_mut();
+        let outer = NestedInput(ctx.input_dyn());
+
+        #[cfg(feature = "rayon")]
+        let parallel = self.parallel;
+
+        self.advance(delta, |schedule, mut timestep| {
+            let input = InputChain::new(&outer, (&mut timestep).into_input());
+
+            #[cfg(feature = "rayon")]
+            if parallel {
+                return schedule.execute_par_with(&mut world, input);
+            }
+
+            schedule.execute_seq_with(&mut world, input)
+        })?;
+
+        Ok(true)
+    }
+
+    fn access(&self, world: &World, dst: &mut Vec<Access>) {
+        dst.push(Access {
+            kind: AccessKind::Input(TypeId::of::<Duration>()),
+            mutable: false,
+        });
+
+        // The nested schedule flushes its commands to the world
+        dst.push(Access {
+            kind: AccessKind::World,
+            mutable: true,
+        });
+
+        for system in self.schedule.systems.iter().flatten() {
+            system.access(world, dst);
+        }
+    }
+}
+
+fn remainder(value: Duration, step: Duration) -> Duration {
+    let nanos = value.as_nanos() % step.as_nanos();
+    // The remainder is less than `step`, and as such fits
+    Duration::new(
+        (nanos / 1_000_000_000) as u64,
+        (nanos % 1_000_000_000) as u32,
+    )
+}
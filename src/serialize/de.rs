@@ -1,32 +1,146 @@
 use core::marker::PhantomData;
 use std::borrow::Cow;
 
-use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use alloc::{collections::BTreeMap, format, string::String, sync::Arc, vec::Vec};
 use serde::{
-    de::{self, DeserializeSeed, SeqAccess, VariantAccess, Visitor},
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, VariantAccess, Visitor},
     Deserialize, Deserializer,
 };
 
 use crate::{
     archetype::{ArchetypeStorage, BatchSpawn},
     component::{dummy, ComponentDesc, ComponentValue},
+    world::MigratedEntities,
     Component, Entity, EntityBuilder, RelationExt, World,
 };
 
 use super::{
     registry::{deser_col, deser_one, REGISTRY},
-    DeserializeColFn, DeserializeRowFn, RowFields, SerializeFormat, WorldFields,
+    unknown::split_column,
+    unknown_components, DeserializeResourceFn, ReportingWorldDeserializer, RowFields,
+    SerializeFormat, UnknownComponent, UnknownComponents, Value, WorldFields,
 };
 
+type DeserializeCol = Arc<
+    dyn Fn(
+            &mut dyn erased_serde::Deserializer,
+            ComponentDesc,
+            usize,
+        ) -> erased_serde::Result<ArchetypeStorage>
+        + Send
+        + Sync,
+>;
+
+type DeserializeRow = Arc<
+    dyn Fn(
+            &mut dyn erased_serde::Deserializer,
+            ComponentDesc,
+            &mut EntityBuilder,
+        ) -> erased_serde::Result<()>
+        + Send
+        + Sync,
+>;
+
+type SkipFn = fn(&mut dyn erased_serde::Deserializer) -> erased_serde::Result<()>;
+
 #[derive(Clone)]
-struct Slot {
+pub(super) struct Slot {
     /// Takes a whole column and returns a serializer for it
-    deser_col: DeserializeColFn,
-    deser_one: DeserializeRowFn,
-    desc: ComponentDesc,
+    deser_col: DeserializeCol,
+    pub(super) deser_one: DeserializeRow,
+    pub(super) desc: ComponentDesc,
     is_relation: bool,
 }
 
+impl Slot {
+    fn new<T>(desc: ComponentDesc, is_relation: bool) -> Self
+    where
+        T: ComponentValue + for<'x> Deserialize<'x>,
+    {
+        Self {
+            deser_col: Arc::new(deser_col::<T>),
+            deser_one: Arc::new(deser_one::<T>),
+            desc,
+            is_relation,
+        }
+    }
+
+    /// Deserializes values of type `U` and converts them to the component type
+    fn migrated<U, T>(
+        desc: ComponentDesc,
+        is_relation: bool,
+        migrate: impl Fn(U) -> T + Send + Sync + 'static,
+    ) -> Self
+    where
+        U: for<'x> Deserialize<'x>,
+        T: ComponentValue,
+    {
+        let migrate = Arc::new(migrate);
+        let migrate_col = migrate.clone();
+
+        Self {
+            deser_col: Arc::new(move |deserializer, desc, len| {
+                let values = erased_serde::deserialize::<Vec<U>>(deserializer)?;
+                let mut storage = ArchetypeStorage::with_capacity(desc, len);
+                for value in values {
+                    unsafe { storage.push(migrate_col(value)) }
+                }
+
+                Ok(storage)
+            }),
+            deser_one: Arc::new(move |deserializer, desc, builder| {
+                let value = erased_serde::deserialize::<U>(deserializer)?;
+                builder.set(desc.downcast(), migrate(value));
+                Ok(())
+            }),
+            desc,
+            is_relation,
+        }
+    }
+}
+
+/// Describes how a component of an older version is deserialized
+#[derive(Clone)]
+struct Migration {
+    /// The migration applies to worlds serialized before this version
+    version: u32,
+    kind: MigrationKind,
+}
+
+#[derive(Clone)]
+enum MigrationKind {
+    /// The component was renamed to the given key
+    Rename(String),
+    /// The values are converted from the type used before the version
+    Convert(Slot),
+    /// The component was removed, and the values are skipped
+    Remove {
+        is_relation: bool,
+        skip_one: SkipFn,
+        skip_col: SkipFn,
+    },
+}
+
+/// Determines what happens to components which are not registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum UnknownMode {
+    #[default]
+    Error,
+    Ignore,
+    Keep,
+}
+
+/// The deserializer of a component key
+pub(super) enum Resolved<'a> {
+    Slot(&'a Slot),
+    Removed {
+        is_relation: bool,
+        skip_one: SkipFn,
+        skip_col: SkipFn,
+    },
+    Unknown,
+}
+
 /// [ T, T, T ]
 struct DeserializeStorage<'a> {
     slot: &'a Slot,
@@ -53,10 +167,19 @@ impl<'de> DeserializeSeed<'de> for DeserializeStorage<'_> {
     }
 }
 
+#[derive(Clone)]
+struct ResourceSlot {
+    deser: DeserializeResourceFn,
+}
+
 #[derive(Clone, Default)]
 /// Incrementally construct a [crate::serialize::DeserializeContext]
 pub struct DeserializeBuilder {
     slots: BTreeMap<String, Slot>,
+    resources: BTreeMap<String, ResourceSlot>,
+    migrations: BTreeMap<String, Vec<Migration>>,
+    version: Option<u32>,
+    unknown: UnknownMode,
 }
 
 impl DeserializeBuilder {
@@ -73,8 +196,8 @@ impl DeserializeBuilder {
                 desc.name().to_string(),
                 Slot {
                     is_relation: v.is_relation,
-                    deser_col: v.deserialize_col_fn,
-                    deser_one: v.deserialize_row_fn,
+                    deser_col: Arc::new(v.deserialize_col_fn),
+                    deser_one: Arc::new(v.deserialize_row_fn),
                     desc,
                 },
             )
@@ -110,15 +233,8 @@ impl DeserializeBuilder {
     {
         let key = key.into();
 
-        self.slots.insert(
-            key,
-            Slot {
-                is_relation: false,
-                deser_col: deser_col::<T>,
-                deser_one: deser_one::<T>,
-                desc: component.desc(),
-            },
-        );
+        self.slots
+            .insert(key, Slot::new::<T>(component.desc(), false));
         self
     }
 
@@ -133,29 +249,187 @@ impl DeserializeBuilder {
     {
         let key = key.into();
 
-        self.slots.insert(
-            key,
-            Slot {
-                is_relation: true,
-                deser_col: deser_col::<T>,
-                deser_one: deser_one::<T>,
-                desc: relation.of(dummy()).desc(),
+        self.slots
+            .insert(key, Slot::new::<T>(relation.of(dummy()).desc(), true));
+        self
+    }
+
+    /// Register a resource using the type name.
+    ///
+    /// See [`Self::with_resource_name`]
+    pub fn with_resource<T>(&mut self) -> &mut Self
+    where
+        T: ComponentValue + for<'x> Deserialize<'x>,
+    {
+        self.with_resource_name::<T>(tynm::type_name::<T>())
+    }
+
+    /// Register a new resource to be deserialized into the world
+    pub fn with_resource_name<T>(&mut self, key: impl Into<String>) -> &mut Self
+    where
+        T: ComponentValue + for<'x> Deserialize<'x>,
+    {
+        self.resources.insert(
+            key.into(),
+            ResourceSlot {
+                deser: |deserializer, world| {
+                    let value = erased_serde::deserialize::<T>(deserializer)?;
+                    world.insert_resource(value);
+                    Ok(())
+                },
             },
         );
         self
     }
 
+    /// Sets the current version of the serialized format.
+    ///
+    /// The version is read from the header of each deserialized world, and determines which
+    /// migrations are applied. Worlds serialized without a version are treated as version `0`.
+    ///
+    /// **Note**: formats which are not self-describing, such as bincode, can not load worlds
+    /// serialized without a version once a version is set.
+    pub fn with_version(&mut self, version: u32) -> &mut Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Deserializes the component `key` of worlds serialized before `version` as `new_key`.
+    ///
+    /// Migrations of `new_key` are applied to the renamed component.
+    pub fn with_rename(
+        &mut self,
+        key: impl Into<String>,
+        version: u32,
+        new_key: impl Into<String>,
+    ) -> &mut Self {
+        self.migrate(key, version, MigrationKind::Rename(new_key.into()))
+    }
+
+    /// Deserializes the component `key` of worlds serialized before `version` as the type `U`
+    /// and converts it to the current type using `migrate`.
+    ///
+    /// `U` is the type of the component before the version, or [`Value`](super::Value) for
+    /// self-describing formats.
+    ///
+    /// The migration with the lowest version which is greater than the version of the world is
+    /// used, and is thus expected to convert directly to the current type.
+    pub fn with_migration<U, T>(
+        &mut self,
+        key: impl Into<String>,
+        version: u32,
+        component: Component<T>,
+        migrate: impl Fn(U) -> T + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        U: for<'x> Deserialize<'x>,
+        T: ComponentValue,
+    {
+        let slot = Slot::migrated(component.desc(), false, migrate);
+        self.migrate(key, version, MigrationKind::Convert(slot))
+    }
+
+    /// Deserializes the relation `key` of worlds serialized before `version` as the type `U`
+    /// and converts it to the current type using `migrate`.
+    ///
+    /// See [`Self::with_migration`]
+    pub fn with_relation_migration<U, T>(
+        &mut self,
+        key: impl Into<String>,
+        version: u32,
+        relation: impl RelationExt<T>,
+        migrate: impl Fn(U) -> T + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        U: for<'x> Deserialize<'x>,
+        T: ComponentValue,
+    {
+        let slot = Slot::migrated(relation.of(dummy()).desc(), true, migrate);
+        self.migrate(key, version, MigrationKind::Convert(slot))
+    }
+
+    /// Skips the component `key` of worlds serialized before `version`, as it has since been
+    /// removed.
+    ///
+    /// The values are deserialized as `U`, which is either the former type of the component,
+    /// or [`IgnoredAny`] for self-describing formats.
+    pub fn with_removed<U>(&mut self, key: impl Into<String>, version: u32) -> &mut Self
+    where
+        U: for<'x> Deserialize<'x>,
+    {
+        self.migrate(key, version, removed::<U>(false))
+    }
+
+    /// Skips the relation `key` of worlds serialized before `version`, as it has since been
+    /// removed.
+    ///
+    /// See [`Self::with_removed`]
+    pub fn with_removed_relation<U>(&mut self, key: impl Into<String>, version: u32) -> &mut Self
+    where
+        U: for<'x> Deserialize<'x>,
+    {
+        self.migrate(key, version, removed::<U>(true))
+    }
+
+    /// Skips components which are not registered rather than failing.
+    ///
+    /// **Note**: requires a self-describing format, such as json or ron.
+    pub fn ignore_unknown(&mut self) -> &mut Self {
+        self.unknown = UnknownMode::Ignore;
+        self
+    }
+
+    /// Keeps components which are not registered rather than failing.
+    ///
+    /// The raw data of the components is stored in the [`UnknownComponents`] of each entity, and
+    /// is written back when the world is serialized again. This allows loading files containing
+    /// components of mods or newer builds without losing them.
+    ///
+    /// Use [`DeserializeContext::deserialize_world_with_report`] or [`UnknownReport`](super::UnknownReport) to list the
+    /// unknown components.
+    ///
+    /// **Note**: requires a self-describing format, such as json or ron.
+    pub fn keep_unknown(&mut self) -> &mut Self {
+        self.unknown = UnknownMode::Keep;
+        self
+    }
+
+    fn migrate(&mut self, key: impl Into<String>, version: u32, kind: MigrationKind) -> &mut Self {
+        self.migrations
+            .entry(key.into())
+            .or_default()
+            .push(Migration { version, kind });
+
+        self
+    }
+
     /// Finish constructing the deserialization context
     pub fn build(&mut self) -> DeserializeContext {
         DeserializeContext {
             slots: self.slots.clone(),
+            resources: self.resources.clone(),
+            migrations: self.migrations.clone(),
+            version: self.version,
+            unknown: self.unknown,
         }
     }
 }
 
+fn removed<U: for<'x> Deserialize<'x>>(is_relation: bool) -> MigrationKind {
+    MigrationKind::Remove {
+        is_relation,
+        skip_one: |deserializer| erased_serde::deserialize::<U>(deserializer).map(|_| ()),
+        skip_col: |deserializer| erased_serde::deserialize::<Vec<U>>(deserializer).map(|_| ()),
+    }
+}
+
 /// Describes how to deserialize the world from the described components.
 pub struct DeserializeContext {
     slots: BTreeMap<String, Slot>,
+    resources: BTreeMap<String, ResourceSlot>,
+    migrations: BTreeMap<String, Vec<Migration>>,
+    version: Option<u32>,
+    unknown: UnknownMode,
 }
 
 impl DeserializeContext {
@@ -166,16 +440,132 @@ impl DeserializeContext {
         WorldDeserializer { context: self }
     }
 
+    /// Deserializes a world and reports the components which were not registered.
+    ///
+    /// See [`DeserializeBuilder::keep_unknown`]
+    pub fn deserialize_world_with_report(&self) -> ReportingWorldDeserializer<'_> {
+        ReportingWorldDeserializer {
+            inner: self.deserialize_world(),
+        }
+    }
+
+    /// Deserializes a world and merges it into `world`.
+    ///
+    /// Entity ids which are occupied in `world` are remapped, including relation targets and the
+    /// ids contained in [`EntityMappable`](crate::metadata::EntityMappable) components.
+    ///
+    /// See [`World::merge_with`]
+    pub fn deserialize_into<'a>(&'a self, world: &'a mut World) -> WorldMergeDeserializer<'a> {
+        WorldMergeDeserializer {
+            context: self,
+            world,
+        }
+    }
+
     /// Deserializes an entity into the provided builder
     pub fn deserialize_entity(&self) -> EntityDataDeserializer {
         EntityDataDeserializer { context: self }
     }
 
-    fn get(&self, key: &str) -> Result<&Slot, String> {
+    /// Returns the current version of the format
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    pub(super) fn get(&self, key: &str) -> Result<&Slot, String> {
         self.slots
             .get(key)
             .ok_or_else(|| format!("Unknown component key: {key:?}"))
     }
+
+    /// Returns the deserializer of a component key of a world serialized at `version`
+    fn resolve(&self, key: &str, version: u32) -> Result<Resolved<'_>, String> {
+        let mut key = key;
+        let mut renamed = Vec::new();
+
+        let migration = loop {
+            let migration = self
+                .migrations
+                .get(key)
+                .into_iter()
+                .flatten()
+                .filter(|v| version < v.version)
+                .min_by_key(|v| v.version);
+
+            match migration.map(|v| &v.kind) {
+                Some(MigrationKind::Rename(new_key)) => {
+                    renamed.push(key);
+                    if renamed.contains(&new_key.as_str()) {
+                        return Err(format!(
+                            "Cyclic rename of component key {key:?} to {new_key:?}"
+                        ));
+                    }
+
+                    key = new_key;
+                }
+                _ => break migration,
+            }
+        };
+
+        match migration.map(|v| &v.kind) {
+            Some(MigrationKind::Rename(_)) => unreachable!("Renames are followed"),
+            Some(MigrationKind::Convert(slot)) => Ok(Resolved::Slot(slot)),
+            Some(&MigrationKind::Remove {
+                is_relation,
+                skip_one,
+                skip_col,
+            }) => Ok(Resolved::Removed {
+                is_relation,
+                skip_one,
+                skip_col,
+            }),
+            None => match self.slots.get(key) {
+                Some(slot) => Ok(Resolved::Slot(slot)),
+                None if self.unknown != UnknownMode::Error => Ok(Resolved::Unknown),
+                None => Err(format!("Unknown component key: {key:?}")),
+            },
+        }
+    }
+
+    /// Reads the version of a world serialized as a sequence, which is only a part of the
+    /// format when versioned
+    fn read_version<'de, A: SeqAccess<'de>>(&self, seq: &mut A) -> Result<u32, A::Error> {
+        if self.version.is_none() {
+            return Ok(0);
+        }
+
+        seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &"a world version"))
+    }
+
+    /// Reads the version field of a world serialized as a map.
+    ///
+    /// The version must precede the data it applies to.
+    fn read_version_field<'de, A: MapAccess<'de>>(
+        &self,
+        map: &mut A,
+        version: &mut Option<u32>,
+        has_data: bool,
+    ) -> Result<(), A::Error> {
+        if version.is_some() {
+            return Err(de::Error::duplicate_field("version"));
+        }
+
+        if has_data && self.version.is_some() {
+            return Err(de::Error::custom(
+                "The world version must precede the serialized entities",
+            ));
+        }
+
+        *version = Some(map.next_value()?);
+        Ok(())
+    }
+
+    fn get_resource(&self, key: &str) -> Result<&ResourceSlot, String> {
+        self.resources
+            .get(key)
+            .ok_or_else(|| format!("Unknown resource key: {key:?}"))
+    }
 }
 
 /// Deserializes an entire world in either column or row format
@@ -200,6 +590,28 @@ impl<'de> DeserializeSeed<'de> for WorldDeserializer<'_> {
     }
 }
 
+/// Deserializes a world and merges it into an existing world
+pub struct WorldMergeDeserializer<'a> {
+    context: &'a DeserializeContext,
+    world: &'a mut World,
+}
+
+impl<'de> DeserializeSeed<'de> for WorldMergeDeserializer<'_> {
+    type Value = MigratedEntities;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut other = WorldDeserializer {
+            context: self.context,
+        }
+        .deserialize(deserializer)?;
+
+        Ok(self.world.merge_with(&mut other))
+    }
+}
+
 /// Deserializes a single entity (map-like format)
 pub struct EntityDataDeserializer<'a> {
     context: &'a DeserializeContext,
@@ -231,6 +643,8 @@ impl<'de> Visitor<'de> for EntityDataDeserializer<'_> {
 
         while let Some(()) = seq.next_element_seed(ComponentKeyValueDeserializer {
             context: self.context,
+            // Entities are serialized without a header, and are thus of the current version
+            version: self.context.version.unwrap_or_default(),
             builder: &mut builder,
         })? {}
 
@@ -247,6 +661,7 @@ impl<'de> Visitor<'de> for EntityDataDeserializer<'_> {
 
 struct ComponentKeyValueDeserializer<'a> {
     context: &'a DeserializeContext,
+    version: u32,
     builder: &'a mut EntityBuilder,
 }
 
@@ -276,7 +691,33 @@ impl<'de> Visitor<'de> for ComponentKeyValueDeserializer<'_> {
             de::Error::invalid_length(1, &"Expected a sequence of at least 1 element")
         })?;
 
-        let slot = self.context.get(&key).map_err(de::Error::custom)?;
+        let slot = match self
+            .context
+            .resolve(&key, self.version)
+            .map_err(de::Error::custom)?
+        {
+            Resolved::Slot(slot) => slot,
+            Resolved::Removed {
+                is_relation,
+                skip_one,
+                ..
+            } => return skip_component(seq, is_relation, skip_one),
+            Resolved::Unknown => {
+                if let Some(data) = read_unknown(seq, self.context.unknown)? {
+                    let component = UnknownComponent::new(key.into_owned(), data);
+                    match self.builder.get_mut(unknown_components()) {
+                        Some(unknown) => unknown.push(component),
+                        None => {
+                            let mut unknown = UnknownComponents::default();
+                            unknown.push(component);
+                            self.builder.set(unknown_components(), unknown);
+                        }
+                    }
+                }
+
+                return Ok(());
+            }
+        };
 
         let target = if slot.is_relation {
             Some(
@@ -299,11 +740,19 @@ impl<'de> Visitor<'de> for ComponentKeyValueDeserializer<'_> {
 
 struct ComponentKeyStorageDeserializer<'a> {
     context: &'a DeserializeContext,
+    version: u32,
     len: usize,
 }
 
+/// A deserialized column of an archetype
+enum Column {
+    Storage(ArchetypeStorage),
+    Unknown { key: String, data: Vec<Value> },
+    Skipped,
+}
+
 impl<'de> DeserializeSeed<'de> for ComponentKeyStorageDeserializer<'_> {
-    type Value = ArchetypeStorage;
+    type Value = Column;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
@@ -314,7 +763,7 @@ impl<'de> DeserializeSeed<'de> for ComponentKeyStorageDeserializer<'_> {
 }
 
 impl<'de> Visitor<'de> for ComponentKeyStorageDeserializer<'_> {
-    type Value = ArchetypeStorage;
+    type Value = Column;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         formatter.write_str("a component key")
@@ -328,7 +777,27 @@ impl<'de> Visitor<'de> for ComponentKeyStorageDeserializer<'_> {
             de::Error::invalid_length(1, &"Expected a sequence of at least 1 element")
         })?;
 
-        let slot = self.context.get(&key).map_err(de::Error::custom)?;
+        let slot = match self
+            .context
+            .resolve(&key, self.version)
+            .map_err(de::Error::custom)?
+        {
+            Resolved::Slot(slot) => slot,
+            Resolved::Removed {
+                is_relation,
+                skip_col,
+                ..
+            } => return skip_component(seq, is_relation, skip_col).map(|_| Column::Skipped),
+            Resolved::Unknown => {
+                return Ok(match read_unknown(seq, self.context.unknown)? {
+                    Some(data) => Column::Unknown {
+                        key: key.into_owned(),
+                        data,
+                    },
+                    None => Column::Skipped,
+                })
+            }
+        };
 
         let target = if slot.is_relation {
             Some(
@@ -347,7 +816,61 @@ impl<'de> Visitor<'de> for ComponentKeyStorageDeserializer<'_> {
             })?
             .ok_or_else(|| de::Error::invalid_length(3, &"Expected 3 elements"))?;
 
-        Ok(storage)
+        Ok(Column::Storage(storage))
+    }
+}
+
+/// Skips the remaining relation target and value of a removed component
+fn skip_component<'de, A: SeqAccess<'de>>(
+    mut seq: A,
+    is_relation: bool,
+    skip: SkipFn,
+) -> Result<(), A::Error> {
+    if is_relation {
+        seq.next_element::<Entity>()?
+            .ok_or_else(|| de::Error::invalid_length(1, &"Expected 3 elements"))?;
+    }
+
+    seq.next_element_seed(Skip(skip))?
+        .ok_or_else(|| de::Error::invalid_length(2, &"Expected a component value"))
+}
+
+/// Reads the remaining elements of an unknown component, as it is not known whether it is a
+/// relation.
+///
+/// Returns `None` if the component is skipped.
+fn read_unknown<'de, A: SeqAccess<'de>>(
+    mut seq: A,
+    mode: UnknownMode,
+) -> Result<Option<Vec<Value>>, A::Error> {
+    if mode != UnknownMode::Keep {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        return Ok(None);
+    }
+
+    let mut data = Vec::new();
+    while let Some(value) = seq.next_element()? {
+        data.push(value);
+    }
+
+    if data.is_empty() {
+        return Err(de::Error::invalid_length(1, &"a component value"));
+    }
+
+    Ok(Some(data))
+}
+
+struct Skip(SkipFn);
+
+impl<'de> DeserializeSeed<'de> for Skip {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut deserializer).map_err(de::Error::custom)
     }
 }
 struct WorldFormatVisitor<'a> {
@@ -366,15 +889,29 @@ impl<'de> Visitor<'de> for WorldFormatVisitor<'_> {
         A: de::EnumAccess<'de>,
     {
         let (format, variant) = data.variant::<SerializeFormat>()?;
+        // Resources are only a part of the format when registered
+        let has_resources = !self.context.resources.is_empty();
+        let has_version = self.context.version.is_some();
+
         let world = match format {
             SerializeFormat::ColumnMajor => variant.struct_variant(
-                &["archetypes"],
+                match (has_version, has_resources) {
+                    (false, false) => &["archetypes"],
+                    (false, true) => &["archetypes", "resources"],
+                    (true, false) => &["version", "archetypes"],
+                    (true, true) => &["version", "archetypes", "resources"],
+                },
                 WorldColumnVisitor {
                     context: self.context,
                 },
             )?,
             SerializeFormat::RowMajor => variant.struct_variant(
-                &["entities"],
+                match (has_version, has_resources) {
+                    (false, false) => &["entities"],
+                    (false, true) => &["entities", "resources"],
+                    (true, false) => &["version", "entities"],
+                    (true, true) => &["version", "entities", "resources"],
+                },
                 WorldRowVisitor {
                     context: self.context,
                 },
@@ -386,6 +923,8 @@ impl<'de> Visitor<'de> for WorldFormatVisitor<'_> {
 
 struct DeserializeEntities<'a> {
     context: &'a DeserializeContext,
+    /// The version of the serialized world
+    version: u32,
     world: &'a mut World,
 }
 
@@ -414,6 +953,7 @@ impl<'de> Visitor<'de> for DeserializeEntities<'_> {
         let mut builder = EntityBuilder::new();
         while let Some(id) = seq.next_element_seed(EntityVisitor {
             context: self.context,
+            version: self.version,
             builder: &mut builder,
         })? {
             // The world that is serialized into is empty
@@ -429,6 +969,7 @@ impl<'de> Visitor<'de> for DeserializeEntities<'_> {
 /// (id, components)
 struct EntityVisitor<'a> {
     context: &'a DeserializeContext,
+    version: u32,
     builder: &'a mut EntityBuilder,
 }
 
@@ -460,6 +1001,7 @@ impl<'de> Visitor<'de> for EntityVisitor<'_> {
 
         seq.next_element_seed(DeserializeEntityData {
             context: self.context,
+            version: self.version,
             builder: self.builder,
         })?
         .ok_or_else(|| de::Error::invalid_length(0, &self))?;
@@ -471,6 +1013,7 @@ impl<'de> Visitor<'de> for EntityVisitor<'_> {
 /// Deserialize the entity data into the provided entity builder
 struct DeserializeEntityData<'a> {
     context: &'a DeserializeContext,
+    version: u32,
     builder: &'a mut EntityBuilder,
 }
 
@@ -498,6 +1041,7 @@ impl<'de> Visitor<'de> for DeserializeEntityData<'_> {
     {
         while let Some(()) = seq.next_element_seed(ComponentKeyValueDeserializer {
             context: self.context,
+            version: self.version,
             builder: self.builder,
         })? {}
 
@@ -550,12 +1094,22 @@ impl<'de> Visitor<'de> for WorldRowVisitor<'_> {
     {
         let mut world = World::new();
 
+        let version = self.context.read_version(&mut seq)?;
+
         seq.next_element_seed(DeserializeEntities {
             context: self.context,
+            version,
             world: &mut world,
         })?
         .ok_or_else(|| de::Error::invalid_length(1, &self))?;
 
+        if !self.context.resources.is_empty() {
+            seq.next_element_seed(DeserializeResources {
+                context: self.context,
+                world: &mut world,
+            })?;
+        }
+
         Ok(world)
     }
 
@@ -564,10 +1118,25 @@ impl<'de> Visitor<'de> for WorldRowVisitor<'_> {
         A: de::MapAccess<'de>,
     {
         let mut world = World::new();
+        let mut version = None;
+        let mut has_entities = false;
 
         while let Some(key) = map.next_key()? {
             match key {
-                RowFields::Entities => map.next_value_seed(DeserializeEntities {
+                RowFields::Version => {
+                    self.context
+                        .read_version_field(&mut map, &mut version, has_entities)?
+                }
+                RowFields::Entities => {
+                    map.next_value_seed(DeserializeEntities {
+                        context: self.context,
+                        version: version.unwrap_or_default(),
+                        world: &mut world,
+                    })?;
+
+                    has_entities = true;
+                }
+                RowFields::Resources => map.next_value_seed(DeserializeResources {
                     context: self.context,
                     world: &mut world,
                 })?,
@@ -596,9 +1165,14 @@ impl<'de> Visitor<'de> for WorldColumnVisitor<'_> {
     {
         let mut world = World::new();
         let mut has_archetypes = false;
+        let mut version = None;
 
         while let Some(key) = map.next_key()? {
             match key {
+                WorldFields::Version => {
+                    self.context
+                        .read_version_field(&mut map, &mut version, has_archetypes)?
+                }
                 WorldFields::Archetypes => {
                     if has_archetypes {
                         return Err(de::Error::duplicate_field("archetypes"));
@@ -606,11 +1180,16 @@ impl<'de> Visitor<'de> for WorldColumnVisitor<'_> {
 
                     map.next_value_seed(DeserializeArchetypes {
                         context: self.context,
+                        version: version.unwrap_or_default(),
                         world: &mut world,
                     })?;
 
                     has_archetypes = true;
                 }
+                WorldFields::Resources => map.next_value_seed(DeserializeResources {
+                    context: self.context,
+                    world: &mut world,
+                })?,
             }
         }
 
@@ -623,19 +1202,130 @@ impl<'de> Visitor<'de> for WorldColumnVisitor<'_> {
     {
         let mut world = World::new();
 
+        let version = self.context.read_version(&mut seq)?;
+
         seq.next_element_seed(DeserializeArchetypes {
             context: self.context,
+            version,
             world: &mut world,
         })?
         .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
+        if !self.context.resources.is_empty() {
+            seq.next_element_seed(DeserializeResources {
+                context: self.context,
+                world: &mut world,
+            })?;
+        }
+
         Ok(world)
     }
 }
 
+/// Deserializes a list of resource key-values into the world
+struct DeserializeResources<'a> {
+    context: &'a DeserializeContext,
+    world: &'a mut World,
+}
+
+impl<'de> DeserializeSeed<'de> for DeserializeResources<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DeserializeResources<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a sequence of resource key-values")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while let Some(()) = seq.next_element_seed(ResourceKeyValueDeserializer {
+            context: self.context,
+            world: self.world,
+        })? {}
+
+        Ok(())
+    }
+}
+
+/// (key, value)
+struct ResourceKeyValueDeserializer<'a> {
+    context: &'a DeserializeContext,
+    world: &'a mut World,
+}
+
+impl<'de> DeserializeSeed<'de> for ResourceKeyValueDeserializer<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de> Visitor<'de> for ResourceKeyValueDeserializer<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a resource key followed by its value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let key: Cow<'de, str> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let slot = self.context.get_resource(&key).map_err(de::Error::custom)?;
+
+        seq.next_element_seed(DeserializeResource {
+            slot,
+            world: self.world,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(1, &"a resource key followed by its value"))?;
+
+        Ok(())
+    }
+}
+
+/// A single resource value
+struct DeserializeResource<'a> {
+    slot: &'a ResourceSlot,
+    world: &'a mut World,
+}
+
+impl<'de> DeserializeSeed<'de> for DeserializeResource<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+
+        (self.slot.deser)(&mut deserializer, self.world).map_err(de::Error::custom)
+    }
+}
+
 /// Deserializes a list of archetypes
 struct DeserializeArchetypes<'a> {
     context: &'a DeserializeContext,
+    version: u32,
     world: &'a mut World,
 }
 
@@ -648,6 +1338,7 @@ impl<'de> DeserializeSeed<'de> for DeserializeArchetypes<'_> {
     {
         deserializer.deserialize_seq(ArchetypesVisitor {
             context: self.context,
+            version: self.version,
             world: self.world,
         })
     }
@@ -655,6 +1346,7 @@ impl<'de> DeserializeSeed<'de> for DeserializeArchetypes<'_> {
 
 struct ArchetypesVisitor<'a> {
     context: &'a DeserializeContext,
+    version: u32,
     world: &'a mut World,
 }
 
@@ -672,6 +1364,7 @@ impl<'de> Visitor<'de> for ArchetypesVisitor<'_> {
         let world = self.world;
         while let Some((ids, mut batch)) = seq.next_element_seed(DeserializeArchetype {
             context: self.context,
+            version: self.version,
         })? {
             world
                 .spawn_batch_at(&ids, &mut batch)
@@ -684,6 +1377,7 @@ impl<'de> Visitor<'de> for ArchetypesVisitor<'_> {
 
 struct DeserializeArchetype<'a> {
     context: &'a DeserializeContext,
+    version: u32,
 }
 
 impl<'de> DeserializeSeed<'de> for DeserializeArchetype<'_> {
@@ -698,6 +1392,7 @@ impl<'de> DeserializeSeed<'de> for DeserializeArchetype<'_> {
             2,
             ArchetypeVisitor {
                 context: self.context,
+                version: self.version,
             },
         )
     }
@@ -705,6 +1400,7 @@ impl<'de> DeserializeSeed<'de> for DeserializeArchetype<'_> {
 
 struct ArchetypeVisitor<'a> {
     context: &'a DeserializeContext,
+    version: u32,
 }
 
 impl<'de> Visitor<'de> for ArchetypeVisitor<'_> {
@@ -726,6 +1422,7 @@ impl<'de> Visitor<'de> for ArchetypeVisitor<'_> {
             .next_element_seed(DeserializeStorages {
                 len: entities.len(),
                 context: self.context,
+                version: self.version,
             })?
             .ok_or_else(|| de::Error::invalid_length(1, &self))?;
 
@@ -736,6 +1433,7 @@ impl<'de> Visitor<'de> for ArchetypeVisitor<'_> {
 struct DeserializeStorages<'a> {
     len: usize,
     context: &'a DeserializeContext,
+    version: u32,
 }
 
 impl<'de> DeserializeSeed<'de> for DeserializeStorages<'_> {
@@ -748,6 +1446,7 @@ impl<'de> DeserializeSeed<'de> for DeserializeStorages<'_> {
         deserializer.deserialize_seq(StoragesVisitor {
             len: self.len,
             context: self.context,
+            version: self.version,
         })
     }
 }
@@ -755,6 +1454,7 @@ impl<'de> DeserializeSeed<'de> for DeserializeStorages<'_> {
 struct StoragesVisitor<'a> {
     len: usize,
     context: &'a DeserializeContext,
+    version: u32,
 }
 
 impl<'de> Visitor<'de> for StoragesVisitor<'_> {
@@ -769,10 +1469,34 @@ impl<'de> Visitor<'de> for StoragesVisitor<'_> {
         A: de::SeqAccess<'de>,
     {
         let mut batch = BatchSpawn::new(self.len);
-        while let Some(storage) = seq.next_element_seed(ComponentKeyStorageDeserializer {
+        let mut unknown: Vec<UnknownComponents> = Vec::new();
+
+        while let Some(column) = seq.next_element_seed(ComponentKeyStorageDeserializer {
             context: self.context,
+            version: self.version,
             len: self.len,
         })? {
+            match column {
+                Column::Storage(storage) => batch.append(storage).map_err(de::Error::custom)?,
+                Column::Unknown { key, data } => {
+                    unknown.resize_with(self.len, Default::default);
+                    for (entity, component) in
+                        unknown.iter_mut().zip(split_column(key, data, self.len)?)
+                    {
+                        entity.push(component);
+                    }
+                }
+                Column::Skipped => {}
+            }
+        }
+
+        if !unknown.is_empty() {
+            let mut storage =
+                ArchetypeStorage::with_capacity(unknown_components().desc(), self.len);
+            for value in unknown {
+                unsafe { storage.push(value) }
+            }
+
             batch.append(storage).map_err(de::Error::custom)?;
         }
 
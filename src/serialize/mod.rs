@@ -2,13 +2,20 @@
 /// Global component serialization registry
 pub mod registry;
 
+/// Replicates the changes of a world to clients
+pub mod replication;
+
 mod de;
 mod ser;
+mod unknown;
+mod value;
 
 use alloc::string::String;
 pub use de::*;
 pub use ser::*;
 use serde::{Deserialize, Serialize};
+pub use unknown::*;
+pub use value::Value;
 
 use crate::{
     archetype::ArchetypeStorage,
@@ -29,6 +36,9 @@ type DeserializeColFn = fn(
     usize,
 ) -> erased_serde::Result<ArchetypeStorage>;
 
+type DeserializeResourceFn =
+    fn(&mut dyn erased_serde::Deserializer, &mut World) -> erased_serde::Result<()>;
+
 type SerializeFn = for<'x> fn(&'x ArchetypeStorage, slot: usize) -> &'x dyn erased_serde::Serialize;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -40,13 +50,17 @@ struct ComponentSerKey {
 #[derive(serde::Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum WorldFields {
+    Version,
     Archetypes,
+    Resources,
 }
 
 #[derive(serde::Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum RowFields {
+    Version,
     Entities,
+    Resources,
 }
 
 /// Describes the serialialization format
@@ -142,6 +156,41 @@ impl SerializationContextBuilder {
         self
     }
 
+    /// Sets the current version of the serialized format.
+    ///
+    /// See [`DeserializeBuilder::with_version`]
+    pub fn with_version(&mut self, version: u32) -> &mut Self {
+        self.ser.with_version(version);
+        self.de.with_version(version);
+        self
+    }
+
+    /// Returns the deserialization builder, used to register migrations from older versions
+    pub fn deserializer_mut(&mut self) -> &mut DeserializeBuilder {
+        &mut self.de
+    }
+
+    /// Register a resource using the type name.
+    ///
+    /// See [`Self::with_resource_name`]
+    pub fn with_resource<T>(&mut self) -> &mut Self
+    where
+        T: ComponentValue + Serialize + for<'de> Deserialize<'de>,
+    {
+        self.with_resource_name::<T>(tynm::type_name::<T>())
+    }
+
+    /// Register a resource for both serialization and deserialization
+    pub fn with_resource_name<T>(&mut self, key: impl Into<String>) -> &mut Self
+    where
+        T: ComponentValue + Serialize + for<'de> Deserialize<'de>,
+    {
+        let key = key.into();
+        self.ser.with_resource_name::<T>(key.clone());
+        self.de.with_resource_name::<T>(key);
+        self
+    }
+
     /// Finish constructing the serialize and deserialize context.
     pub fn build(&mut self) -> SerializationContext {
         SerializationContext {
@@ -193,6 +242,13 @@ impl SerializationContext {
         self.deserializer.deserialize_world()
     }
 
+    /// Deserializes a world and merges it into `world`.
+    ///
+    /// See [`DeserializeContext::deserialize_into`]
+    pub fn deserialize_into<'a>(&'a self, world: &'a mut World) -> WorldMergeDeserializer<'a> {
+        self.deserializer.deserialize_into(world)
+    }
+
     /// Deserialize a single entity's data
     pub fn deserialize_entity(&self) -> EntityDataDeserializer {
         self.deserializer.deserialize_entity()
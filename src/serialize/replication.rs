@@ -0,0 +1,731 @@
+use core::fmt;
+
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
+use serde::{
+    de::{self, DeserializeSeed, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeTuple, SerializeTupleStruct},
+    Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    archetype::{Cell, ChangeKind, Slot},
+    component::ComponentDesc,
+    delta::DeltaTracker,
+    metadata::{map_buffer_entities, replicated, Replicated},
+    world::MigratedEntities,
+    Entity, EntityBuilder, World,
+};
+
+use super::{de::Slot as ComponentSlot, DeserializeContext};
+
+/// Identifies a client of a [`ReplicationServer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClientId(u32);
+
+#[derive(Default)]
+struct ClientState {
+    /// The last tick acknowledged by the client
+    acked: u32,
+    /// The entities the client is interested in
+    interest: BTreeSet<Entity>,
+    /// The replicated entities, and the tick at which they were first sent
+    visible: BTreeMap<Entity, u32>,
+    /// The entities which left the interest set or were despawned, and the tick at which they
+    /// left
+    hidden: BTreeMap<Entity, u32>,
+}
+
+/// Creates the packets which replicate the changes of a world to each client.
+///
+/// Only components marked as [`Replicated`] are sent, and only for the entities in the interest
+/// set of each client.
+///
+/// Each packet contains the changes since the last tick acknowledged by the client, and is thus
+/// resent until acknowledged.
+///
+/// Component removals are retained until acknowledged by every connected client. A client which
+/// never acknowledges thus causes the removals to accumulate, and should be
+/// [disconnected](Self::disconnect).
+pub struct ReplicationServer {
+    clients: BTreeMap<ClientId, ClientState>,
+    next_id: u32,
+    /// The removals which are not yet acknowledged by all clients.
+    ///
+    /// Independent of [`World::track_deltas`], as other consumers may require older removals.
+    /// Pruned whenever a client acknowledges or disconnects, and not recorded while no client is
+    /// connected.
+    removals: Arc<DeltaTracker>,
+}
+
+impl ReplicationServer {
+    /// Creates a new server, and starts tracking the changes of `world` required for replication.
+    pub fn new(world: &mut World) -> Self {
+        let removals = world.track_removals();
+        removals.set_paused(true);
+
+        Self {
+            clients: BTreeMap::new(),
+            next_id: 0,
+            removals,
+        }
+    }
+
+    /// Adds a new client with an empty interest set
+    pub fn connect(&mut self) -> ClientId {
+        let id = ClientId(self.next_id);
+        self.next_id += 1;
+        self.clients.insert(id, ClientState::default());
+        self.removals.set_paused(false);
+        id
+    }
+
+    /// Removes a client
+    pub fn disconnect(&mut self, client: ClientId) {
+        self.clients.remove(&client);
+        self.prune_removals();
+    }
+
+    /// Returns the entities which are replicated to the client.
+    ///
+    /// # Panics
+    /// If the client is not connected
+    pub fn interest_mut(&mut self, client: ClientId) -> &mut BTreeSet<Entity> {
+        &mut self.client_mut(client).interest
+    }
+
+    /// Marks the packet with the given [`Packet::tick`] as received by the client.
+    ///
+    /// # Panics
+    /// If the client is not connected
+    pub fn acknowledge(&mut self, client: ClientId, tick: u32) {
+        let state = self.client_mut(client);
+        state.acked = state.acked.max(tick);
+
+        let acked = state.acked;
+        state.hidden.retain(|_, &mut v| v > acked);
+
+        self.prune_removals();
+    }
+
+    /// Returns the changes since the last acknowledged tick of the client.
+    ///
+    /// # Panics
+    /// If the client is not connected
+    pub fn packet<'a>(&'a mut self, world: &'a mut World, client: ClientId) -> Packet<'a> {
+        profile_function!();
+
+        self.prune_removals();
+
+        let world = &*world;
+        let removals = self.removals.clone();
+
+        let ClientState {
+            acked,
+            interest,
+            visible,
+            hidden,
+        } = self.client_mut(client);
+
+        let acked = *acked;
+
+        let left = visible
+            .keys()
+            .filter(|&id| !interest.contains(id) || !world.is_alive(*id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let entered = interest
+            .iter()
+            .filter(|&&id| world.is_alive(id) && !visible.contains_key(&id))
+            .copied()
+            .collect::<Vec<_>>();
+
+        // Entities entering or leaving are stamped with a new tick, as the current one may already
+        // have been acknowledged. The tick is only advanced once read, which is done by
+        // `change_tick`.
+        if !left.is_empty() || !entered.is_empty() {
+            world.advance_change_tick();
+        }
+
+        let tick = world.change_tick();
+
+        for id in left {
+            visible.remove(&id);
+            hidden.insert(id, tick);
+        }
+
+        for id in entered {
+            visible.insert(id, tick);
+            hidden.remove(&id);
+        }
+
+        let removed = removals
+            .removed_since(acked)
+            .into_iter()
+            .filter(|(id, desc)| {
+                visible.contains_key(id)
+                    && desc.meta_ref().has(replicated())
+                    && world
+                        .location(*id)
+                        .is_ok_and(|loc| !world.archetypes.get(loc.arch_id).has(desc.key()))
+            })
+            .collect();
+
+        let mut columns = Vec::new();
+        for (_, arch) in world.archetypes.iter() {
+            let entities = arch
+                .slots()
+                .iter()
+                .zip(arch.entities())
+                .filter_map(|(slot, id)| Some((slot, *id, *visible.get(id)?)))
+                .collect::<Vec<_>>();
+
+            if entities.is_empty() {
+                continue;
+            }
+
+            for cell in arch.cells() {
+                let desc = cell.desc();
+                let Some(replicated) = desc.meta_ref().get(replicated()) else {
+                    continue;
+                };
+
+                // Relations are only sent once the client knows the target, as it would otherwise
+                // be spawned without ever being despawned.
+                let target_sent = match desc.key().target() {
+                    Some(target) if target.is_static() => 0,
+                    Some(target) => match visible.get(&target) {
+                        Some(&sent) => sent,
+                        None => continue,
+                    },
+                    None => 0,
+                };
+
+                let mut ticks = alloc::vec![0; arch.len()];
+                for (slot, tick) in cell
+                    .data
+                    .borrow()
+                    .changes
+                    .get(ChangeKind::Modified)
+                    .iter_collapsed()
+                {
+                    ticks[slot] = tick;
+                }
+
+                // Entities which have not yet been received are sent in full, as are relations to
+                // targets which have not yet been received
+                let (slots, ids): (Vec<_>, Vec<_>) = entities
+                    .iter()
+                    .filter(|&&(slot, _, sent)| {
+                        sent.max(target_sent) > acked || ticks[slot] > acked
+                    })
+                    .map(|&(slot, id, _)| (slot, id))
+                    .unzip();
+
+                if !slots.is_empty() {
+                    columns.push(Column {
+                        cell,
+                        replicated: replicated.clone(),
+                        ids,
+                        slots,
+                    });
+                }
+            }
+        }
+
+        Packet {
+            tick,
+            despawned: hidden.keys().copied().collect(),
+            removed,
+            columns,
+        }
+    }
+
+    /// Discards the removals which all clients have acknowledged.
+    ///
+    /// Clients connecting later receive the entities in full, and thus never require earlier
+    /// removals.
+    fn prune_removals(&mut self) {
+        let acked = self.clients.values().map(|v| v.acked).min();
+        self.removals.prune(acked.unwrap_or(u32::MAX));
+        self.removals.set_paused(acked.is_none());
+    }
+
+    fn client_mut(&mut self, client: ClientId) -> &mut ClientState {
+        self.clients
+            .get_mut(&client)
+            .expect("Client is not connected")
+    }
+}
+
+/// The replicated changes for a single client.
+///
+/// Created by [`ReplicationServer::packet`], and applied using [`ReplicationClient::apply`].
+pub struct Packet<'a> {
+    tick: u32,
+    despawned: Vec<Entity>,
+    removed: Vec<(Entity, ComponentDesc)>,
+    columns: Vec<Column<'a>>,
+}
+
+impl Packet<'_> {
+    /// Returns the server tick of the packet, which is to be acknowledged by the client.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns true if the packet contains no changes
+    pub fn is_empty(&self) -> bool {
+        self.despawned.is_empty() && self.removed.is_empty() && self.columns.is_empty()
+    }
+}
+
+impl fmt::Debug for Packet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Packet")
+            .field("tick", &self.tick)
+            .field("despawned", &self.despawned)
+            .field("removed", &self.removed)
+            .field("columns", &self.columns.len())
+            .finish()
+    }
+}
+
+/// The changed values of a component
+struct Column<'a> {
+    cell: &'a Cell,
+    replicated: Replicated,
+    ids: Vec<Entity>,
+    slots: Vec<Slot>,
+}
+
+impl Serialize for Packet<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_tuple_struct("Packet", 4)?;
+        state.serialize_field(&self.tick)?;
+        state.serialize_field(&self.despawned)?;
+        state.serialize_field(&SerializeRemoved(&self.removed))?;
+        state.serialize_field(&SerializeColumns(&self.columns))?;
+        state.end()
+    }
+}
+
+struct SerializeRemoved<'a>(&'a [(Entity, ComponentDesc)]);
+
+impl Serialize for SerializeRemoved<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for (id, desc) in self.0 {
+            seq.serialize_element(&(id, desc.name(), desc.key().target()))?;
+        }
+
+        seq.end()
+    }
+}
+
+struct SerializeColumns<'a>(&'a [Column<'a>]);
+
+impl Serialize for SerializeColumns<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for column in self.0 {
+            seq.serialize_element(column)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl Serialize for Column<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let desc = self.cell.desc();
+        let data = self.cell.data.borrow();
+
+        let mut state = serializer.serialize_tuple(4)?;
+        state.serialize_element(desc.name())?;
+        state.serialize_element(&desc.key().target())?;
+        state.serialize_element(&self.ids)?;
+        state.serialize_element(&SerializeValues {
+            values: self
+                .slots
+                .iter()
+                .map(|&slot| (self.replicated.serialize)(&data.storage, slot)),
+        })?;
+
+        state.end()
+    }
+}
+
+struct SerializeValues<I> {
+    values: I,
+}
+
+impl<'a, I> Serialize for SerializeValues<I>
+where
+    I: ExactSizeIterator<Item = &'a dyn erased_serde::Serialize> + Clone,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.values.len()))?;
+        for value in self.values.clone() {
+            seq.serialize_element(value)?;
+        }
+
+        seq.end()
+    }
+}
+
+/// Applies the packets of a [`ReplicationServer`] to a local world.
+///
+/// The server entities are mapped to local entities, which are spawned as needed. This includes
+/// relation targets, and the ids contained in [`EntityMappable`](crate::metadata::EntityMappable)
+/// components. Contained ids of entities which have not been replicated are kept as is.
+pub struct ReplicationClient {
+    context: DeserializeContext,
+    ids: MigratedEntities,
+}
+
+impl ReplicationClient {
+    /// Creates a new client which deserializes the components registered in `context`
+    pub fn new(context: DeserializeContext) -> Self {
+        Self {
+            context,
+            ids: MigratedEntities::default(),
+        }
+    }
+
+    /// Returns the mapping from server entities to local entities
+    pub fn ids(&self) -> &MigratedEntities {
+        &self.ids
+    }
+
+    /// Applies a serialized [`Packet`] to the world.
+    ///
+    /// Returns the tick of the packet, which is to be acknowledged to the server.
+    pub fn apply<'a>(&'a mut self, world: &'a mut World) -> PacketDeserializer<'a> {
+        PacketDeserializer {
+            context: &self.context,
+            ids: &mut self.ids,
+            world,
+        }
+    }
+}
+
+/// Returns the local entity of a server entity, spawning it if necessary
+fn local_id(ids: &mut MigratedEntities, world: &mut World, id: Entity) -> Entity {
+    match ids.ids.get(&id) {
+        // The local entity may have been despawned through a relation
+        Some(&local) if world.is_alive(local) => local,
+        _ => {
+            let local = world.spawn();
+            ids.ids.insert(id, local);
+            local
+        }
+    }
+}
+
+/// Deserializes and applies a [`Packet`]
+pub struct PacketDeserializer<'a> {
+    context: &'a DeserializeContext,
+    ids: &'a mut MigratedEntities,
+    world: &'a mut World,
+}
+
+impl<'de> DeserializeSeed<'de> for PacketDeserializer<'_> {
+    type Value = u32;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple_struct("Packet", 4, self)
+    }
+}
+
+impl<'de> Visitor<'de> for PacketDeserializer<'_> {
+    type Value = u32;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a replication packet")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let tick: u32 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let despawned: Vec<Entity> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        for id in despawned {
+            if let Some(local) = self.ids.ids.remove(&id) {
+                // The entity may already have been despawned through a relation
+                let _ = self.world.despawn(local);
+            }
+        }
+
+        let removed: Vec<(Entity, Cow<'de, str>, Option<Entity>)> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+        for (id, key, target) in removed {
+            let slot = self.context.get(&key).map_err(de::Error::custom)?;
+            if let Some(&local) = self.ids.ids.get(&id) {
+                let desc = slot.desc.with_relation(target.map(|v| self.ids.get(v)));
+                let _ = self.world.remove_dyn(local, desc);
+            }
+        }
+
+        let mut builders = BTreeMap::new();
+        seq.next_element_seed(ColumnsDeserializer {
+            context: self.context,
+            ids: self.ids,
+            world: self.world,
+            builders: &mut builders,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(3, &"a replication packet"))?;
+
+        for (id, mut builder) in builders {
+            // Entity ids contained in the values refer to server entities
+            map_buffer_entities(builder.buffer_mut(), &|v| self.ids.get(v));
+
+            builder
+                .append_to(self.world, id)
+                .map_err(de::Error::custom)?;
+        }
+
+        Ok(tick)
+    }
+}
+
+/// Deserializes the columns of a packet into a builder for each local entity
+struct ColumnsDeserializer<'a> {
+    context: &'a DeserializeContext,
+    ids: &'a mut MigratedEntities,
+    world: &'a mut World,
+    builders: &'a mut BTreeMap<Entity, EntityBuilder>,
+}
+
+impl<'de> DeserializeSeed<'de> for ColumnsDeserializer<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ColumnsDeserializer<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of columns")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while let Some(()) = seq.next_element_seed(ColumnDeserializer {
+            context: self.context,
+            ids: self.ids,
+            world: self.world,
+            builders: self.builders,
+        })? {}
+
+        Ok(())
+    }
+}
+
+/// (key, target, [ids], [values])
+struct ColumnDeserializer<'a> {
+    context: &'a DeserializeContext,
+    ids: &'a mut MigratedEntities,
+    world: &'a mut World,
+    builders: &'a mut BTreeMap<Entity, EntityBuilder>,
+}
+
+impl<'de> DeserializeSeed<'de> for ColumnDeserializer<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(4, self)
+    }
+}
+
+impl<'de> Visitor<'de> for ColumnDeserializer<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a component column")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let key: Cow<'de, str> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let target: Option<Entity> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        let ids: Vec<Entity> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+        let slot = self.context.get(&key).map_err(de::Error::custom)?;
+
+        // Static entities are the same in all worlds
+        let target = target.map(|v| {
+            if v.is_static() {
+                v
+            } else {
+                local_id(self.ids, self.world, v)
+            }
+        });
+        let ids = ids
+            .into_iter()
+            .map(|v| local_id(self.ids, self.world, v))
+            .collect::<Vec<_>>();
+
+        seq.next_element_seed(ValuesDeserializer {
+            slot,
+            desc: slot.desc.with_relation(target),
+            ids: &ids,
+            builders: self.builders,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(3, &"a component column"))?;
+
+        Ok(())
+    }
+}
+
+/// Deserializes a value for each entity of a column
+struct ValuesDeserializer<'a> {
+    slot: &'a ComponentSlot,
+    desc: ComponentDesc,
+    ids: &'a [Entity],
+    builders: &'a mut BTreeMap<Entity, EntityBuilder>,
+}
+
+impl<'de> DeserializeSeed<'de> for ValuesDeserializer<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValuesDeserializer<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of {} values", self.ids.len())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        for (i, id) in self.ids.iter().enumerate() {
+            seq.next_element_seed(ValueDeserializer {
+                slot: self.slot,
+                desc: self.desc,
+                builder: self.builders.entry(*id).or_default(),
+            })?
+            .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct ValueDeserializer<'a> {
+    slot: &'a ComponentSlot,
+    desc: ComponentDesc,
+    builder: &'a mut EntityBuilder,
+}
+
+impl<'de> DeserializeSeed<'de> for ValueDeserializer<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.slot.deser_one)(&mut deserializer, self.desc, self.builder).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component, metadata::Replicated};
+
+    use super::*;
+
+    component! {
+        health: f32 => [ Replicated ],
+    }
+
+    #[test]
+    fn prune_removals() {
+        let mut world = World::new();
+        let mut server = ReplicationServer::new(&mut world);
+
+        let id = Entity::builder().set(health(), 1.0).spawn(&mut world);
+
+        // Nothing retains the removals without clients
+        world.remove(id, health()).unwrap();
+        assert!(server.removals.removed_since(0).is_empty());
+
+        let client = server.connect();
+        world.set(id, health(), 1.0).unwrap();
+        world.remove(id, health()).unwrap();
+        assert_eq!(server.removals.removed_since(0).len(), 1);
+
+        server.acknowledge(client, world.change_tick());
+        assert!(server.removals.removed_since(0).is_empty());
+
+        world.set(id, health(), 1.0).unwrap();
+        world.remove(id, health()).unwrap();
+        assert_eq!(server.removals.removed_since(0).len(), 1);
+
+        server.disconnect(client);
+        assert!(server.removals.removed_since(0).is_empty());
+    }
+}
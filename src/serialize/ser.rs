@@ -6,13 +6,17 @@ use crate::{
     Component, Entity, EntityRef, RelationExt, World,
 };
 
-use alloc::{collections::BTreeMap, string::String};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::any::{Any, TypeId};
+use itertools::Itertools;
 use serde::{
     ser::{SerializeSeq, SerializeStructVariant, SerializeTupleStruct},
     Serialize, Serializer,
 };
 
-use super::{registry::REGISTRY, SerializeFormat};
+use super::{
+    registry::REGISTRY, unknown_components, SerializeFormat, UnknownComponent, UnknownComponents,
+};
 
 #[derive(Clone)]
 struct Slot {
@@ -21,10 +25,18 @@ struct Slot {
     key: String,
 }
 
+#[derive(Clone)]
+struct ResourceSlot {
+    ser: fn(&dyn Any) -> &dyn erased_serde::Serialize,
+    key: String,
+}
+
 #[derive(Clone)]
 /// Builder for a serialialization context
 pub struct SerializeBuilder {
     slots: BTreeMap<Entity, Slot>,
+    resources: BTreeMap<TypeId, ResourceSlot>,
+    version: Option<u32>,
 }
 
 impl SerializeBuilder {
@@ -32,6 +44,8 @@ impl SerializeBuilder {
     pub fn new() -> Self {
         Self {
             slots: Default::default(),
+            resources: Default::default(),
+            version: None,
         }
     }
 }
@@ -133,10 +147,52 @@ impl SerializeBuilder {
         self
     }
 
+    /// Register a resource using the type name.
+    ///
+    /// See [`Self::with_resource_name`]
+    pub fn with_resource<T>(&mut self) -> &mut Self
+    where
+        T: ComponentValue + Serialize,
+    {
+        self.with_resource_name::<T>(tynm::type_name::<T>())
+    }
+
+    /// Register a resource to be serialized alongside the world if present
+    pub fn with_resource_name<T>(&mut self, key: impl Into<String>) -> &mut Self
+    where
+        T: ComponentValue + Serialize,
+    {
+        fn ser_resource<T: Serialize + ComponentValue>(
+            value: &dyn Any,
+        ) -> &dyn erased_serde::Serialize {
+            value.downcast_ref::<T>().unwrap()
+        }
+
+        self.resources.insert(
+            TypeId::of::<T>(),
+            ResourceSlot {
+                key: key.into(),
+                ser: ser_resource::<T>,
+            },
+        );
+
+        self
+    }
+
+    /// Writes `version` to the header of serialized worlds.
+    ///
+    /// See [`DeserializeBuilder::with_version`](super::DeserializeBuilder::with_version)
+    pub fn with_version(&mut self, version: u32) -> &mut Self {
+        self.version = Some(version);
+        self
+    }
+
     /// Finish constructing the serialization context
     pub fn build(&mut self) -> SerializeContext {
         SerializeContext {
             slots: self.slots.clone(),
+            resources: self.resources.clone(),
+            version: self.version,
         }
     }
 }
@@ -145,6 +201,8 @@ impl SerializeBuilder {
 /// and an optional filter. Empty entities will be skipped.
 pub struct SerializeContext {
     slots: BTreeMap<Entity, Slot>,
+    resources: BTreeMap<TypeId, ResourceSlot>,
+    version: Option<u32>,
 }
 
 impl SerializeContext {
@@ -185,10 +243,11 @@ impl SerializeContext {
     ) -> impl Iterator<Item = (ArchetypeId, &'a Archetype)> + 'a {
         world.archetypes.iter().filter(move |(_, arch)| {
             !arch.is_empty()
-                && arch
+                && (arch
                     .components()
                     .keys()
                     .any(|id| self.slots.contains_key(&id.id))
+                    || arch.has(unknown_components().key()))
                 && !arch.has(component_info().key())
                 && filter.filter_static(arch)
         })
@@ -208,9 +267,17 @@ impl<F: StaticFilter> Serialize for WorldSerializer<'_, F> {
     where
         S: Serializer,
     {
-        match self.format {
+        // Resources are only a part of the format when registered
+        let has_resources = !self.context.resources.is_empty();
+        let len = 1 + has_resources as usize + self.context.version.is_some() as usize;
+
+        let mut state = match self.format {
             SerializeFormat::RowMajor => {
-                let mut state = serializer.serialize_struct_variant("World", 0, "row", 1)?;
+                let mut state = serializer.serialize_struct_variant("World", 0, "row", len)?;
+                if let Some(version) = self.context.version {
+                    state.serialize_field("version", &version)?;
+                }
+
                 state.serialize_field(
                     "entities",
                     &SerializeEntities {
@@ -219,10 +286,14 @@ impl<F: StaticFilter> Serialize for WorldSerializer<'_, F> {
                         filter: &self.filter,
                     },
                 )?;
-                state.end()
+                state
             }
             SerializeFormat::ColumnMajor => {
-                let mut state = serializer.serialize_struct_variant("World", 1, "col", 1)?;
+                let mut state = serializer.serialize_struct_variant("World", 1, "col", len)?;
+                if let Some(version) = self.context.version {
+                    state.serialize_field("version", &version)?;
+                }
+
                 state.serialize_field(
                     "archetypes",
                     &SerializeArchetypes {
@@ -231,9 +302,48 @@ impl<F: StaticFilter> Serialize for WorldSerializer<'_, F> {
                         filter: &self.filter,
                     },
                 )?;
-                state.end()
+                state
             }
+        };
+
+        if has_resources {
+            state.serialize_field(
+                "resources",
+                &SerializeResources {
+                    world: self.world,
+                    context: self.context,
+                },
+            )?;
+        }
+
+        state.end()
+    }
+}
+
+struct SerializeResources<'a> {
+    world: &'a World,
+    context: &'a SerializeContext,
+}
+
+impl Serialize for SerializeResources<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let resources = self
+            .context
+            .resources
+            .iter()
+            .filter_map(|(&ty, slot)| Some((slot, self.world.resources.get_dyn(ty)?)))
+            .collect_vec();
+
+        let mut seq = serializer.serialize_seq(Some(resources.len()))?;
+
+        for (slot, value) in &resources {
+            seq.serialize_element(&(&slot.key, (slot.ser)(&**value)))?;
         }
+
+        seq.end()
     }
 }
 
@@ -308,12 +418,18 @@ impl Serialize for EntityDataSerializer<'_> {
     where
         S: Serializer,
     {
+        let unknown = self
+            .arch
+            .borrow::<UnknownComponents>(unknown_components().key());
+        let unknown = unknown.as_ref().map(|v| &v.get()[self.slot]);
+
         let component_count = self
             .arch
             .components()
             .keys()
             .filter(|key| self.context.slots.contains_key(&key.id()))
-            .count();
+            .count()
+            + unknown.map_or(0, |v| v.len());
 
         let mut state = serializer.serialize_seq(Some(component_count))?;
         for cell in self.arch.cells() {
@@ -328,6 +444,10 @@ impl Serialize for EntityDataSerializer<'_> {
             }
         }
 
+        for component in unknown.into_iter().flatten() {
+            state.serialize_element(&UnknownKeyValueSerializer { component })?;
+        }
+
         state.end()
     }
 }
@@ -343,29 +463,61 @@ impl<F: StaticFilter> serde::Serialize for SerializeArchetypes<'_, F> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(
-            self.context.archetypes(self.world, self.filter).count(),
-        ))?;
+        let archetypes = self
+            .context
+            .archetypes(self.world, self.filter)
+            .flat_map(|(_, arch)| {
+                // Entities with different unknown components can not share columns
+                let groups = match arch.borrow::<UnknownComponents>(unknown_components().key()) {
+                    Some(unknown) => group_unknown(unknown.get()).into_iter().map(Some).collect(),
+                    None => alloc::vec![None],
+                };
+
+                groups.into_iter().map(|slots| SerializeArchetype {
+                    context: self.context,
+                    arch,
+                    slots,
+                })
+            })
+            .collect_vec();
 
-        for (_, arch) in self.context.archetypes(self.world, self.filter) {
-            state.serialize_element(&SerializeArchetype {
-                context: self.context,
-                arch,
-            })?;
+        let mut state = serializer.serialize_seq(Some(archetypes.len()))?;
+
+        for arch in &archetypes {
+            state.serialize_element(arch)?;
         }
 
         state.end()
     }
 }
 
+/// Groups the slots of an archetype by the layout of their unknown components
+fn group_unknown(unknown: &[UnknownComponents]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (slot, value) in unknown.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|group| unknown[group[0]].has_same_columns(value))
+        {
+            Some(group) => group.push(slot),
+            None => groups.push(alloc::vec![slot]),
+        }
+    }
+
+    groups
+}
+
 struct SerializeArchetype<'a> {
     arch: &'a Archetype,
     context: &'a SerializeContext,
+    /// Serialize only a subset of the entities
+    slots: Option<Vec<usize>>,
 }
 
 struct SerializeStorage<'a> {
     storage: &'a ArchetypeStorage,
     slot: &'a Slot,
+    slots: Option<&'a [usize]>,
 }
 
 impl serde::Serialize for SerializeStorage<'_> {
@@ -374,6 +526,10 @@ impl serde::Serialize for SerializeStorage<'_> {
         S: Serializer,
     {
         let ser_fn = self.slot.ser;
+        if let Some(slots) = self.slots {
+            return serializer.collect_seq(slots.iter().map(|&slot| ser_fn(self.storage, slot)));
+        }
+
         let mut seq = serializer.serialize_seq(Some(self.storage.len()))?;
         for slot in 0..self.storage.len() {
             seq.serialize_element(ser_fn(self.storage, slot))?;
@@ -386,6 +542,7 @@ impl serde::Serialize for SerializeStorage<'_> {
 struct SerializeStorages<'a> {
     arch: &'a Archetype,
     context: &'a SerializeContext,
+    slots: Option<&'a [usize]>,
 }
 
 impl serde::Serialize for SerializeStorages<'_> {
@@ -393,12 +550,23 @@ impl serde::Serialize for SerializeStorages<'_> {
     where
         S: serde::Serializer,
     {
+        let unknown = self
+            .arch
+            .borrow::<UnknownComponents>(unknown_components().key());
+
+        // All entities of the group share the layout of the unknown components
+        let unknown_columns = match (&unknown, self.slots) {
+            (Some(unknown), Some(&[first, ..])) => unknown.get()[first].len(),
+            _ => 0,
+        };
+
         let len = self
             .arch
             .components()
             .keys()
             .filter(|key| self.context.slots.contains_key(&key.id()))
-            .count();
+            .count()
+            + unknown_columns;
 
         let mut state = serializer.serialize_seq(Some(len))?;
 
@@ -411,6 +579,17 @@ impl serde::Serialize for SerializeStorages<'_> {
                     key: data.key,
                     slot,
                     storage: &data.storage,
+                    slots: self.slots,
+                })?;
+            }
+        }
+
+        if let (Some(unknown), Some(slots)) = (&unknown, self.slots) {
+            for index in 0..unknown_columns {
+                state.serialize_element(&UnknownKeyColumnSerializer {
+                    unknown: unknown.get(),
+                    slots,
+                    index,
                 })?;
             }
         }
@@ -423,6 +602,7 @@ struct ComponentKeyStorageSerializer<'a> {
     key: ComponentKey,
     slot: &'a Slot,
     storage: &'a ArchetypeStorage,
+    slots: Option<&'a [usize]>,
 }
 
 impl Serialize for ComponentKeyStorageSerializer<'_> {
@@ -442,6 +622,7 @@ impl Serialize for ComponentKeyStorageSerializer<'_> {
         s.serialize_element(&SerializeStorage {
             storage: self.storage,
             slot: self.slot,
+            slots: self.slots,
         })?;
 
         s.end()
@@ -473,18 +654,111 @@ impl Serialize for ComponentKeyValueSerializer<'_> {
     }
 }
 
+/// Serializes an unknown component as it was deserialized
+struct UnknownKeyValueSerializer<'a> {
+    component: &'a UnknownComponent,
+}
+
+impl Serialize for UnknownKeyValueSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let data = self.component.data();
+        let mut s = serializer.serialize_seq(Some(1 + data.len()))?;
+        s.serialize_element(self.component.key())?;
+        for value in data {
+            s.serialize_element(value)?;
+        }
+
+        s.end()
+    }
+}
+
+/// Serializes the `index`th unknown component of each entity as a column
+struct UnknownKeyColumnSerializer<'a> {
+    unknown: &'a [UnknownComponents],
+    slots: &'a [usize],
+    index: usize,
+}
+
+impl Serialize for UnknownKeyColumnSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let component = &self.unknown[self.slots[0]].components()[self.index];
+        let header = component.header();
+
+        let mut s = serializer.serialize_seq(Some(2 + header.len()))?;
+        s.serialize_element(component.key())?;
+        for value in header {
+            s.serialize_element(value)?;
+        }
+
+        s.serialize_element(&UnknownColumnSerializer {
+            unknown: self.unknown,
+            slots: self.slots,
+            index: self.index,
+        })?;
+
+        s.end()
+    }
+}
+
+struct UnknownColumnSerializer<'a> {
+    unknown: &'a [UnknownComponents],
+    slots: &'a [usize],
+    index: usize,
+}
+
+impl Serialize for UnknownColumnSerializer<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(
+            self.slots
+                .iter()
+                .map(|&slot| self.unknown[slot].components()[self.index].value()),
+        )
+    }
+}
+
 impl serde::Serialize for SerializeArchetype<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         let mut state = serializer.serialize_tuple_struct("Arch", 3)?;
-        state.serialize_field(self.arch.entities())?;
+        match &self.slots {
+            Some(slots) => {
+                let entities = self.arch.entities();
+                state.serialize_field(&SerializeEntityIds { entities, slots })?
+            }
+            None => state.serialize_field(self.arch.entities())?,
+        }
+
         state.serialize_field(&SerializeStorages {
             arch: self.arch,
             context: self.context,
+            slots: self.slots.as_deref(),
         })?;
 
         state.end()
     }
 }
+
+struct SerializeEntityIds<'a> {
+    entities: &'a [Entity],
+    slots: &'a [usize],
+}
+
+impl Serialize for SerializeEntityIds<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.slots.iter().map(|&slot| self.entities[slot]))
+    }
+}
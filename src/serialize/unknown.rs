@@ -0,0 +1,189 @@
+use alloc::{string::String, vec::Vec};
+use serde::de::{self, DeserializeSeed, Deserializer};
+
+use crate::{entity_ids, Debuggable, Entity, Query, World};
+
+use super::{Value, WorldDeserializer};
+
+component! {
+    /// Components which were not known to the [`DeserializeContext`](super::DeserializeContext)
+    /// of a world.
+    ///
+    /// Kept as opaque data so they are written back when the entity is serialized again.
+    ///
+    /// See [`DeserializeBuilder::keep_unknown`](super::DeserializeBuilder::keep_unknown)
+    pub unknown_components: UnknownComponents => [ Debuggable ],
+}
+
+/// A component which was not registered when deserializing
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownComponent {
+    key: String,
+    /// The serialized elements following the key, i.e; the relation target, if any, and the value
+    data: Vec<Value>,
+}
+
+impl UnknownComponent {
+    pub(super) fn new(key: String, data: Vec<Value>) -> Self {
+        debug_assert!(!data.is_empty(), "Unknown component without a value");
+        Self { key, data }
+    }
+
+    /// Returns the serialized component key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the serialized value
+    pub fn value(&self) -> &Value {
+        self.data.last().expect("Component data contains a value")
+    }
+
+    /// Returns the raw serialized data following the key, such as the relation target and the
+    /// value
+    pub fn data(&self) -> &[Value] {
+        &self.data
+    }
+
+    /// Returns the data preceding the value, which is shared by a column
+    pub(super) fn header(&self) -> &[Value] {
+        &self.data[..self.data.len() - 1]
+    }
+}
+
+/// The unknown components of an entity, in the order they were deserialized.
+///
+/// **Note**: entity ids contained in the data are not remapped when merging worlds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnknownComponents {
+    components: Vec<UnknownComponent>,
+}
+
+impl UnknownComponents {
+    pub(super) fn push(&mut self, component: UnknownComponent) {
+        self.components.push(component)
+    }
+
+    pub(super) fn components(&self) -> &[UnknownComponent] {
+        &self.components
+    }
+
+    /// Returns true if the components can be serialized in the same columns as `other`
+    pub(super) fn has_same_columns(&self, other: &Self) -> bool {
+        self.components.len() == other.components.len()
+            && self
+                .components
+                .iter()
+                .zip(&other.components)
+                .all(|(a, b)| a.key == b.key && a.header() == b.header())
+    }
+
+    /// Returns the unknown component with the given key
+    pub fn get(&self, key: &str) -> Option<&UnknownComponent> {
+        self.components.iter().find(|v| v.key == key)
+    }
+
+    /// Iterate the unknown components
+    pub fn iter(&self) -> core::slice::Iter<'_, UnknownComponent> {
+        self.components.iter()
+    }
+
+    /// Returns the number of unknown components
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns true if there are no unknown components
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a UnknownComponents {
+    type Item = &'a UnknownComponent;
+    type IntoIter = core::slice::Iter<'a, UnknownComponent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Lists the components which were not known when deserializing a world
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnknownReport {
+    entries: Vec<(Entity, UnknownComponent)>,
+}
+
+impl UnknownReport {
+    /// Collects the [`UnknownComponents`] of all entities in `world`
+    pub fn from_world(world: &World) -> Self {
+        let mut query = Query::new((entity_ids(), unknown_components()));
+
+        let entries = query
+            .borrow(world)
+            .iter()
+            .flat_map(|(id, unknown)| unknown.iter().map(move |v| (id, v.clone())))
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Returns the unknown components and the entity they belong to
+    pub fn entries(&self) -> &[(Entity, UnknownComponent)] {
+        &self.entries
+    }
+
+    /// Returns the distinct unknown component keys
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<_> = self.entries.iter().map(|(_, v)| v.key()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Returns true if all components were known
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Deserializes a world and reports the components which were not known
+pub struct ReportingWorldDeserializer<'a> {
+    pub(super) inner: WorldDeserializer<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for ReportingWorldDeserializer<'_> {
+    type Value = (World, UnknownReport);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let world = self.inner.deserialize(deserializer)?;
+        let report = UnknownReport::from_world(&world);
+
+        Ok((world, report))
+    }
+}
+
+/// Splits the deserialized column of an unknown component into the data of each entity
+pub(super) fn split_column<E: de::Error>(
+    key: String,
+    mut data: Vec<Value>,
+    len: usize,
+) -> Result<impl Iterator<Item = UnknownComponent>, E> {
+    let values = match data.pop() {
+        Some(Value::Seq(values)) if values.len() == len => values,
+        _ => {
+            return Err(de::Error::custom(format_args!(
+                "Expected a column of {len} values for unknown component {key:?}"
+            )))
+        }
+    };
+
+    Ok(values.into_iter().map(move |value| {
+        let mut data = data.clone();
+        data.push(value);
+        UnknownComponent::new(key.clone(), data)
+    }))
+}
@@ -0,0 +1,58 @@
+use core::fmt::{self, Debug, Formatter};
+
+use alloc::vec::Vec;
+
+use crate::{
+    archetype::{ArchetypeStorage, ChangeList},
+    entity::EntityStore,
+    Entity,
+};
+
+/// A copy of the entities and components of a world, which can be restored at a later point.
+///
+/// Created by [`World::snapshot`](crate::World::snapshot) and applied using
+/// [`World::restore`](crate::World::restore).
+///
+/// Components which are not [`Cloneable`](crate::metadata::Cloneable), or are marked as
+/// [`NoRollback`](crate::metadata::NoRollback) are not part of the snapshot.
+pub struct WorldSnapshot {
+    pub(crate) entities: EntityStore,
+    pub(crate) archetypes: Vec<ArchetypeSnapshot>,
+}
+
+/// The entities and cloned component columns of a single archetype
+pub(crate) struct ArchetypeSnapshot {
+    pub(crate) entities: Vec<Entity>,
+    pub(crate) cells: Vec<CellSnapshot>,
+}
+
+/// The cloned values of a component column, and the change ticks of each slot
+pub(crate) struct CellSnapshot {
+    pub(crate) storage: ArchetypeStorage,
+    pub(crate) changes: [ChangeList; 3],
+}
+
+// The stored components are Send + Sync
+unsafe impl Send for WorldSnapshot {}
+unsafe impl Sync for WorldSnapshot {}
+
+impl WorldSnapshot {
+    /// Returns the number of entities in the snapshot
+    pub fn len(&self) -> usize {
+        self.archetypes.iter().map(|v| v.entities.len()).sum()
+    }
+
+    /// Returns true if the snapshot contains no entities
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Debug for WorldSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorldSnapshot")
+            .field("entities", &self.len())
+            .field("archetypes", &self.archetypes.len())
+            .finish()
+    }
+}
@@ -0,0 +1,92 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt::{self, Formatter};
+
+use crate::World;
+
+use super::{Access, System, SystemContext, SystemData, SystemFn};
+
+/// Abstraction over a system which decides if another system should run
+#[doc(hidden)]
+pub trait DynCondition {
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result;
+    fn evaluate(&mut self, ctx: &SystemContext<'_, '_, '_>) -> bool;
+    fn access(&self, world: &World, dst: &mut Vec<Access>);
+}
+
+impl<F, Args> DynCondition for System<F, Args, bool>
+where
+    Args: for<'x> SystemData<'x>,
+    F: for<'x> SystemFn<'x, <Args as SystemData<'x>>::Value, bool>,
+{
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("fn ")?;
+        f.write_str(&self.name)?;
+        self.data.describe(f)?;
+        f.write_str(" -> bool")
+    }
+
+    fn evaluate(&mut self, ctx: &SystemContext<'_, '_, '_>) -> bool {
+        if !self.should_run(ctx) {
+            return false;
+        }
+
+        let data = self.data.acquire(ctx);
+        self.func.execute(data)
+    }
+
+    fn access(&self, world: &World, dst: &mut Vec<Access>) {
+        self.data.access(world, dst);
+        self.conditions_access(world, dst);
+    }
+}
+
+/// A type erased run condition of a system.
+///
+/// Created from a [`System`] returning `bool`, which allows the condition to declare its accesses
+/// precisely, and run in parallel with other systems.
+pub struct BoxedCondition {
+    inner: Box<dyn DynCondition + Send + Sync>,
+}
+
+impl BoxedCondition {
+    /// Creates a condition from a predicate over the world.
+    ///
+    /// **Note**: As the whole world is accessed, the condition creates a barrier to other systems.
+    /// Prefer creating the condition from a [`System`] with specific accesses instead.
+    pub fn from_fn<F>(mut predicate: F) -> Self
+    where
+        F: 'static + FnMut(&World) -> bool + Send + Sync,
+    {
+        System::builder()
+            .with_name(tynm::type_name::<F>())
+            .with_world()
+            .build(move |world: &World| predicate(world))
+            .into()
+    }
+
+    pub(crate) fn evaluate(&mut self, ctx: &SystemContext<'_, '_, '_>) -> bool {
+        self.inner.evaluate(ctx)
+    }
+
+    /// Returns the accesses of the condition
+    pub fn access(&self, world: &World, dst: &mut Vec<Access>) {
+        self.inner.access(world, dst)
+    }
+}
+
+impl core::fmt::Debug for BoxedCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.inner.describe(f)
+    }
+}
+
+impl<T> From<T> for BoxedCondition
+where
+    T: 'static + Send + Sync + DynCondition,
+{
+    fn from(condition: T) -> Self {
+        Self {
+            inner: Box::new(condition),
+        }
+    }
+}
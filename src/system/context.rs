@@ -114,6 +114,11 @@ impl<'a, 'b, 'input> SystemContext<'a, 'b, 'input> {
         AtomicRefMut::map(borrow, |v| *v)
     }
 
+    /// Returns the type erased input, for forwarding it to a nested schedule
+    pub(crate) fn input_dyn(&self) -> &'b dyn ExtractDyn<'b, 'input> {
+        self.input
+    }
+
     /// Access user provided input data
     #[inline]
     pub fn input<T: 'static>(&self) -> Option<AtomicRef<T>> {
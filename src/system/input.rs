@@ -48,6 +48,56 @@ impl<'a, T: 'static + Send + Sync> IntoInput<'a> for &'a mut T {
     }
 }
 
+/// Extends an existing input with additional values, shadowing the outer input on conflicting
+/// types.
+pub(crate) struct InputChain<'a, 'b, A, B> {
+    outer: &'b A,
+    inner: B,
+    _marker: core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a, 'b, A, B> InputChain<'a, 'b, A, B> {
+    pub(crate) fn new(outer: &'b A, inner: B) -> Self {
+        Self {
+            outer,
+            inner,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+unsafe impl<'x, 'a: 'b, 'b, A, B> ExtractDyn<'x, 'b> for InputChain<'a, 'b, A, B>
+where
+    A: ExtractDyn<'x, 'a>,
+    B: ExtractDyn<'x, 'b>,
+{
+    unsafe fn extract_dyn(&'x self, ty: TypeId) -> Option<&'x AtomicRefCell<NonNull<()>>> {
+        self.inner
+            .extract_dyn(ty)
+            .or_else(|| self.outer.extract_dyn(ty))
+    }
+}
+
+impl<'a: 'b, 'b, A, B> IntoInput<'b> for InputChain<'a, 'b, A, B>
+where
+    Self: for<'x> ExtractDyn<'x, 'b>,
+{
+    type Output = Self;
+
+    fn into_input(self) -> Self::Output {
+        self
+    }
+}
+
+/// The input of an executing system, forwarded to the systems of a nested schedule
+pub(crate) struct NestedInput<'b, 'input>(pub(crate) &'b dyn ExtractDyn<'b, 'input>);
+
+unsafe impl<'x, 'b, 'input> ExtractDyn<'x, 'input> for NestedInput<'b, 'input> {
+    unsafe fn extract_dyn(&'x self, ty: TypeId) -> Option<&'x AtomicRefCell<NonNull<()>>> {
+        self.0.extract_dyn(ty)
+    }
+}
+
 pub struct ErasedCell<'a, T: ?Sized> {
     cell: AtomicRefCell<NonNull<()>>,
     _marker: core::marker::PhantomData<&'a mut T>,
@@ -1,17 +1,19 @@
+mod condition;
 mod context;
 mod input;
+mod stats;
 mod traits;
 
 use crate::{
     archetype::{ArchetypeId, ArchetypeInfo},
-    component::ComponentKey,
+    component::{ComponentKey, ComponentValue},
     query::{QueryData, QueryStrategy},
     util::TuplePush,
     CommandBuffer, Fetch, FetchItem, Query, World,
 };
 use alloc::{
     boxed::Box,
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     format,
     string::{String, ToString},
     vec::Vec,
@@ -22,11 +24,17 @@ use core::{
     marker::PhantomData,
 };
 
+pub use condition::{BoxedCondition, DynCondition};
 pub use context::*;
 pub use input::IntoInput;
-pub use traits::{AsBorrowed, SystemAccess, SystemData, SystemFn};
+pub(crate) use input::{InputChain, NestedInput};
+pub use stats::SystemStats;
+pub use traits::{AsBorrowed, ResourceData, SystemAccess, SystemData, SystemFn};
 
-use self::traits::{WithCmd, WithCmdMut, WithInput, WithInputMut, WithWorld, WithWorldMut};
+use self::traits::{
+    WithCmd, WithCmdMut, WithInput, WithInputMut, WithResource, WithResourceMut, WithWorld,
+    WithWorldMut,
+};
 
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelBridge, ParallelIterator};
@@ -36,6 +44,7 @@ use rayon::prelude::{ParallelBridge, ParallelIterator};
 pub struct SystemBuilder<Args> {
     args: Args,
     name: Option<String>,
+    conditions: Vec<BoxedCondition>,
 }
 
 impl SystemBuilder<()> {
@@ -44,6 +53,7 @@ impl SystemBuilder<()> {
         Self {
             args: (),
             name: None,
+            conditions: Vec::new(),
         }
     }
 }
@@ -161,6 +171,7 @@ where
             self.name.unwrap_or_else(|| type_name::<Func>().to_string()),
             ForEach { func },
             self.args,
+            self.conditions,
         )
     }
 
@@ -180,6 +191,7 @@ where
                 _marker: PhantomData,
             },
             self.args,
+            self.conditions,
         )
     }
 }
@@ -200,6 +212,7 @@ where
             self.name.unwrap_or_else(|| type_name::<Func>().to_string()),
             ParForEach { func },
             self.args,
+            self.conditions,
         )
     }
 
@@ -217,6 +230,7 @@ where
             self.name.unwrap_or_else(|| type_name::<Func>().to_string()),
             TryParForEach { func },
             self.args,
+            self.conditions,
         )
     }
 }
@@ -293,6 +307,53 @@ impl<Args> SystemBuilder<Args> {
         self.with(WithInputMut::<T>(PhantomData))
     }
 
+    /// Access a resource of the world.
+    ///
+    /// Systems which only read the same resource may run in parallel.
+    ///
+    /// See [`World::insert_resource`]
+    pub fn with_resource_ref<T>(self) -> SystemBuilder<Args::PushRight>
+    where
+        T: ComponentValue,
+        Args: TuplePush<WithResource<T>>,
+    {
+        self.with(WithResource::<T>(PhantomData))
+    }
+
+    /// Access a resource of the world mutably.
+    ///
+    /// See [`World::insert_resource`]
+    pub fn with_resource_mut<T>(self) -> SystemBuilder<Args::PushRight>
+    where
+        T: ComponentValue,
+        Args: TuplePush<WithResourceMut<T>>,
+    {
+        self.with(WithResourceMut::<T>(PhantomData))
+    }
+
+    /// Only run the system if `predicate` returns true.
+    ///
+    /// **Note**: As the whole world is accessed, the predicate creates a barrier to other systems.
+    /// See [`Self::with_condition`] for conditions with specific accesses.
+    pub fn run_if<F>(self, predicate: F) -> Self
+    where
+        F: 'static + FnMut(&World) -> bool + Send + Sync,
+    {
+        self.with_condition(BoxedCondition::from_fn(predicate))
+    }
+
+    /// Only run the system if `condition` returns true.
+    ///
+    /// The condition is a system returning `bool`, and its accesses are a part of the system's
+    /// accesses in the schedule.
+    ///
+    /// Conditions are evaluated when the system is executed as a [`BoxedSystem`] or in a
+    /// [`Schedule`](crate::Schedule), but not through [`System::run`].
+    pub fn with_condition(mut self, condition: impl Into<BoxedCondition>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
     /// Set the systems name
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
@@ -322,6 +383,7 @@ impl<Args> SystemBuilder<Args> {
             self.name.unwrap_or_else(|| type_name::<Func>().to_string()),
             func,
             self.args,
+            self.conditions,
         )
     }
 
@@ -335,6 +397,7 @@ impl<Args> SystemBuilder<Args> {
         SystemBuilder {
             name: self.name,
             args: self.args.push_right(other),
+            conditions: self.conditions,
         }
     }
 }
@@ -344,6 +407,7 @@ pub struct System<F, Args, Ret> {
     name: String,
     data: Args,
     func: F,
+    conditions: Vec<BoxedCondition>,
     _marker: PhantomData<Ret>,
 }
 
@@ -352,7 +416,10 @@ pub struct System<F, Args, Ret> {
 pub trait DynSystem {
     fn name(&self) -> &str;
     fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result;
-    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<()>;
+    /// Executes the system, unless skipped by its run conditions.
+    ///
+    /// Returns true if the system ran.
+    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<bool>;
     fn access(&self, world: &World, dst: &mut Vec<Access>);
 }
 
@@ -362,12 +429,16 @@ where
     F: for<'x> SystemFn<'x, <Args as SystemData<'x>>::Value, Result<(), Err>>,
     Err: Into<anyhow::Error>,
 {
-    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<()> {
+    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<bool> {
         profile_function!(self.name());
 
         #[cfg(feature = "tracing")]
         let _span = tracing::info_span!("system", name = self.name).entered();
 
+        if !self.should_run(ctx) {
+            return Ok(false);
+        }
+
         let data = self.data.acquire(ctx);
 
         let res: anyhow::Result<()> = self.func.execute(data).map_err(Into::into);
@@ -375,7 +446,7 @@ where
             return Err(err.context(format!("Failed to execute system: {:?}", self)));
         }
 
-        Ok(())
+        Ok(true)
     }
 
     fn describe(&self, f: &mut fmt::Formatter<'_>) -> core::fmt::Result {
@@ -384,12 +455,12 @@ where
         self.data.describe(f)?;
         f.write_str(" -> ")?;
         f.write_str(&tynm::type_name::<core::result::Result<(), Err>>())?;
-
-        Ok(())
+        self.describe_conditions(f)
     }
 
     fn access(&self, world: &World, dst: &mut Vec<Access>) {
-        self.data.access(world, dst)
+        self.data.access(world, dst);
+        self.conditions_access(world, dst);
     }
 
     fn name(&self) -> &str {
@@ -402,12 +473,16 @@ where
     Args: for<'x> SystemData<'x>,
     F: for<'x> SystemFn<'x, <Args as SystemData<'x>>::Value, ()>,
 {
-    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<()> {
+    fn execute(&mut self, ctx: &SystemContext<'_, '_, '_>) -> anyhow::Result<bool> {
         profile_function!(self.name());
 
         #[cfg(feature = "tracing")]
         let _span = tracing::info_span!("system", name = self.name).entered();
 
+        if !self.should_run(ctx) {
+            return Ok(false);
+        }
+
         let data = {
             profile_scope!("acquire_data");
             self.data.acquire(ctx)
@@ -418,19 +493,19 @@ where
             self.func.execute(data);
         }
 
-        Ok(())
+        Ok(true)
     }
 
     fn describe(&self, f: &mut fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str("fn ")?;
         f.write_str(&self.name)?;
         self.data.describe(f)?;
-
-        Ok(())
+        self.describe_conditions(f)
     }
 
     fn access(&self, world: &World, dst: &mut Vec<Access>) {
-        self.data.access(world, dst)
+        self.data.access(world, dst);
+        self.conditions_access(world, dst);
     }
 
     fn name(&self) -> &str {
@@ -448,15 +523,36 @@ where
 }
 
 impl<F, Args, Ret> System<F, Args, Ret> {
-    pub(crate) fn new(name: String, func: F, data: Args) -> Self {
+    pub(crate) fn new(name: String, func: F, data: Args, conditions: Vec<BoxedCondition>) -> Self {
         Self {
             name,
             data,
             func,
+            conditions,
             _marker: PhantomData,
         }
     }
 
+    /// Evaluates the conditions of the system
+    fn should_run(&mut self, ctx: &SystemContext<'_, '_, '_>) -> bool {
+        self.conditions.iter_mut().all(|v| v.evaluate(ctx))
+    }
+
+    fn conditions_access(&self, world: &World, dst: &mut Vec<Access>) {
+        for condition in &self.conditions {
+            condition.access(world, dst)
+        }
+    }
+
+    fn describe_conditions(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for condition in &self.conditions {
+            f.write_str(" if ")?;
+            fmt::Debug::fmt(condition, f)?;
+        }
+
+        Ok(())
+    }
+
     /// Convert to a type erased Send + Sync system
     pub fn boxed(self) -> BoxedSystem
     where
@@ -531,6 +627,8 @@ pub enum AccessKind {
     CommandBuffer,
     /// Data supplied by user in the execution context
     Input(TypeId),
+    /// A resource of the world
+    Resource(TypeId),
 }
 
 impl AccessKind {
@@ -584,6 +682,7 @@ pub struct AccessInfo {
     cmd: Option<bool>,
     external: Vec<TypeId>,
     input: Vec<(TypeId, bool)>,
+    resources: Vec<(TypeId, bool)>,
 }
 
 #[derive(Hash, Debug, Clone, PartialEq, Eq)]
@@ -597,6 +696,20 @@ pub struct Access {
 }
 
 /// Transform accesses into a human friendly format
+/// Counts the entities of the distinct archetypes accessed
+pub(crate) fn matched_entities(accesses: &[Access], world: &World) -> usize {
+    accesses
+        .iter()
+        .filter_map(|v| match v.kind {
+            AccessKind::Archetype { id, .. } => Some(id),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|id| world.archetypes.get(id).len())
+        .sum()
+}
+
 pub(crate) fn access_info(accesses: &[Access], world: &World) -> AccessInfo {
     let mut result = AccessInfo::default();
     for access in accesses {
@@ -621,6 +734,9 @@ pub(crate) fn access_info(accesses: &[Access], world: &World) -> AccessInfo {
             AccessKind::Input(ty) => {
                 result.input.push((ty, access.mutable));
             }
+            AccessKind::Resource(ty) => {
+                result.resources.push((ty, access.mutable));
+            }
             AccessKind::World => match result.world {
                 Some(true) => result.world = Some(true),
                 _ => result.world = Some(access.mutable),
@@ -645,11 +761,16 @@ impl Access {
 /// A type erased system
 pub struct BoxedSystem {
     inner: Box<dyn DynSystem + Send + Sync>,
+    conditions: Vec<BoxedCondition>,
+    labels: Vec<String>,
+    before: Vec<String>,
+    after: Vec<String>,
+    stats: SystemStats,
 }
 
 impl core::fmt::Debug for BoxedSystem {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.inner.describe(f)
+        self.describe(f)
     }
 }
 
@@ -668,12 +789,114 @@ impl BoxedSystem {
     {
         Self {
             inner: Box::new(system),
+            conditions: Vec::new(),
+            labels: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+            stats: SystemStats::default(),
         }
     }
 
+    /// Add a label to the system, which other systems can be ordered relative to using
+    /// [`Self::before`] and [`Self::after`].
+    ///
+    /// Several systems can share the same label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Run the system before all systems with `label` in the schedule, regardless of insertion
+    /// order.
+    pub fn before(mut self, label: impl Into<String>) -> Self {
+        self.before.push(label.into());
+        self
+    }
+
+    /// Run the system after all systems with `label` in the schedule, regardless of insertion
+    /// order.
+    pub fn after(mut self, label: impl Into<String>) -> Self {
+        self.after.push(label.into());
+        self
+    }
+
+    /// Returns the labels of the system
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub(crate) fn before_labels(&self) -> &[String] {
+        &self.before
+    }
+
+    pub(crate) fn after_labels(&self) -> &[String] {
+        &self.after
+    }
+
+    /// Only run the system if `predicate` returns true.
+    ///
+    /// See [`SystemBuilder::run_if`]
+    pub fn run_if<F>(self, predicate: F) -> Self
+    where
+        F: 'static + FnMut(&World) -> bool + Send + Sync,
+    {
+        self.with_condition(BoxedCondition::from_fn(predicate))
+    }
+
+    /// Only run the system if `condition` returns true.
+    ///
+    /// See [`SystemBuilder::with_condition`]
+    pub fn with_condition(mut self, condition: impl Into<BoxedCondition>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
     /// Execute the system with the provided context
     pub fn execute<'a>(&'a mut self, ctx: &'a SystemContext<'_, '_, '_>) -> anyhow::Result<()> {
-        self.inner.execute(ctx)
+        self.execute_in(ctx, None)
+    }
+
+    /// Execute the system as part of the `batch` of a schedule
+    pub(crate) fn execute_in<'a>(
+        &'a mut self,
+        ctx: &'a SystemContext<'_, '_, '_>,
+        batch: Option<usize>,
+    ) -> anyhow::Result<()> {
+        if !self.conditions.iter_mut().all(|v| v.evaluate(ctx)) {
+            return Ok(());
+        }
+
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+
+        // Skipped by the run conditions of the system itself
+        if !self.inner.execute(ctx)? {
+            return Ok(());
+        }
+
+        #[cfg(feature = "std")]
+        let elapsed = start.elapsed();
+        #[cfg(not(feature = "std"))]
+        let elapsed = core::time::Duration::ZERO;
+
+        let world = ctx.world();
+        let mut accesses = Vec::new();
+        self.access(&world, &mut accesses);
+        let entities = matched_entities(&accesses, &world);
+
+        self.stats.record(elapsed, batch, entities);
+
+        Ok(())
+    }
+
+    /// Returns the execution statistics of the system
+    pub fn stats(&self) -> &SystemStats {
+        &self.stats
+    }
+
+    /// Clears the collected execution statistics
+    pub fn reset_stats(&mut self) {
+        self.stats = SystemStats::default();
     }
 
     /// Same as execute but creates and applied a temporary command buffer
@@ -685,7 +908,7 @@ impl BoxedSystem {
         let mut cmd = CommandBuffer::new();
         let input = input.into_input();
         let ctx = SystemContext::new(world, &mut cmd, &input);
-        self.inner.execute(&ctx)?;
+        self.execute(&ctx)?;
 
         ctx.cmd_mut()
             .apply(&mut ctx.world.borrow_mut())
@@ -696,12 +919,23 @@ impl BoxedSystem {
 
     /// Describes the system held within
     pub fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.describe(f)
+        self.inner.describe(f)?;
+
+        for condition in &self.conditions {
+            f.write_str(" if ")?;
+            fmt::Debug::fmt(condition, f)?;
+        }
+
+        Ok(())
     }
 
     /// Returns the accesses of the system held within
     pub fn access(&self, world: &World, dst: &mut Vec<Access>) {
-        self.inner.access(world, dst)
+        self.inner.access(world, dst);
+
+        for condition in &self.conditions {
+            condition.access(world, dst)
+        }
     }
 
     /// Returns the boxed system's name
@@ -0,0 +1,88 @@
+use core::time::Duration;
+
+/// Runtime statistics of a system, collected as it is executed.
+///
+/// Execution times are only measured with the `std` feature enabled, and are zero otherwise.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SystemStats {
+    invocations: u64,
+    last: Duration,
+    total: Duration,
+    max: Duration,
+    batch: Option<usize>,
+    thread: Option<usize>,
+    entities: usize,
+}
+
+impl SystemStats {
+    pub(crate) fn record(&mut self, elapsed: Duration, batch: Option<usize>, entities: usize) {
+        self.invocations += 1;
+        self.last = elapsed;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        self.batch = batch;
+        self.thread = current_thread();
+        self.entities = entities;
+    }
+
+    /// Returns the number of times the system has been executed.
+    ///
+    /// Executions skipped due to a run condition are not counted.
+    pub fn invocations(&self) -> u64 {
+        self.invocations
+    }
+
+    /// Returns the duration of the last execution
+    pub fn last(&self) -> Duration {
+        self.last
+    }
+
+    /// Returns the average duration of an execution
+    pub fn average(&self) -> Duration {
+        if self.invocations == 0 {
+            return Duration::ZERO;
+        }
+
+        let nanos = self.total.as_nanos() / self.invocations as u128;
+        Duration::from_nanos(nanos as u64)
+    }
+
+    /// Returns the longest duration of an execution
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the accumulated duration of all executions
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the index of the batch the system last ran in.
+    ///
+    /// `None` if the system was executed sequentially.
+    pub fn batch(&self) -> Option<usize> {
+        self.batch
+    }
+
+    /// Returns the number of entities matched by the queries of the system when it last ran
+    pub fn entities(&self) -> usize {
+        self.entities
+    }
+
+    /// Returns the index of the worker thread the system last ran on.
+    ///
+    /// `None` if the system was not executed on a thread of the rayon thread pool.
+    pub fn thread(&self) -> Option<usize> {
+        self.thread
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn current_thread() -> Option<usize> {
+    rayon::current_thread_index()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn current_thread() -> Option<usize> {
+    None
+}
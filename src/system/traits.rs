@@ -6,6 +6,7 @@ use core::{
     marker::PhantomData,
 };
 
+use crate::component::ComponentValue;
 use crate::system::AccessKind;
 use crate::*;
 
@@ -276,6 +277,107 @@ impl<T: 'static> SystemAccess for WithInputMut<T> {
     }
 }
 
+/// A borrowed resource of the world
+pub struct ResourceData<'a, G> {
+    // Declared first, as it borrows from the world and must be dropped before it
+    value: G,
+    _world: AtomicRef<'a, World>,
+}
+
+impl<'a, G> AsBorrowed<'a> for ResourceData<'_, G>
+where
+    G: AsBorrowed<'a>,
+{
+    type Borrowed = G::Borrowed;
+
+    fn as_borrowed(&'a mut self) -> Self::Borrowed {
+        self.value.as_borrowed()
+    }
+}
+
+impl<'a, G> ResourceData<'a, G> {
+    fn acquire(
+        ctx: &'a SystemContext<'_, '_, '_>,
+        borrow: impl FnOnce(&'a World) -> error::Result<G>,
+    ) -> Self {
+        let world = ctx.world();
+        // SAFETY: the world is kept borrowed for as long as the resource is
+        let value = borrow(unsafe { &*(&*world as *const World) });
+
+        match value {
+            Ok(value) => Self {
+                value,
+                _world: world,
+            },
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+/// Access a resource of the world
+pub struct WithResource<T>(pub(crate) PhantomData<T>);
+
+impl<'a, T: ComponentValue> SystemData<'a> for WithResource<T> {
+    type Value = ResourceData<'a, AtomicRef<'a, T>>;
+
+    fn acquire(&'a mut self, ctx: &'a SystemContext<'_, '_, '_>) -> Self::Value {
+        ResourceData::acquire(ctx, |world| world.resource())
+    }
+
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Res<&")?;
+        f.write_str(&tynm::type_name::<T>())?;
+        f.write_str(">")
+    }
+}
+
+impl<T: ComponentValue> SystemAccess for WithResource<T> {
+    fn access(&self, _: &World, dst: &mut Vec<Access>) {
+        dst.extend([
+            Access {
+                kind: AccessKind::World,
+                mutable: false,
+            },
+            Access {
+                kind: AccessKind::Resource(TypeId::of::<T>()),
+                mutable: false,
+            },
+        ]);
+    }
+}
+
+/// Access a resource of the world mutably
+pub struct WithResourceMut<T>(pub(crate) PhantomData<T>);
+
+impl<'a, T: ComponentValue> SystemData<'a> for WithResourceMut<T> {
+    type Value = ResourceData<'a, AtomicRefMut<'a, T>>;
+
+    fn acquire(&'a mut self, ctx: &'a SystemContext<'_, '_, '_>) -> Self::Value {
+        ResourceData::acquire(ctx, |world| world.resource_mut())
+    }
+
+    fn describe(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Res<&mut ")?;
+        f.write_str(&tynm::type_name::<T>())?;
+        f.write_str(">")
+    }
+}
+
+impl<T: ComponentValue> SystemAccess for WithResourceMut<T> {
+    fn access(&self, _: &World, dst: &mut Vec<Access>) {
+        dst.extend([
+            Access {
+                kind: AccessKind::World,
+                mutable: false,
+            },
+            Access {
+                kind: AccessKind::Resource(TypeId::of::<T>()),
+                mutable: true,
+            },
+        ]);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use alloc::string::String;
@@ -1,4 +1,8 @@
-use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Weak},
+    vec::Vec,
+};
 use core::{
     fmt,
     fmt::Formatter,
@@ -8,15 +12,17 @@ use core::{
 use once_cell::unsync::OnceCell;
 use smallvec::SmallVec;
 
-use atomic_refcell::{AtomicRef, BorrowError, BorrowMutError};
+use atomic_refcell::{AtomicRef, AtomicRefMut, BorrowError, BorrowMutError};
 use itertools::Itertools;
 
 use crate::{
-    archetype::{Archetype, ArchetypeId, ArchetypeInfo, Slot},
+    archetype::{Archetype, ArchetypeId, ArchetypeInfo, Change, ChangeKind, Slice, Slot},
     archetypes::Archetypes,
     buffer::ComponentBuffer,
+    bundle::Bundle,
     component::{dummy, ComponentDesc, ComponentKey, ComponentValue},
-    components::{self, component_info, is_static_entity, name},
+    components::{self, child_of, component_info, is_static_entity, name},
+    delta::{DeltaTracker, WorldDelta},
     entity::{entity_ids, Entity, EntityIndex, EntityKind, EntityLocation, EntityStore},
     entity_ref::{EntityRef, EntityRefMut},
     entry::{Entry, OccupiedEntry, VacantEntry},
@@ -24,7 +30,15 @@ use crate::{
     events::EventSubscriber,
     filter::StaticFilter,
     format::{EntitiesFormatter, HierarchyFormatter, WorldFormatter},
+    metadata::{
+        self, append_required, append_required_batch, has_requirements, on_target_despawn,
+        required_components, symmetric, HookDispatcher, IndexDispatcher, NameIndex,
+        OnTargetDespawn, Symmetric,
+    },
+    query::RemovedDispatcher,
     relation::{Relation, RelationExt},
+    resources::Resources,
+    snapshot::{ArchetypeSnapshot, CellSnapshot, WorldSnapshot},
     writer::{
         self, EntityWriter, FnWriter, Replace, ReplaceDyn, SingleComponentWriter, WriteDedup,
     },
@@ -84,22 +98,53 @@ pub(crate) fn update_entity_loc(
 pub struct World {
     entities: EntityStores,
     pub(crate) archetypes: Archetypes,
-    change_tick: AtomicU32,
+    change_tick: Arc<AtomicU32>,
 
     has_reserved: AtomicBool,
+    pub(crate) delta: Option<Arc<DeltaTracker>>,
+    hooks: Arc<HookDispatcher>,
+    pub(crate) hook_errors: Vec<anyhow::Error>,
+    indexes: Arc<IndexDispatcher>,
+    names: Arc<NameIndex>,
+    pub(crate) removed: Arc<RemovedDispatcher>,
+    pub(crate) resources: Resources,
 }
 
 impl World {
     /// Creates a new empty world
     pub fn new() -> Self {
+        let hooks = Arc::new(HookDispatcher::default());
+        let indexes = Arc::new(IndexDispatcher::default());
+        let names = Arc::new(NameIndex::default());
+        let change_tick = Arc::new(AtomicU32::new(0b11));
+        let removed = Arc::new(RemovedDispatcher::new(change_tick.clone()));
+        let mut archetypes = Archetypes::new();
+        archetypes.add_subscriber(hooks.clone());
+        archetypes.add_subscriber(indexes.clone());
+        archetypes.add_subscriber(names.clone());
+        archetypes.add_subscriber(removed.clone());
+
         Self {
             entities: EntityStores::new(),
-            archetypes: Archetypes::new(),
-            change_tick: AtomicU32::new(0b11),
+            archetypes,
+            change_tick,
             has_reserved: AtomicBool::new(false),
+            delta: None,
+            hooks,
+            hook_errors: Vec::new(),
+            indexes,
+            names,
+            removed,
+            resources: Resources::default(),
         }
     }
 
+    /// Returns a handle which identifies this world, for state which must only be used with the
+    /// world it was created for.
+    pub(crate) fn handle(&self) -> Weak<AtomicU32> {
+        Arc::downgrade(&self.change_tick)
+    }
+
     /// Reserve a single entity id concurrently.
     ///
     /// See: [`World::reserve`]
@@ -148,10 +193,13 @@ impl World {
         profile_function!();
         self.flush_reserved();
 
+        append_required_batch(chunk);
+
         for component in chunk.components() {
             self.init_component(component);
         }
 
+        let symmetric = symmetric_relations(chunk.components());
         let change_tick = self.advance_change_tick();
 
         let (arch_id, arch) = self.archetypes.find_create(chunk.components());
@@ -176,6 +224,12 @@ impl World {
             }
         }
 
+        for &id in &ids {
+            self.mirror_missing_symmetric(id, &symmetric);
+        }
+
+        self.flush_hooks();
+
         ids
     }
 
@@ -202,7 +256,7 @@ impl World {
         self.flush_reserved();
 
         if self.is_reserved(id) {
-            self.despawn(id).unwrap();
+            self.despawn_inner(id).unwrap();
         }
 
         let store = self.entities.init(id.kind());
@@ -220,12 +274,16 @@ impl World {
         id: Entity,
         buffer: &mut ComponentBuffer,
     ) -> Result<(Entity, EntityLocation)> {
+        append_required(buffer);
+
         let change_tick = self.advance_change_tick();
 
         for &component in buffer.components() {
             self.init_component(component);
         }
 
+        let symmetric = symmetric_relations(buffer.components().copied());
+
         let (arch_id, _) = self.archetypes.find_create(buffer.components().copied());
         let (loc, arch) = self.spawn_at_inner(id, arch_id)?;
 
@@ -233,17 +291,28 @@ impl World {
             unsafe { arch.push(desc.key(), src, change_tick) }
         }
 
-        Ok((id, loc))
+        if symmetric.is_empty() {
+            return Ok((id, loc));
+        }
+
+        self.mirror_missing_symmetric(id, &symmetric);
+
+        // Mirroring may have moved `id` within its archetype
+        Ok((id, self.location(id)?))
     }
 
     /// Spawn an entity with the given components.
     ///
     /// For increased ergonomics, prefer [crate::EntityBuilder]
     pub(crate) fn spawn_with(&mut self, buffer: &mut ComponentBuffer) -> Entity {
+        append_required(buffer);
+
         for component in buffer.components() {
             self.init_component(*component);
         }
 
+        let symmetric = symmetric_relations(buffer.components().copied());
+
         let change_tick = self.advance_change_tick();
         let (arch_id, _) = self.archetypes.find_create(buffer.components().copied());
 
@@ -255,6 +324,8 @@ impl World {
             }
         }
 
+        self.mirror_missing_symmetric(id, &symmetric);
+
         id
     }
 
@@ -262,6 +333,8 @@ impl World {
     pub fn clear(&mut self, id: Entity) -> Result<()> {
         let EntityLocation { arch_id, slot } = self.init_location(id)?;
 
+        let symmetric = symmetric_relations(self.archetypes.get(arch_id).components_desc());
+
         let (src, dst) = self
             .archetypes
             .get_disjoint(arch_id, self.archetypes.root)
@@ -285,6 +358,12 @@ impl World {
             arch_id: self.archetypes.root,
         };
 
+        for (desc, _) in symmetric {
+            self.remove_mirror(id, desc.key);
+        }
+
+        self.flush_hooks();
+
         Ok(())
     }
 
@@ -294,13 +373,28 @@ impl World {
     }
 
     pub(crate) fn retain_entity_components(
+        &mut self,
+        id: Entity,
+        loc: EntityLocation,
+        f: impl FnMut(ComponentKey) -> bool,
+    ) -> EntityLocation {
+        unsafe { self.retain_entity_components_with(id, loc, f, |c, p| c.drop(p)) }
+    }
+
+    /// Moves the entity to the archetype of the retained components, in a single move.
+    ///
+    /// # Safety
+    /// `on_take` is responsible for storing or dropping the removed components
+    unsafe fn retain_entity_components_with(
         &mut self,
         id: Entity,
         loc: EntityLocation,
         mut f: impl FnMut(ComponentKey) -> bool,
+        on_take: impl FnMut(ComponentDesc, *mut u8),
     ) -> EntityLocation {
         let src = self.archetypes.get(loc.arch_id);
 
+        let symmetric = symmetric_relations(src.components_desc());
         let dst_components: SmallVec<[ComponentDesc; 8]> =
             src.components_desc().filter(|v| f(v.key())).collect();
 
@@ -308,7 +402,7 @@ impl World {
 
         let (src, dst) = self.archetypes.get_disjoint(loc.arch_id, dst_id).unwrap();
 
-        let (dst_slot, swapped) = unsafe { src.move_to(dst, loc.slot, |c, p| c.drop(p)) };
+        let (dst_slot, swapped) = src.move_to(dst, loc.slot, on_take);
 
         if let Some((swapped, slot)) = swapped {
             // The last entity in src was moved into the slot occupied by id
@@ -326,7 +420,18 @@ impl World {
         };
 
         *self.location_mut(id).expect("Entity is not valid") = loc;
-        loc
+
+        if symmetric.is_empty() {
+            return loc;
+        }
+
+        for (desc, _) in symmetric {
+            if !self.archetypes.get(dst_id).has(desc.key) {
+                self.remove_mirror(id, desc.key);
+            }
+        }
+
+        self.location(id).expect("Entity is not valid")
     }
 
     /// Set metadata for a given component if they do not already exist
@@ -350,18 +455,108 @@ impl World {
         }
         self.spawn_at(id).unwrap();
 
-        self.set_with(id, &mut meta).unwrap();
+        self.set_with_writer(id, writer::Buffered::new(&mut meta))
+            .unwrap();
     }
 
     /// Despawn an entity.
     /// Any relations to other entities will be removed.
+    ///
+    /// The [`OnTargetDespawn`] policy of each relation targeting the entity is applied before the
+    /// entity is despawned, which may cascade to other entities.
     pub fn despawn(&mut self, id: Entity) -> Result<()> {
         profile_function!();
+        self.despawn_inner(id)?;
+        self.flush_hooks();
+
+        Ok(())
+    }
+
+    fn despawn_inner(&mut self, id: Entity) -> Result<()> {
         self.flush_reserved();
+        self.init_location(id)?;
+
+        let mut stack = alloc::vec![id];
+        while let Some(id) = stack.pop() {
+            // Entities may be reachable through more than one cascading relation
+            let Ok(loc) = self.init_location(id) else {
+                continue;
+            };
+
+            self.apply_target_despawn(id, &mut stack);
+            // Retargeting may have moved the entity
+            let loc = self.location(id).unwrap_or(loc);
+            self.despawn_entity(id, loc)?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies the [`OnTargetDespawn`] policy for all relations targeting `id`.
+    ///
+    /// Subjects which are to be despawned alongside `id` are pushed to `cascade`.
+    fn apply_target_despawn(&mut self, id: Entity, cascade: &mut Vec<Entity>) {
+        let mut retarget = Vec::new();
+
+        for arch_id in self
+            .archetypes
+            .index
+            .find_relation_targets(id)
+            .into_iter()
+            .flat_map(|v| v.keys().copied())
+        {
+            let arch = self.archetypes.get(arch_id);
+            for desc in arch
+                .components_desc()
+                .filter(|v| v.key().target == Some(id))
+            {
+                let policy = self
+                    .get(desc.key().id, on_target_despawn())
+                    .map(|v| *v)
+                    .unwrap_or_default();
+
+                match policy {
+                    OnTargetDespawn::Remove => {}
+                    OnTargetDespawn::Cascade => {
+                        cascade.extend(arch.entities().iter().copied().filter(|&v| v != id))
+                    }
+                    OnTargetDespawn::Panic => panic!(
+                        "Attempt to despawn {id} which is the target of {} for {} entities",
+                        desc.name(),
+                        arch.len()
+                    ),
+                    OnTargetDespawn::Retarget(target) if target != id && self.is_alive(target) => {
+                        retarget.extend(
+                            arch.entities()
+                                .iter()
+                                .filter(|&&v| v != id)
+                                .map(|&v| (v, desc, target)),
+                        )
+                    }
+                    OnTargetDespawn::Retarget(_) => {}
+                }
+            }
+        }
+
+        for (subject, desc, target) in retarget {
+            let mut buffer = ComponentBuffer::new();
+            unsafe {
+                self.remove_inner(subject, desc, |ptr| {
+                    buffer.set_dyn(desc.with_relation(Some(target)), ptr)
+                })
+                .expect("Subject is alive");
+            }
+
+            self.set_with_writer(subject, writer::Buffered::new(&mut buffer))
+                .expect("Subject is alive");
+        }
+    }
+
+    fn despawn_entity(&mut self, id: Entity, loc: EntityLocation) -> Result<()> {
         let EntityLocation {
             arch_id: arch,
             slot,
-        } = self.init_location(id)?;
+        } = loc;
 
         // if id.is_static() {
         //     panic!("Attempt to despawn static component");
@@ -386,7 +581,7 @@ impl World {
 
         // self.archetypes.prune_arch(arch);
         self.entities.init(id.kind()).despawn(id)?;
-        self.detach(id);
+        self.detach_inner(id);
         Ok(())
     }
 
@@ -466,13 +661,560 @@ impl World {
             }
         }
 
+        self.flush_hooks();
+
         Ok(())
     }
 
+    /// Instantiates a [`prefab`](crate::components::prefab) by cloning its components and
+    /// [`child_of`] subtree.
+    ///
+    /// Relations which target an entity of the subtree are remapped to the new entities, while
+    /// other relations are kept as is. The `prefab` marker is not cloned.
+    ///
+    /// Fails without spawning anything if a component of the subtree is not
+    /// [`Cloneable`](crate::metadata::Cloneable).
+    ///
+    /// Returns the instantiated root.
+    ///
+    /// ```rust
+    /// # use flax::{*, components::*};
+    /// component! {
+    ///     health: f32 => [ Cloneable ],
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let enemy = Entity::builder()
+    ///     .set(name(), "Enemy".into())
+    ///     .set(health(), 100.0)
+    ///     .tag(prefab())
+    ///     .attach(child_of, Entity::builder().set(name(), "Weapon".into()))
+    ///     .spawn(&mut world);
+    ///
+    /// let instance = world.instantiate(enemy).unwrap();
+    ///
+    /// assert_eq!(world.get_copy(instance, health()), Ok(100.0));
+    /// assert!(!world.has(instance, prefab()));
+    /// assert_eq!(Query::new(health()).borrow(&world).iter().count(), 1);
+    /// ```
+    pub fn instantiate(&mut self, prefab: Entity) -> Result<Entity> {
+        profile_function!();
+        self.flush_reserved();
+
+        let mut subtree = alloc::vec![prefab];
+        let mut visited = BTreeSet::from([prefab]);
+        let mut i = 0;
+        while let Some(&id) = subtree.get(i) {
+            i += 1;
+
+            let loc = self.location(id)?;
+            if let Some(desc) = self
+                .archetypes
+                .get(loc.arch_id)
+                .cells()
+                .iter()
+                .map(|v| v.desc())
+                .find(|v| !v.meta_ref().has(metadata::cloneable()))
+            {
+                return Err(Error::NotCloneable(desc));
+            }
+
+            subtree.extend(
+                self.archetypes
+                    .index
+                    .find(child_of(id).key())
+                    .into_iter()
+                    .flat_map(|v| v.keys())
+                    .flat_map(|&arch_id| self.archetypes.get(arch_id).entities())
+                    // A `child_of` cycle would otherwise be walked forever
+                    .filter(|&&child| visited.insert(child)),
+            );
+        }
+
+        let ids: BTreeMap<Entity, Entity> = subtree.iter().map(|&id| (id, self.spawn())).collect();
+
+        for (&src, &dst) in &ids {
+            let mut buffer =
+                self.clone_components(src, |target| ids.get(&target).copied().unwrap_or(target))?;
+
+            buffer.remove(components::prefab());
+            metadata::map_buffer_entities(&mut buffer, &|v| ids.get(&v).copied().unwrap_or(v));
+            self.set_with(dst, &mut buffer)?;
+        }
+
+        Ok(ids[&prefab])
+    }
+
+    /// Spawns a copy of an entity by cloning each of its components.
+    ///
+    /// Relations are cloned with the same targets, but children are not cloned. See
+    /// [`Self::instantiate`] for cloning a subtree.
+    ///
+    /// Fails without spawning anything if a component is not
+    /// [`Cloneable`](crate::metadata::Cloneable).
+    ///
+    /// ```rust
+    /// # use flax::{*, components::*};
+    /// component! {
+    ///     health: f32 => [ Cloneable ],
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let id = Entity::builder()
+    ///     .set(name(), "Enemy".into())
+    ///     .set(health(), 100.0)
+    ///     .spawn(&mut world);
+    ///
+    /// let copy = world.clone_entity(id).unwrap();
+    /// assert_ne!(copy, id);
+    /// assert_eq!(world.get_copy(copy, health()), Ok(100.0));
+    /// ```
+    pub fn clone_entity(&mut self, id: Entity) -> Result<Entity> {
+        let mut builder = self.entity(id)?.to_builder()?;
+        Ok(builder.spawn(self))
+    }
+
+    /// Spawns a copy of an entity in another world by cloning each of its components.
+    ///
+    /// Relation targets are kept as is, and thus refer to entities of this world.
+    ///
+    /// Fails without spawning anything if a component is not
+    /// [`Cloneable`](crate::metadata::Cloneable).
+    pub fn clone_entity_into(&self, id: Entity, other: &mut World) -> Result<Entity> {
+        let mut builder = self.entity(id)?.to_builder()?;
+        Ok(builder.spawn(other))
+    }
+
+    /// Clones the components of an entity.
+    ///
+    /// The relation targets are remapped using `map_target`.
+    pub(crate) fn clone_components(
+        &self,
+        id: Entity,
+        map_target: impl Fn(Entity) -> Entity,
+    ) -> Result<ComponentBuffer> {
+        let loc = self.location(id)?;
+        let mut buffer = ComponentBuffer::new();
+
+        for cell in self.archetypes.get(loc.arch_id).cells() {
+            let desc = cell.desc();
+            let cloneable = desc
+                .meta_ref()
+                .get(metadata::cloneable())
+                .ok_or(Error::NotCloneable(desc))?;
+
+            let dst_desc = desc.with_relation(desc.key().target().map(&map_target));
+            cloneable.clone_storage(&cell.data.borrow().storage, loc.slot, dst_desc, &mut buffer);
+        }
+
+        Ok(buffer)
+    }
+
+    /// Copies all entities and their components into a snapshot, which can later be restored using
+    /// [`Self::restore`].
+    ///
+    /// The components are cloned column-wise per archetype. Components which are not
+    /// [`Cloneable`](crate::metadata::Cloneable) or are marked as
+    /// [`NoRollback`](crate::metadata::NoRollback) are skipped.
+    ///
+    /// Static entities, components, and resources are not part of the snapshot.
+    ///
+    /// ```rust
+    /// # use flax::*;
+    /// component! {
+    ///     health: f32 => [ Cloneable ],
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let id = Entity::builder().set(health(), 100.0).spawn(&mut world);
+    ///
+    /// let snapshot = world.snapshot();
+    ///
+    /// *world.get_mut(id, health()).unwrap() -= 25.0;
+    /// let other = world.spawn();
+    ///
+    /// world.restore(&snapshot);
+    ///
+    /// assert_eq!(world.get_copy(id, health()), Ok(100.0));
+    /// assert!(!world.is_alive(other));
+    /// ```
+    pub fn snapshot(&self) -> WorldSnapshot {
+        profile_function!();
+
+        let entities = self
+            .entities
+            .get(EntityKind::empty())
+            .expect("Missing entity store")
+            .clone();
+
+        let archetypes = self
+            .archetypes
+            .iter()
+            .map(|(_, arch)| arch)
+            .filter(|arch| !arch.is_empty() && is_dynamic(arch))
+            .map(|arch| ArchetypeSnapshot {
+                entities: arch.entities().to_vec(),
+                cells: arch
+                    .cells()
+                    .iter()
+                    .filter_map(|cell| {
+                        let cloneable = rollback_cloneable(cell.desc())?;
+                        let data = cell.data.borrow();
+
+                        Some(CellSnapshot {
+                            storage: cloneable.clone_column(&data.storage),
+                            changes: data.changes.lists(),
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        WorldSnapshot {
+            entities,
+            archetypes,
+        }
+    }
+
+    /// Restores the entities and components of a [`WorldSnapshot`].
+    ///
+    /// Entities spawned since the snapshot are despawned, and despawned entities are respawned
+    /// with the same ids. Components which were excluded from the snapshot are kept as is for
+    /// entities which exist in the snapshot.
+    ///
+    /// The change ticks of the components are restored as well, and components which are kept
+    /// retain their own change ticks. A change query which has already visited a later tick does
+    /// thus not observe the rolled back values as changed. The world change tick is not rewound.
+    /// Component hooks do not run.
+    ///
+    /// When deltas or replication are tracked, the restored components are additionally marked as
+    /// modified at the restore, so that [`Self::diff`] and replication carry the rollback.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        profile_function!();
+        self.flush_reserved();
+        let removed_tick = self.change_tick();
+
+        // Keep the components which are not rolled back, alongside their change ticks
+        let mut kept: BTreeMap<Entity, (ComponentBuffer, Vec<_>)> = BTreeMap::new();
+        for (_, arch) in self.archetypes.iter_mut() {
+            if !is_dynamic(arch) {
+                continue;
+            }
+
+            let changes = arch
+                .cells()
+                .iter()
+                .map(|cell| cell.data.borrow().changes.lists())
+                .collect_vec();
+
+            let (entities, storages) = arch.take_all();
+            for (mut storage, changes) in storages.into_iter().zip(changes) {
+                let desc = storage.desc();
+                if rollback_cloneable(desc).is_some() {
+                    continue;
+                }
+
+                for (slot, &id) in entities.iter().enumerate().rev() {
+                    let (buffer, ticks) = kept.entry(id).or_default();
+                    storage.swap_remove(slot, |ptr| unsafe { buffer.set_dyn(desc, ptr) });
+                    ticks.push((desc.key(), changes.each_ref().map(|v| v.extract_slot(slot))));
+                }
+            }
+        }
+
+        *self.entities.init(EntityKind::empty()) = snapshot.entities.clone();
+
+        let change_tick = self.advance_change_tick();
+        let track_modified = self.archetypes.tracks_modified();
+        for snapshot in &snapshot.archetypes {
+            let descs = snapshot.cells.iter().map(|v| v.storage.desc());
+            for desc in descs.clone() {
+                self.init_component(desc);
+            }
+
+            let (arch_id, arch) = self.archetypes.find_create(descs);
+            let slots = arch.allocate_n(&snapshot.entities);
+
+            for cell in &snapshot.cells {
+                let desc = cell.storage.desc();
+                let cloneable = rollback_cloneable(desc).expect("Component is cloneable");
+
+                // Safety: the slots were just allocated
+                unsafe { arch.extend(&mut cloneable.clone_column(&cell.storage), change_tick) }
+
+                let changes = arch
+                    .changes_mut(desc.key())
+                    .expect("Component is in archetype");
+
+                changes.restore(slots, &cell.changes);
+                if track_modified {
+                    changes.set_modified(Change::new(slots, change_tick));
+                }
+            }
+
+            let store = self.entities.init(EntityKind::empty());
+            for (slot, &id) in slots.iter().zip(&snapshot.entities) {
+                *store.get_mut(id).expect("Invalid entity") = EntityLocation { slot, arch_id };
+            }
+        }
+
+        for (id, (mut buffer, ticks)) in kept {
+            if !self.is_alive(id) {
+                continue;
+            }
+
+            self.set_with(id, &mut buffer).unwrap();
+
+            let loc = self.location(id).unwrap();
+            let arch = self.archetypes.get_mut(loc.arch_id);
+            for (key, changes) in ticks {
+                arch.changes_mut(key)
+                    .expect("Component is in archetype")
+                    .restore(Slice::single(loc.slot), &changes);
+            }
+        }
+
+        // The restored state is authoritative
+        self.hooks.take();
+        self.removed.discard(removed_tick);
+    }
+
+    /// Starts recording the changes required by [`Self::diff`].
+    ///
+    /// Modifications of all components are tracked from now on, and removed components are
+    /// recorded until discarded by [`Self::prune_deltas`].
+    ///
+    /// **Note**: the recorded removals grow with every removed component and despawned entity.
+    /// Call [`Self::prune_deltas`] with the oldest baseline still in use, such as after each diff
+    /// has been applied, to bound the memory usage.
+    pub fn track_deltas(&mut self) {
+        if self.delta.is_some() {
+            return;
+        }
+
+        let tracker = Arc::new(DeltaTracker::new(self.change_tick.clone()));
+        self.archetypes.add_subscriber(tracker.clone());
+        self.archetypes.track_modified();
+        self.delta = Some(tracker);
+    }
+
+    /// Starts recording removed components and modifications for a single consumer.
+    ///
+    /// Unlike [`Self::track_deltas`], the removals are owned by the returned tracker, and are
+    /// recorded for as long as it is alive.
+    #[cfg(feature = "serde")]
+    pub(crate) fn track_removals(&mut self) -> Arc<DeltaTracker> {
+        let tracker = Arc::new(DeltaTracker::new(self.change_tick.clone()));
+        self.archetypes
+            .add_subscriber(Arc::new(Arc::downgrade(&tracker)));
+        self.archetypes.track_modified();
+        tracker
+    }
+
+    /// Discards the recorded removals up to and including `tick`.
+    ///
+    /// Diffs against an earlier baseline will no longer contain these removals.
+    pub fn prune_deltas(&mut self, tick: u32) {
+        if let Some(delta) = &self.delta {
+            delta.prune(tick);
+        }
+    }
+
+    /// Returns the spawned and despawned entities, and the added, modified, and removed
+    /// components since `baseline`, such as the [`WorldDelta::tick`] of a previous diff.
+    ///
+    /// The added and modified values are cloned. Components which are not
+    /// [`Cloneable`](crate::metadata::Cloneable) are skipped, as are static entities and
+    /// components.
+    ///
+    /// Removals, despawns, and modifications are only known after [`Self::track_deltas`].
+    /// Entities without any components are not part of the delta.
+    ///
+    /// Diffing does not discard the recorded removals, as other baselines may still require them.
+    /// Use [`Self::prune_deltas`] once `baseline` is no longer needed.
+    ///
+    /// ```rust
+    /// # use flax::*;
+    /// component! {
+    ///     health: f32 => [ Cloneable ],
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.track_deltas();
+    ///
+    /// let id = Entity::builder().set(health(), 100.0).spawn(&mut world);
+    ///
+    /// let delta = world.diff(0);
+    /// assert_eq!(delta.spawned(), [id]);
+    ///
+    /// *world.get_mut(id, health()).unwrap() = 50.0;
+    ///
+    /// let delta = world.diff(delta.tick());
+    /// assert_eq!(delta.spawned(), []);
+    /// assert_eq!(delta.get(id).unwrap().modified().get(health()), Some(&50.0));
+    ///
+    /// // Removals before the next baseline are no longer needed
+    /// world.prune_deltas(delta.tick());
+    /// ```
+    pub fn diff(&self, baseline: u32) -> WorldDelta {
+        profile_function!();
+
+        let mut delta = WorldDelta {
+            tick: self.change_tick(),
+            ..Default::default()
+        };
+
+        // Entities which existed before the baseline
+        let mut existing = BTreeSet::new();
+        let mut despawned = BTreeSet::new();
+
+        for (id, desc) in self
+            .delta
+            .iter()
+            .flat_map(|tracker| tracker.removed_since(baseline))
+        {
+            let Ok(loc) = self.location(id) else {
+                despawned.insert(id);
+                continue;
+            };
+
+            existing.insert(id);
+            // The component was added back
+            if self.archetypes.get(loc.arch_id).has(desc.key()) {
+                continue;
+            }
+
+            let removed = &mut delta.entities.entry(id).or_default().removed;
+            if !removed.iter().any(|v| v.key() == desc.key()) {
+                removed.push(desc);
+            }
+        }
+
+        delta.despawned = despawned.into_iter().collect();
+
+        for (_, arch) in self.archetypes.iter() {
+            if arch.is_empty() || arch.cells().is_empty() || !is_dynamic(arch) {
+                continue;
+            }
+
+            let entities = arch.entities();
+            // Slots for which all components were added since the baseline
+            let mut fresh = alloc::vec![true; entities.len()];
+
+            for cell in arch.cells() {
+                let desc = cell.desc();
+                let data = cell.data.borrow();
+
+                let mut added = alloc::vec![false; entities.len()];
+                for (slot, tick) in data.changes.get(ChangeKind::Added).iter_collapsed() {
+                    added[slot] = tick > baseline;
+                }
+
+                for (fresh, &added) in fresh.iter_mut().zip(&added) {
+                    *fresh &= added;
+                }
+
+                let Some(cloneable) = desc.meta_ref().get(metadata::cloneable()) else {
+                    continue;
+                };
+
+                for (slot, tick) in data.changes.get(ChangeKind::Modified).iter_collapsed() {
+                    if tick <= baseline {
+                        continue;
+                    }
+
+                    let entity = delta.entities.entry(entities[slot]).or_default();
+                    let dst = if added[slot] {
+                        &mut entity.added
+                    } else {
+                        &mut entity.modified
+                    };
+
+                    cloneable.clone_storage(&data.storage, slot, desc, dst);
+                }
+            }
+
+            for (&id, _) in entities.iter().zip(fresh).filter(|v| v.1) {
+                if !existing.contains(&id) {
+                    delta.entities.entry(id).or_default();
+                    delta.spawned.push(id);
+                }
+            }
+        }
+
+        delta.spawned.sort();
+        delta
+    }
+
+    /// Applies a [`WorldDelta`] from [`Self::diff`] of another world.
+    ///
+    /// `ids` maps the entities of the other world to the entities in this world. Entities which
+    /// are not yet mapped are spawned and added to `ids`. Components and relation targets are
+    /// remapped using `ids`.
+    ///
+    /// ```rust
+    /// # use flax::*;
+    /// component! {
+    ///     health: f32 => [ Cloneable ],
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.track_deltas();
+    ///
+    /// let id = Entity::builder().set(health(), 100.0).spawn(&mut world);
+    ///
+    /// let mut replica = World::new();
+    /// let mut ids = world::MigratedEntities::default();
+    ///
+    /// replica.apply_delta(world.diff(0), &mut ids);
+    /// assert_eq!(replica.get_copy(ids.get(id), health()), Ok(100.0));
+    /// ```
+    pub fn apply_delta(&mut self, delta: WorldDelta, ids: &mut MigratedEntities) {
+        profile_function!();
+
+        for id in delta.despawned {
+            if let Some(local) = ids.ids.remove(&id) {
+                // The entity may already have been despawned through a relation
+                let _ = self.despawn(local);
+            }
+        }
+
+        // Map all entities before remapping relation targets
+        for &id in delta.entities.keys() {
+            if !ids.ids.get(&id).is_some_and(|&local| self.is_alive(local)) {
+                ids.ids.insert(id, self.spawn());
+            }
+        }
+
+        for (id, mut entity) in delta.entities {
+            let local = ids.get(id);
+
+            for desc in entity.removed {
+                let _ = self.remove_dyn(local, ids.get_desc(desc));
+            }
+
+            let mut buffer = ComponentBuffer::new();
+            for (desc, src) in entity.added.drain().chain(entity.modified.drain()) {
+                unsafe { buffer.set_dyn(ids.get_desc(desc), src) }
+            }
+
+            metadata::map_buffer_entities(&mut buffer, &|v| ids.get(v));
+            let _ = self.set_with(local, &mut buffer);
+        }
+    }
+
     /// Removes all instances of relations and component of the given entities
     /// in the world. If used upon an entity with a child -> parent relation, this removes the relation
     /// on all the children.
     pub fn detach(&mut self, id: Entity) {
+        self.detach_inner(id);
+        self.flush_hooks();
+    }
+
+    fn detach_inner(&mut self, id: Entity) {
         profile_function!();
         let index = &self.archetypes.index;
         let archetypes = index
@@ -557,22 +1299,31 @@ impl World {
         component: Component<T>,
         value: T,
     ) -> Result<Option<T>> {
-        Ok(self
-            .set_with_writer(
-                id,
-                SingleComponentWriter::new(component.desc(), Replace::new(value)),
-            )?
-            .1
-            .left())
+        let (_, old) = self.set_with_writer(
+            id,
+            SingleComponentWriter::new(component.desc(), Replace::new(value)),
+        )?;
+
+        self.flush_hooks();
+
+        Ok(old.left())
     }
 
     /// Add the components stored in a component buffer to an entity
     pub fn set_with(&mut self, id: Entity, buffer: &mut ComponentBuffer) -> Result<()> {
         self.set_with_writer(id, writer::Buffered::new(buffer))?;
+        self.flush_hooks();
 
         Ok(())
     }
 
+    /// Sets the components of a bundle for an entity
+    pub fn set_bundle<B: Bundle>(&mut self, id: Entity, bundle: B) -> Result<()> {
+        let mut buffer = ComponentBuffer::new();
+        bundle.set_into(&mut buffer);
+        self.set_with(id, &mut buffer)
+    }
+
     #[inline]
     pub(crate) fn set_dyn(
         &mut self,
@@ -591,6 +1342,59 @@ impl World {
         &mut self,
         id: Entity,
         writer: U,
+    ) -> Result<(EntityLocation, U::Output)> {
+        let mut symmetric = SmallVec::<[_; 2]>::new();
+        let mut required = SmallVec::<[_; 2]>::new();
+        writer.for_each_component(|desc| {
+            if let Some(v) = symmetric_relation(desc) {
+                symmetric.push((desc, v))
+            }
+
+            if has_requirements(desc) {
+                required.push(desc)
+            }
+        });
+
+        if symmetric.is_empty() && required.is_empty() {
+            return self.set_with_writer_inner(id, writer);
+        }
+
+        // Exclusive relations may displace the existing relations, which then need to be unmirrored
+        let src_loc = self.init_location(id)?;
+        let displaced = symmetric
+            .iter()
+            .map(|(desc, _)| self.relation_targets(src_loc, desc.key.id))
+            .collect_vec();
+
+        // Only newly added components insert their requirements
+        let src = self.archetypes.get(src_loc.arch_id);
+        required.retain(|desc| !src.has(desc.key()));
+
+        let (_, output) = self.set_with_writer_inner(id, writer)?;
+
+        for ((desc, symmetric), displaced) in symmetric.into_iter().zip(displaced) {
+            self.remove_displaced_mirrors(id, desc.key.id, &displaced);
+            self.mirror_symmetric(id, desc, &symmetric);
+        }
+
+        if !required.is_empty() {
+            let arch = self.archetypes.get(self.location(id)?.arch_id);
+            let mut buffer = required_components(required, |key| arch.has(key));
+
+            if !buffer.is_empty() {
+                self.set_with_writer(id, writer::Buffered::new(&mut buffer))?;
+            }
+        }
+
+        // Mirroring and requirements may have moved `id`
+        Ok((self.location(id)?, output))
+    }
+
+    #[inline]
+    fn set_with_writer_inner<U: EntityWriter>(
+        &mut self,
+        id: Entity,
+        writer: U,
     ) -> Result<(EntityLocation, U::Output)> {
         // We know things will change either way
         let change_tick = self.advance_change_tick();
@@ -600,6 +1404,85 @@ impl World {
         Ok(writer.write(self, id, src_loc, change_tick))
     }
 
+    /// Returns the targets of `relation` for the entity at `loc`
+    fn relation_targets(&self, loc: EntityLocation, relation: Entity) -> SmallVec<[Entity; 4]> {
+        self.archetypes
+            .get(loc.arch_id)
+            .relations_like(relation)
+            .map(|(key, _)| key.target.unwrap())
+            .collect()
+    }
+
+    /// Sets the mirror of the symmetric relation `desc` on the relation target.
+    fn mirror_symmetric(&mut self, id: Entity, desc: ComponentDesc, symmetric: &Symmetric) {
+        let target = desc.key.target.unwrap();
+        if target == id {
+            return;
+        }
+
+        // The target is not alive, and can not hold the mirror
+        let Ok(target_loc) = self.init_location(target) else {
+            return;
+        };
+
+        let displaced = self.relation_targets(target_loc, desc.key.id);
+        let mut buffer = symmetric.mirror(self, id, desc);
+
+        self.set_with_writer_inner(target, writer::Buffered::new(&mut buffer))
+            .expect("Target is alive");
+
+        self.remove_displaced_mirrors(target, desc.key.id, &displaced);
+    }
+
+    /// Mirrors the symmetric relations of a newly spawned entity which do not yet have a mirror
+    fn mirror_missing_symmetric(&mut self, id: Entity, symmetric: &[(ComponentDesc, Symmetric)]) {
+        for (desc, symmetric) in symmetric {
+            let target = desc.key.target.unwrap();
+            let mirror = ComponentKey::new(desc.key.id, Some(id));
+
+            let has_mirror = self
+                .location(target)
+                .is_ok_and(|loc| self.archetypes.get(loc.arch_id).has(mirror));
+
+            if !has_mirror {
+                self.mirror_symmetric(id, *desc, symmetric);
+            }
+        }
+    }
+
+    /// Removes the mirrors of the relations in `prev` which `id` no longer has
+    fn remove_displaced_mirrors(&mut self, id: Entity, relation: Entity, prev: &[Entity]) {
+        let Ok(loc) = self.location(id) else {
+            return;
+        };
+
+        let arch = self.archetypes.get(loc.arch_id);
+        let displaced = prev
+            .iter()
+            .filter(|&&target| !arch.has(ComponentKey::new(relation, Some(target))))
+            .map(|&target| ComponentKey::new(relation, Some(target)))
+            .collect::<SmallVec<[_; 2]>>();
+
+        for key in displaced {
+            self.remove_mirror(id, key);
+        }
+    }
+
+    /// Removes the mirror of the symmetric relation `key` which was removed from `id`
+    fn remove_mirror(&mut self, id: Entity, key: ComponentKey) {
+        let target = key.target.unwrap();
+        let mirror = ComponentKey::new(key.id, Some(id));
+
+        let Ok(loc) = self.location(target) else {
+            return;
+        };
+
+        if let Some(desc) = self.archetypes.get(loc.arch_id).component(mirror) {
+            self.remove_dyn(target, desc)
+                .expect("Mirrored relation is present");
+        }
+    }
+
     #[inline]
     pub(crate) fn remove_dyn(&mut self, id: Entity, component: ComponentDesc) -> Result<()> {
         unsafe {
@@ -617,7 +1500,7 @@ impl World {
         let EntityLocation {
             arch_id: src_id,
             slot,
-        } = self.init_location(id).unwrap();
+        } = self.init_location(id)?;
 
         let src = self.archetypes.get(src_id);
 
@@ -671,7 +1554,14 @@ impl World {
 
         *self.location_mut(id).expect("Entity is not valid") = loc;
 
-        Ok(loc)
+        if symmetric_relation(desc).is_none() {
+            return Ok(loc);
+        }
+
+        self.remove_mirror(id, desc.key);
+
+        // Removing the mirror may have moved `id` within its archetype
+        self.location(id)
     }
 
     /// Remove a component from the entity
@@ -685,9 +1575,43 @@ impl World {
 
             res.assume_init()
         };
+
+        self.flush_hooks();
+
         Ok(res)
     }
 
+    /// Removes the components of a bundle from an entity, and returns them.
+    ///
+    /// The components are removed together, and hooks run once all of them are removed.
+    ///
+    /// Fails without removing anything if the entity is missing a component of the bundle.
+    pub fn remove_bundle<B: Bundle>(&mut self, id: Entity) -> Result<B> {
+        let loc = self.init_location(id)?;
+        let arch = self.archetypes.get(loc.arch_id);
+
+        let mut components = Vec::new();
+        B::components(&mut components);
+
+        if let Some(&desc) = components.iter().find(|v| !arch.has(v.key())) {
+            return Err(Error::MissingComponent(MissingComponent { id, desc }));
+        }
+
+        let mut buffer = ComponentBuffer::new();
+        unsafe {
+            self.retain_entity_components_with(
+                id,
+                loc,
+                |key| components.iter().all(|v| v.key() != key),
+                |desc, ptr| buffer.set_dyn(desc, ptr),
+            );
+        }
+
+        self.flush_hooks();
+
+        Ok(B::take_from(&mut buffer).expect("All components of the bundle were removed"))
+    }
+
     /// Randomly access an entity's component.
     pub fn get<T: ComponentValue>(
         &self,
@@ -818,6 +1742,116 @@ impl World {
         }
     }
 
+    /// Returns the entities which have `value` for an indexed component.
+    ///
+    /// Resolves through the index maintained by the [`Indexed`](crate::metadata::Indexed)
+    /// metadata of the component, rather than visiting every entity.
+    ///
+    /// **Note**: Values which are currently being written to through a mutable borrow are
+    /// resolved using their value prior to the borrow.
+    pub fn lookup<T: ComponentValue + Clone>(
+        &self,
+        component: Component<T>,
+        value: &T,
+    ) -> Result<Vec<Entity>> {
+        self.indexes.lookup(self, component, value)
+    }
+
+    /// Returns an entity with the given [`name`](crate::components::name).
+    ///
+    /// Resolved through an index of the names, which is built on the first lookup and kept up to
+    /// date as names change from then on.
+    ///
+    /// If several entities share the name, the one with the lowest id is returned.
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.find_all_by_name(name).into_iter().min()
+    }
+
+    /// Returns all entities with the given [`name`](crate::components::name).
+    ///
+    /// Components are not included, despite being named.
+    pub fn find_all_by_name(&self, entity_name: &str) -> Vec<Entity> {
+        self.names.lookup(self, entity_name)
+    }
+
+    /// Resolves a `/` separated path of names through the [`child_of`] hierarchy.
+    ///
+    /// The first name refers to a root entity, i.e; without a parent, and each following name to
+    /// a child of the previous.
+    ///
+    /// ```rust
+    /// # use flax::{*, components::{name, child_of}};
+    /// let mut world = World::new();
+    ///
+    /// let hand = Entity::builder()
+    ///     .set(name(), "Root".into())
+    ///     .attach(
+    ///         child_of,
+    ///         Entity::builder()
+    ///             .set(name(), "Arm".into())
+    ///             .attach(child_of, Entity::builder().set(name(), "Hand".into())),
+    ///     )
+    ///     .spawn(&mut world);
+    ///
+    /// let hand = world.find_by_path("Root/Arm/Hand").unwrap();
+    /// assert_eq!(world.get(hand, name()).as_deref().map(|v| &v[..]), Ok("Hand"));
+    /// assert_eq!(world.find_by_path("Arm/Hand"), None);
+    /// ```
+    pub fn find_by_path(&self, path: &str) -> Option<Entity> {
+        let mut segments = path.split('/').filter(|v| !v.is_empty());
+
+        let mut current = self.find_all_by_name(segments.next()?);
+        current.retain(|&id| {
+            self.location(id)
+                .map(|loc| {
+                    let arch = self.archetypes.get(loc.arch_id);
+                    arch.relations_like(child_of.id()).next().is_none()
+                })
+                .unwrap_or(false)
+        });
+
+        for segment in segments {
+            let parents = mem::take(&mut current);
+            current = self.find_all_by_name(segment);
+            current.retain(|&id| parents.iter().any(|&parent| self.has(id, child_of(parent))));
+
+            if current.is_empty() {
+                return None;
+            }
+        }
+
+        current.into_iter().min()
+    }
+
+    /// Inserts a resource into the world, returning the previous value.
+    ///
+    /// Resources are singletons keyed by their type, and are accessible to systems through
+    /// [`SystemBuilder::with_resource_ref`](crate::SystemBuilder::with_resource_ref) and
+    /// [`SystemBuilder::with_resource_mut`](crate::SystemBuilder::with_resource_mut).
+    pub fn insert_resource<T: ComponentValue>(&mut self, value: T) -> Option<T> {
+        self.resources.insert(value)
+    }
+
+    /// Removes a resource from the world
+    pub fn remove_resource<T: ComponentValue>(&mut self) -> Option<T> {
+        self.resources.remove()
+    }
+
+    /// Returns true if the world contains the resource
+    pub fn has_resource<T: ComponentValue>(&self) -> bool {
+        self.resources.has::<T>()
+    }
+
+    /// Borrow a resource of the world
+    pub fn resource<T: ComponentValue>(&self) -> Result<AtomicRef<'_, T>> {
+        self.resources.get()
+    }
+
+    /// Borrow a resource of the world mutably
+    pub fn resource_mut<T: ComponentValue>(&self) -> Result<AtomicRefMut<'_, T>> {
+        self.resources.get_mut()
+    }
+
     /// Returns true if the entity is still alive.
     ///
     /// **Note**: false is returned static entities which are not yet present in the world, for example, before
@@ -890,11 +1924,23 @@ impl World {
         ids: &'a [Entity],
         chunk: &mut BatchSpawn,
     ) -> Result<&'a [Entity]> {
+        append_required_batch(chunk);
+
         for component in chunk.components() {
             self.init_component(component);
         }
 
-        self.spawn_batch_at_inner(ids, chunk)
+        let symmetric = symmetric_relations(chunk.components());
+
+        self.spawn_batch_at_inner(ids, chunk)?;
+
+        for &id in ids {
+            self.mirror_missing_symmetric(id, &symmetric);
+        }
+
+        self.flush_hooks();
+
+        Ok(ids)
     }
 
     /// Does not initialize components
@@ -912,7 +1958,7 @@ impl World {
 
         for &id in ids {
             if self.is_reserved(id) {
-                self.despawn(id).unwrap();
+                self.despawn_inner(id).unwrap();
             } else if let Some(v) = self.reconstruct(id.index(), id.kind()) {
                 return Err(Error::EntityOccupied(v));
             }
@@ -1025,23 +2071,7 @@ impl World {
 
     /// Increases the change tick and returns the new one
     pub(crate) fn advance_change_tick(&self) -> u32 {
-        let v = self
-            .change_tick
-            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |v| {
-                // No read bit
-                // No need to update
-                if v & 1 == 0 {
-                    None
-                } else {
-                    Some(v + 1)
-                    // v is not even and not read
-                }
-            });
-
-        match v {
-            Ok(v) => ((v + 1) >> 1) + 1,
-            Err(v) => (v >> 1) + 1,
-        }
+        advance_tick(&self.change_tick)
     }
 
     /// Formats the world using the debug visitor.
@@ -1179,6 +2209,15 @@ impl World {
     pub fn merge_with(&mut self, other: &mut World) -> MigratedEntities {
         let mut archetypes = mem::replace(&mut other.archetypes, Archetypes::new());
         let mut entities = mem::take(&mut other.entities);
+        other.archetypes.add_subscriber(other.hooks.clone());
+        other.archetypes.add_subscriber(other.indexes.clone());
+        other.archetypes.add_subscriber(other.names.clone());
+        other.archetypes.add_subscriber(other.removed.clone());
+        if let Some(delta) = &other.delta {
+            other.archetypes.add_subscriber(delta.clone());
+            other.archetypes.track_modified();
+        }
+        let removed_tick = other.change_tick();
 
         let mut components = BTreeMap::new();
 
@@ -1186,7 +2225,8 @@ impl World {
 
         let mut new_ids = BTreeMap::new();
 
-        let mut buffer = Entity::builder();
+        let mut buffer = ComponentBuffer::new();
+        let mut spawned = Vec::new();
 
         for (arch_id, arch) in archetypes.iter_mut() {
             if !arch.has(is_static_entity().key()) {
@@ -1222,6 +2262,9 @@ impl World {
             }
         }
 
+        // Entity ids contained in component values
+        let map_id = |id| *new_ids.get(&id).unwrap_or(&id);
+
         for (_, arch) in archetypes.iter_mut() {
             // Don't migrate static components
             if !arch.has(is_static_entity().key()) {
@@ -1244,14 +2287,34 @@ impl World {
                         storage.set_id(id);
                     }
 
+                    if let Some(mappable) =
+                        storage.desc().meta_ref().get(metadata::entity_mappable())
+                    {
+                        mappable.map_storage(&mut storage, &map_id);
+                    }
+
                     batch.append(storage).expect("Batch is incomplete");
                 }
 
+                let symmetric = symmetric_relations(batch.components());
+
                 // Skip initializing components as component entities will be added by further
                 // iterations of the loop, and can thus not be spawned as they need to be
                 // unoccupied.
                 self.spawn_batch_at_inner(&arch.entities, &mut batch)
                     .expect("Failed to spawn batch");
+
+                if !symmetric.is_empty() {
+                    spawned.push((arch.entities, symmetric));
+                }
+            }
+        }
+
+        // Relations between the merged entities are already mirrored, but relations to entities
+        // outside of `other` are not
+        for (ids, symmetric) in spawned {
+            for id in ids {
+                self.mirror_missing_symmetric(id, &symmetric);
             }
         }
 
@@ -1271,17 +2334,56 @@ impl World {
                             *target = *new_ids.get(target).unwrap_or(target);
                         }
 
+                        if let Some(mappable) = desc.meta_ref().get(metadata::entity_mappable()) {
+                            mappable.map_value(ptr, &map_id);
+                        }
+
                         // Migrate custom components
                         buffer.set_dyn(desc, ptr);
                     })
                 } {
-                    buffer.append_to(self, id).unwrap();
+                    self.set_with_writer(id, writer::Buffered::new(&mut buffer))
+                        .unwrap();
                 }
             }
         }
+
+        // The entities were moved rather than removed from `other`
+        drop(archetypes);
+        other.hooks.take();
+        other.indexes.clear();
+        other.names.clear();
+        other.removed.discard(removed_tick);
+
+        self.flush_hooks();
+
         MigratedEntities { ids: new_ids }
     }
 
+    /// Applies the commands recorded by component hooks.
+    ///
+    /// Returns true if any commands were applied.
+    pub(crate) fn flush_hooks(&mut self) -> bool {
+        let mut cmd = self.hooks.take();
+        if cmd.is_empty() {
+            return false;
+        }
+
+        cmd.apply_hooks(self);
+        true
+    }
+
+    /// Returns the errors of the commands recorded by component hooks since the last call.
+    ///
+    /// Hook commands are applied after the operation which invoked the hook, and their errors can
+    /// thus not be returned by the operation itself. Commands targeting an entity which has since
+    /// been despawned are not considered errors.
+    ///
+    /// The errors are retained until taken.
+    pub fn take_hook_errors(&mut self) -> Vec<anyhow::Error> {
+        mem::take(&mut self.hook_errors)
+    }
+
     /// Converts all reserved entity ids into actual empty entities placed in a special archetype.
     #[inline]
     fn flush_reserved(&mut self) {
@@ -1314,15 +2416,74 @@ impl World {
         assert!(id.is_static());
         let mut buffer = ComponentBuffer::new();
         buffer.set(is_static_entity(), ());
+
+        // Initializing the component may spawn `id` itself, such as for `component_info`
+        self.init_component(is_static_entity().desc());
+        if let Ok(loc) = self.location(id) {
+            return Ok(loc);
+        }
+
         let (_, loc) = self.spawn_at_with(id, &mut buffer)?;
         Ok(loc)
     }
 }
 
+/// Increases the change tick if it has been read, and returns the new one
+pub(crate) fn advance_tick(change_tick: &AtomicU32) -> u32 {
+    let v = change_tick.fetch_update(Ordering::Acquire, Ordering::Relaxed, |v| {
+        // No read bit
+        // No need to update
+        if v & 1 == 0 {
+            None
+        } else {
+            Some(v + 1)
+            // v is not even and not read
+        }
+    });
+
+    match v {
+        Ok(v) => ((v + 1) >> 1) + 1,
+        Err(v) => (v >> 1) + 1,
+    }
+}
+
+/// Returns true if the archetype contains neither static entities nor components
+fn is_dynamic(arch: &Archetype) -> bool {
+    !arch.has(is_static_entity().key()) && !arch.has(component_info().key())
+}
+
+/// Returns the clone implementation of a component which is part of world snapshots
+fn rollback_cloneable(desc: ComponentDesc) -> Option<metadata::Cloneable> {
+    let meta = desc.meta_ref();
+    if meta.has(metadata::no_rollback()) {
+        return None;
+    }
+
+    meta.get(metadata::cloneable()).cloned()
+}
+
+/// Returns the symmetric relation metadata if `desc` is a symmetric relation
+fn symmetric_relation(desc: ComponentDesc) -> Option<Symmetric> {
+    if desc.is_relation() {
+        desc.meta_ref().get(symmetric()).cloned()
+    } else {
+        None
+    }
+}
+
+fn symmetric_relations(
+    components: impl IntoIterator<Item = ComponentDesc>,
+) -> SmallVec<[(ComponentDesc, Symmetric); 2]> {
+    components
+        .into_iter()
+        .filter_map(|desc| Some((desc, symmetric_relation(desc)?)))
+        .collect()
+}
+
 /// Holds the migrated components
-#[derive(Debug, Clone)]
+#[derive(Default, Debug, Clone)]
 pub struct MigratedEntities {
-    ids: BTreeMap<Entity, Entity>,
+    pub(crate) ids: BTreeMap<Entity, Entity>,
 }
 
 impl MigratedEntities {
@@ -1355,6 +2516,12 @@ impl MigratedEntities {
         move |target| component.of(target)
     }
 
+    /// Returns the migrated component and relation target of `desc`
+    pub(crate) fn get_desc(&self, mut desc: ComponentDesc) -> ComponentDesc {
+        desc.key = ComponentKey::new(self.get(desc.key.id), desc.key.target.map(|v| self.get(v)));
+        desc
+    }
+
     /// Returns the migrated ids
     pub fn ids(&self) -> &BTreeMap<Entity, Entity> {
         &self.ids
@@ -76,6 +76,9 @@ pub unsafe trait EntityWriter {
         loc: EntityLocation,
         tick: u32,
     ) -> (EntityLocation, Self::Output);
+
+    /// Visits each component which will be written to the entity
+    fn for_each_component(&self, f: impl FnMut(ComponentDesc));
 }
 
 pub(crate) struct SingleComponentWriter<W> {
@@ -172,6 +175,10 @@ unsafe impl<W: ComponentUpdater + ComponentPusher> EntityWriter for SingleCompon
 
         (dst_loc, Either::Right(pushed))
     }
+
+    fn for_each_component(&self, mut f: impl FnMut(ComponentDesc)) {
+        f(self.desc)
+    }
 }
 
 pub(crate) struct Replace<T: ComponentValue> {
@@ -191,7 +198,7 @@ impl<T: ComponentValue> ComponentUpdater for Replace<T> {
         let storage = data.storage.downcast_mut::<T>();
         let old = mem::replace(&mut storage[slot], self.value);
 
-        data.set_modified(&[id], Slice::single(slot), tick);
+        data.set_replaced(&[id], Slice::single(slot), tick);
 
         old
     }
@@ -254,7 +261,7 @@ impl<T: ComponentValue + PartialEq> ComponentUpdater for WriteDedup<T> {
         if current != &self.value {
             *current = self.value;
 
-            data.set_modified(&[id], Slice::single(slot), tick);
+            data.set_replaced(&[id], Slice::single(slot), tick);
         }
     }
 }
@@ -296,7 +303,7 @@ impl ComponentUpdater for WriteDedupDyn {
             ptr::copy_nonoverlapping(self.value, dst, desc.size());
         }
 
-        data.set_modified(&[id], Slice::single(slot), tick);
+        data.set_replaced(&[id], Slice::single(slot), tick);
     }
 }
 
@@ -328,7 +335,7 @@ impl ComponentUpdater for ReplaceDyn {
             ptr::copy_nonoverlapping(self.value, dst, desc.size());
         }
 
-        data.set_modified(&[id], Slice::single(slot), tick);
+        data.set_replaced(&[id], Slice::single(slot), tick);
     }
 }
 
@@ -404,7 +411,7 @@ unsafe impl EntityWriter for Buffered<'_> {
                     desc.drop(dst);
                     ptr::copy_nonoverlapping(src, dst, desc.size());
 
-                    data.set_modified(&[id], Slice::single(src_loc.slot), tick);
+                    data.set_replaced(&[id], Slice::single(src_loc.slot), tick);
                     false
                 } else {
                     // Component does not exist yet, so defer a move
@@ -464,6 +471,10 @@ unsafe impl EntityWriter for Buffered<'_> {
 
         (dst_loc, ())
     }
+
+    fn for_each_component(&self, f: impl FnMut(ComponentDesc)) {
+        self.buffer.components().copied().for_each(f)
+    }
 }
 
 fn find_archetype_components(
@@ -314,3 +314,107 @@ fn move_older_changes() {
 
     assert_eq!(query.collect_vec(&world), [id2, id1]);
 }
+
+#[test]
+fn removed() {
+    component! {
+        health: f32,
+        pos: (f32, f32),
+    }
+
+    let mut world = World::new();
+
+    let ids = (0..4)
+        .map(|i| {
+            Entity::builder()
+                .set(health(), i as f32)
+                .set(pos(), (0.0, 0.0))
+                .spawn(&mut world)
+        })
+        .collect_vec();
+
+    let mut removed = health().removed();
+    let mut other = health().removed();
+    assert_eq!(removed.borrow(&world), []);
+
+    world.remove(ids[1], health()).unwrap();
+    world.remove(ids[2], pos()).unwrap();
+    world.despawn(ids[3]).unwrap();
+
+    assert_eq!(removed.borrow(&world), [(ids[1], 1.0), (ids[3], 3.0)]);
+    assert_eq!(removed.borrow(&world), []);
+
+    // Removals are tracked from the first borrow
+    assert_eq!(other.borrow(&world), []);
+    world.despawn(ids[0]).unwrap();
+    assert_eq!(other.borrow(&world), [(ids[0], 0.0)]);
+
+    let mut cmd = CommandBuffer::new();
+    cmd.remove(ids[2], health());
+    cmd.apply(&mut world).unwrap();
+
+    assert_eq!(removed.borrow(&world), [(ids[0], 0.0), (ids[2], 2.0)]);
+
+    // Merged entities are moved rather than removed
+    let mut other_world = World::new();
+    Entity::builder().set(health(), 5.0).spawn(&mut other_world);
+
+    let mut removed = health().removed();
+    assert_eq!(removed.borrow(&other_world), []);
+    world.merge_with(&mut other_world);
+    assert_eq!(removed.borrow(&other_world), []);
+}
+
+#[test]
+fn removed_system() {
+    component! {
+        health: f32,
+    }
+
+    let mut world = World::new();
+
+    let ids = (0..4)
+        .map(|i| Entity::builder().set(health(), i as f32).spawn(&mut world))
+        .collect_vec();
+
+    let mut system = System::builder()
+        .with(health().removed())
+        .build(|removed: Vec<(Entity, f32)>| removed);
+
+    assert_eq!(system.run(&mut world), []);
+
+    world.despawn(ids[1]).unwrap();
+    world.despawn(ids[2]).unwrap();
+
+    assert_eq!(system.run(&mut world), [(ids[1], 1.0), (ids[2], 2.0)]);
+    assert_eq!(system.run(&mut world), []);
+}
+
+#[test]
+fn removed_ticks() {
+    component! {
+        health: f32,
+    }
+
+    let mut world = World::new();
+    world.track_deltas();
+
+    let id = Entity::builder().set(health(), 1.0).spawn(&mut world);
+
+    let mut removed = health().removed();
+    assert_eq!(removed.tick(), None);
+    assert_eq!(removed.borrow(&world), []);
+
+    let tick = removed.tick().unwrap();
+    assert_eq!(tick, world.change_tick());
+
+    world.remove(id, health()).unwrap();
+    assert!(world.change_tick() > tick);
+
+    // The removal is part of the changes after the tick of the previous borrow
+    let delta = world.diff(tick);
+    assert_eq!(delta.get(id).unwrap().removed(), [health().desc()]);
+
+    assert_eq!(removed.borrow(&world), [(id, 1.0)]);
+    assert_eq!(removed.tick(), Some(world.change_tick()));
+}
@@ -0,0 +1,93 @@
+use flax::{
+    components::{child_of, name},
+    *,
+};
+use pretty_assertions::assert_eq;
+
+component! {
+    health: f32 => [ Cloneable ],
+    inventory: Vec<String> => [ Cloneable ],
+    handle: std::sync::Arc<()>,
+}
+
+#[test]
+fn clone_entity() {
+    let mut world = World::new();
+
+    let parent = Entity::builder()
+        .set(name(), "Parent".into())
+        .spawn(&mut world);
+
+    let id = Entity::builder()
+        .set(name(), "Player".into())
+        .set(health(), 100.0)
+        .set(inventory(), vec!["Sword".into()])
+        .set(child_of(parent), ())
+        .spawn(&mut world);
+
+    let copy = world.clone_entity(id).unwrap();
+    assert_ne!(copy, id);
+
+    world
+        .get_mut(id, inventory())
+        .unwrap()
+        .push("Shield".into());
+
+    let entity = world.entity(copy).unwrap();
+    assert_eq!(entity.get(name()).as_deref(), Ok(&"Player".into()));
+    assert_eq!(entity.get_copy(health()), Ok(100.0));
+    assert_eq!(
+        entity.get(inventory()).as_deref(),
+        Ok(&vec!["Sword".into()])
+    );
+    assert!(entity.has(child_of(parent)));
+
+    // Modify the clone before spawning
+    let mut builder = world.entity(id).unwrap().to_builder().unwrap();
+    builder.set(health(), 50.0);
+    let modified = builder.spawn(&mut world);
+
+    assert_eq!(world.get_copy(modified, health()), Ok(50.0));
+    assert_eq!(
+        world.get(modified, inventory()).as_deref(),
+        Ok(&vec!["Sword".into(), "Shield".into()])
+    );
+    assert_eq!(world.get_copy(id, health()), Ok(100.0));
+}
+
+#[test]
+fn clone_entity_into() {
+    let mut world = World::new();
+    let mut other = World::new();
+
+    let id = Entity::builder()
+        .set(name(), "Player".into())
+        .set(health(), 100.0)
+        .spawn(&mut world);
+
+    let copy = world.clone_entity_into(id, &mut other).unwrap();
+    assert_eq!(other.get_copy(copy, health()), Ok(100.0));
+    assert_eq!(other.find_by_name("Player"), Some(copy));
+    assert!(world.is_alive(id));
+}
+
+#[test]
+fn clone_not_cloneable() {
+    let mut world = World::new();
+
+    let id = Entity::builder()
+        .set(health(), 100.0)
+        .set(handle(), Default::default())
+        .spawn(&mut world);
+
+    assert_eq!(
+        world.clone_entity(id),
+        Err(Error::NotCloneable(handle().desc()))
+    );
+    assert_eq!(
+        world.entity(id).unwrap().to_builder().map(|_| ()),
+        Err(Error::NotCloneable(handle().desc()))
+    );
+
+    assert_eq!(Query::new(health()).borrow(&world).iter().count(), 1);
+}
@@ -0,0 +1,165 @@
+use flax::{
+    components::{child_of, name},
+    world::MigratedEntities,
+    *,
+};
+use pretty_assertions::assert_eq;
+
+component! {
+    health: f32 => [ Cloneable ],
+    armor: f32 => [ Cloneable ],
+    handle: std::sync::Arc<()>,
+}
+
+#[test]
+fn diff() {
+    let mut world = World::new();
+    world.track_deltas();
+
+    let a = Entity::builder()
+        .set(health(), 100.0)
+        .set(armor(), 5.0)
+        .spawn(&mut world);
+
+    let b = Entity::builder().set(health(), 50.0).spawn(&mut world);
+
+    let delta = world.diff(0);
+    assert_eq!(delta.spawned(), [a, b]);
+    assert_eq!(delta.despawned(), []);
+    assert_eq!(delta.get(a).unwrap().added().get(health()), Some(&100.0));
+
+    let baseline = delta.tick();
+    assert!(world.diff(baseline).is_empty());
+
+    *world.get_mut(a, health()).unwrap() = 75.0;
+    world.remove(a, armor()).unwrap();
+    world.set(b, armor(), 1.0).unwrap();
+    let c = Entity::builder()
+        .set(health(), 10.0)
+        .set(handle(), Default::default())
+        .spawn(&mut world);
+
+    let delta = world.diff(baseline);
+    assert_eq!(delta.spawned(), [c]);
+    assert_eq!(delta.despawned(), []);
+
+    let a_delta = delta.get(a).unwrap();
+    assert_eq!(a_delta.modified().get(health()), Some(&75.0));
+    assert!(a_delta.added().is_empty());
+    assert_eq!(
+        a_delta
+            .removed()
+            .iter()
+            .map(|v| v.key())
+            .collect::<Vec<_>>(),
+        [armor().key()]
+    );
+
+    let b_delta = delta.get(b).unwrap();
+    assert_eq!(b_delta.added().get(armor()), Some(&1.0));
+    assert!(b_delta.modified().is_empty());
+
+    // Not cloneable
+    assert!(!delta.get(c).unwrap().added().has(handle()));
+    assert_eq!(delta.get(c).unwrap().added().get(health()), Some(&10.0));
+
+    let baseline = delta.tick();
+    world.despawn(b).unwrap();
+    world.despawn(c).unwrap();
+
+    let delta = world.diff(baseline);
+    assert_eq!(delta.spawned(), []);
+    assert_eq!(delta.despawned(), [b, c]);
+    assert!(delta.get(a).is_none());
+}
+
+#[test]
+fn apply_delta() {
+    let mut world = World::new();
+    world.track_deltas();
+
+    let parent = Entity::builder()
+        .set(name(), "parent".into())
+        .spawn(&mut world);
+
+    let child = Entity::builder()
+        .set(name(), "child".into())
+        .set(health(), 100.0)
+        .set(child_of(parent), ())
+        .spawn(&mut world);
+
+    let mut replica = World::new();
+    // Offset the ids of the replica
+    replica.spawn();
+
+    let mut ids = MigratedEntities::default();
+
+    let delta = world.diff(0);
+    let baseline = delta.tick();
+    replica.apply_delta(delta, &mut ids);
+
+    let local_parent = ids.get(parent);
+    let local_child = ids.get(child);
+    assert_ne!(local_child, child);
+
+    assert_eq!(replica.find_by_name("parent"), Some(local_parent));
+    assert_eq!(replica.get_copy(local_child, health()), Ok(100.0));
+    assert!(replica.has(local_child, child_of(local_parent)));
+
+    *world.get_mut(child, health()).unwrap() = 25.0;
+    world.set(child, armor(), 3.0).unwrap();
+    world.despawn(parent).unwrap();
+
+    let other = Entity::builder().set(health(), 1.0).spawn(&mut world);
+
+    replica.apply_delta(world.diff(baseline), &mut ids);
+
+    assert!(!replica.is_alive(local_parent));
+    assert_eq!(replica.get_copy(local_child, health()), Ok(25.0));
+    assert_eq!(replica.get_copy(local_child, armor()), Ok(3.0));
+    assert!(!replica.has(local_child, child_of(local_parent)));
+    assert_eq!(replica.get_copy(ids.get(other), health()), Ok(1.0));
+}
+
+#[test]
+fn diff_restore() {
+    let mut world = World::new();
+    world.track_deltas();
+
+    let id = Entity::builder().set(health(), 100.0).spawn(&mut world);
+
+    let mut replica = World::new();
+    let mut ids = MigratedEntities::default();
+
+    let delta = world.diff(0);
+    let mut baseline = delta.tick();
+    replica.apply_delta(delta, &mut ids);
+
+    let snapshot = world.snapshot();
+
+    *world.get_mut(id, health()).unwrap() = 50.0;
+    let other = Entity::builder().set(health(), 1.0).spawn(&mut world);
+
+    let delta = world.diff(baseline);
+    assert_eq!(delta.get(id).unwrap().modified().get(health()), Some(&50.0));
+    baseline = delta.tick();
+    replica.apply_delta(delta, &mut ids);
+
+    assert_eq!(replica.get_copy(ids.get(id), health()), Ok(50.0));
+    let local_other = ids.get(other);
+
+    world.restore(&snapshot);
+    assert_eq!(world.get_copy(id, health()), Ok(100.0));
+
+    // The rollback is a change like any other
+    let delta = world.diff(baseline);
+    assert_eq!(
+        delta.get(id).unwrap().modified().get(health()),
+        Some(&100.0)
+    );
+    assert_eq!(delta.despawned(), [other]);
+    replica.apply_delta(delta, &mut ids);
+
+    assert_eq!(replica.get_copy(ids.get(id), health()), Ok(100.0));
+    assert!(!replica.is_alive(local_other));
+}
@@ -56,3 +56,202 @@ fn derive_fetch() {
         })
     );
 }
+
+#[test]
+#[cfg(feature = "derive")]
+fn derive_fetch_enum() {
+    use flax::{Fetch, *};
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+
+    flax::component! {
+        radius: f32,
+        extent: (f32, f32),
+        color: u32,
+    }
+
+    // The enum only declares the variants of the generated fetch, and is never constructed
+    #[allow(dead_code)]
+    #[derive(Fetch)]
+    #[fetch(item_derives = [Debug, PartialEq])]
+    enum Shape {
+        Circle(Component<f32>),
+        Rect(Component<(f32, f32)>),
+    }
+
+    let mut world = World::new();
+
+    let circle = Entity::builder()
+        .set(radius(), 1.0)
+        .set(color(), 1)
+        .spawn(&mut world);
+
+    let rect = Entity::builder()
+        .set(extent(), (2.0, 3.0))
+        .spawn(&mut world);
+
+    // The first matching variant is used
+    let both = Entity::builder()
+        .set(radius(), 4.0)
+        .set(extent(), (5.0, 6.0))
+        .spawn(&mut world);
+
+    Entity::builder().set(color(), 2).spawn(&mut world);
+
+    let mut query = Query::new((
+        entity_ids(),
+        ShapeFetch {
+            circle: radius(),
+            rect: extent(),
+        },
+    ));
+
+    assert_eq!(
+        query
+            .borrow(&world)
+            .iter()
+            .sorted_by_key(|v| v.0)
+            .collect_vec(),
+        [
+            (circle, ShapeItem::Circle(&1.0)),
+            (rect, ShapeItem::Rect(&(2.0, 3.0))),
+            (both, ShapeItem::Circle(&4.0)),
+        ]
+    );
+
+    assert_eq!(
+        query.borrow(&world).get(rect),
+        Ok((rect, ShapeItem::Rect(&(2.0, 3.0))))
+    );
+}
+
+#[test]
+#[cfg(feature = "derive")]
+fn derive_bundle() {
+    use flax::{Bundle, *};
+    use glam::*;
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+
+    flax::component! {
+        position: Vec3,
+        health: f32,
+        is_player: (),
+    }
+
+    #[derive(Bundle, Debug, Clone, PartialEq)]
+    struct Player {
+        #[bundle(component = position)]
+        pos: Vec3,
+        health: f32,
+    }
+
+    let mut world = World::new();
+
+    let player = Player {
+        pos: vec3(1.0, 2.0, 3.0),
+        health: 100.0,
+    };
+
+    let a = Entity::builder()
+        .set_bundle(player.clone())
+        .tag(is_player())
+        .spawn(&mut world);
+
+    let b = Entity::builder().tag(is_player()).spawn(&mut world);
+
+    world
+        .set_bundle(
+            b,
+            Player {
+                pos: Vec3::ZERO,
+                health: 50.0,
+            },
+        )
+        .unwrap();
+
+    let mut query = Query::new((entity_ids(), Player::fetch()));
+    assert_eq!(
+        query
+            .borrow(&world)
+            .iter()
+            .map(|(id, v)| (id, *v.pos, *v.health))
+            .sorted_by_key(|v| v.0)
+            .collect_vec(),
+        [(a, player.pos, 100.0), (b, Vec3::ZERO, 50.0)]
+    );
+
+    assert_eq!(world.remove_bundle::<Player>(a), Ok(player.clone()));
+    assert!(!world.has(a, position()));
+    assert!(!world.has(a, health()));
+    assert!(world.has(a, is_player()));
+
+    // Nothing is removed if a component is missing
+    world.remove(b, health()).unwrap();
+    assert_eq!(
+        world.remove_bundle::<Player>(b),
+        Err(Error::MissingComponent(error::MissingComponent {
+            id: b,
+            desc: health().desc()
+        }))
+    );
+    assert!(world.has(b, position()));
+
+    let mut cmd = CommandBuffer::new();
+    cmd.set_bundle(b, player.clone());
+    cmd.apply(&mut world).unwrap();
+
+    assert_eq!(world.remove_bundle::<Player>(b), Ok(player));
+}
+
+#[test]
+#[cfg(feature = "derive")]
+fn derive_bundle_remove_hooks() {
+    use std::sync::{Arc, Mutex};
+
+    use flax::{Bundle, *};
+    use pretty_assertions::assert_eq;
+
+    struct MassHooks;
+
+    impl ComponentHooks<f32> for MassHooks {
+        fn on_remove(id: Entity, _: &f32, cmd: &mut CommandBuffer) {
+            cmd.defer(move |world| {
+                // The other components of the bundle are already removed
+                let seen = (world.has(id, velocity()), world.has(id, is_body()));
+                world.get_mut(id, log())?.lock().unwrap().push(seen);
+                Ok(())
+            });
+        }
+    }
+
+    flax::component! {
+        mass: f32 => [ Hooks<MassHooks> ],
+        velocity: f32,
+        is_body: (),
+        log: Arc<Mutex<Vec<(bool, bool)>>>,
+    }
+
+    #[derive(Bundle, Debug, Clone, PartialEq)]
+    struct Body {
+        mass: f32,
+        velocity: f32,
+    }
+
+    let mut world = World::new();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let body = Body {
+        mass: 1.0,
+        velocity: 2.0,
+    };
+
+    let id = Entity::builder()
+        .set_bundle(body.clone())
+        .tag(is_body())
+        .set(log(), seen.clone())
+        .spawn(&mut world);
+
+    assert_eq!(world.remove_bundle::<Body>(id), Ok(body));
+    assert_eq!(*seen.lock().unwrap(), [(false, true)]);
+}
@@ -0,0 +1,163 @@
+use std::sync::Mutex;
+
+use flax::{components::child_of, *};
+use pretty_assertions::assert_eq;
+
+struct BodyHooks;
+
+impl ComponentHooks<f32> for BodyHooks {
+    fn on_add(id: Entity, value: &f32, cmd: &mut CommandBuffer) {
+        cmd.set(id, handle(), *value as u32);
+    }
+
+    fn on_replace(id: Entity, value: &f32, cmd: &mut CommandBuffer) {
+        cmd.set(id, handle(), *value as u32);
+    }
+
+    fn on_remove(id: Entity, _: &f32, cmd: &mut CommandBuffer) {
+        cmd.remove(id, handle());
+    }
+}
+
+struct HandleHooks;
+
+impl ComponentHooks<u32> for HandleHooks {
+    fn on_add(id: Entity, _: &u32, cmd: &mut CommandBuffer) {
+        cmd.set(id, synced(), ());
+    }
+
+    fn on_remove(id: Entity, _: &u32, cmd: &mut CommandBuffer) {
+        cmd.remove(id, synced());
+    }
+}
+
+static LOG: Mutex<Vec<(Entity, &'static str)>> = Mutex::new(Vec::new());
+
+struct LogHooks;
+
+impl ComponentHooks<()> for LogHooks {
+    fn on_add(id: Entity, _: &(), _: &mut CommandBuffer) {
+        LOG.lock().unwrap().push((id, "add"));
+    }
+
+    fn on_replace(id: Entity, _: &(), _: &mut CommandBuffer) {
+        LOG.lock().unwrap().push((id, "replace"));
+    }
+
+    fn on_remove(id: Entity, _: &(), _: &mut CommandBuffer) {
+        LOG.lock().unwrap().push((id, "remove"));
+    }
+}
+
+struct FaultyHooks;
+
+impl ComponentHooks<()> for FaultyHooks {
+    fn on_add(id: Entity, _: &(), cmd: &mut CommandBuffer) {
+        cmd.remove(id, handle());
+    }
+}
+
+component! {
+    body: f32 => [ Hooks<BodyHooks> ],
+    handle: u32 => [ Hooks<HandleHooks> ],
+    synced: (),
+    logged(id): () => [ Hooks<LogHooks>, Exclusive ],
+    faulty: () => [ Hooks<FaultyHooks> ],
+}
+
+#[test]
+fn hooks() {
+    let mut world = World::new();
+
+    let id = Entity::builder().set(body(), 1.0).spawn(&mut world);
+
+    assert_eq!(world.get_copy(id, handle()), Ok(1));
+    assert!(world.has(id, synced()));
+
+    world.set(id, body(), 2.0).unwrap();
+    assert_eq!(world.get_copy(id, handle()), Ok(2));
+
+    // Modifications in place are not replacements
+    *world.get_mut(id, body()).unwrap() = 3.0;
+    assert_eq!(world.get_copy(id, handle()), Ok(2));
+
+    world.entity_mut(id).unwrap().set(body(), 4.0);
+    assert_eq!(world.get_copy(id, handle()), Ok(4));
+
+    let mut cmd = CommandBuffer::new();
+    cmd.set(id, body(), 5.0);
+    cmd.apply(&mut world).unwrap();
+    assert_eq!(world.get_copy(id, handle()), Ok(5));
+
+    world.remove(id, body()).unwrap();
+    assert!(!world.has(id, handle()));
+    assert!(!world.has(id, synced()));
+
+    let mut batch = BatchSpawn::new(2);
+    batch.set(body(), vec![6.0, 7.0]).unwrap();
+    let ids = batch.spawn(&mut world);
+
+    assert_eq!(world.get_copy(ids[0], handle()), Ok(6));
+    assert_eq!(world.get_copy(ids[1], handle()), Ok(7));
+    assert!(world.has(ids[1], synced()));
+
+    // The commands of the removal hooks target a despawned entity
+    world.despawn(ids[0]).unwrap();
+    assert!(!world.is_alive(ids[0]));
+    assert_eq!(world.get_copy(ids[1], handle()), Ok(7));
+    assert!(world.take_hook_errors().is_empty());
+}
+
+#[test]
+fn hook_errors() {
+    let mut world = World::new();
+
+    let id = Entity::builder().set(faulty(), ()).spawn(&mut world);
+    assert!(world.is_alive(id));
+
+    let errors = world.take_hook_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "Failed to remove component handle");
+
+    assert!(world.take_hook_errors().is_empty());
+}
+
+#[test]
+fn hooks_relations() {
+    let mut world = World::new();
+
+    let parent = world.spawn();
+    let a = world.spawn();
+    let b = world.spawn();
+
+    let id = Entity::builder()
+        .set(logged(a), ())
+        .set(child_of(parent), ())
+        .spawn(&mut world);
+
+    world.set(id, logged(a), ()).unwrap();
+    // Displaces the exclusive relation
+    world.set(id, logged(b), ()).unwrap();
+    // Detaches the relation
+    world.despawn(b).unwrap();
+
+    let other = Entity::builder()
+        .set(logged(a), ())
+        .set(child_of(parent), ())
+        .spawn(&mut world);
+
+    world.despawn_recursive(parent, child_of).unwrap();
+
+    assert_eq!(
+        LOG.lock().unwrap()[..],
+        [
+            (id, "add"),
+            (id, "replace"),
+            (id, "remove"),
+            (id, "add"),
+            (id, "remove"),
+            (other, "add"),
+            (other, "remove"),
+        ]
+    );
+}
@@ -0,0 +1,244 @@
+use flax::{
+    components::{child_of, name},
+    *,
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+component! {
+    network_id: u64 => [ Indexed ],
+    guid: String => [ Indexed ],
+    health: f32,
+}
+
+#[test]
+fn lookup() {
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set(network_id(), 1)
+        .set(name(), "a".into())
+        .spawn(&mut world);
+
+    let b = Entity::builder()
+        .set(network_id(), 2)
+        .set(health(), 100.0)
+        .spawn(&mut world);
+
+    assert_eq!(world.lookup(network_id(), &1), Ok(vec![a]));
+    assert_eq!(world.lookup(network_id(), &2), Ok(vec![b]));
+    assert_eq!(world.lookup(network_id(), &3), Ok(vec![]));
+    assert_eq!(
+        world.lookup(health(), &100.0),
+        Err(Error::NotIndexed(health().desc()))
+    );
+
+    // Replace
+    world.set(a, network_id(), 3).unwrap();
+    assert_eq!(world.lookup(network_id(), &1), Ok(vec![]));
+    assert_eq!(world.lookup(network_id(), &3), Ok(vec![a]));
+
+    // Moving the entity to another archetype keeps the value indexed
+    world.set(a, health(), 50.0).unwrap();
+    world.set(b, network_id(), 3).unwrap();
+    assert_eq!(
+        world
+            .lookup(network_id(), &3)
+            .unwrap()
+            .into_iter()
+            .sorted()
+            .collect_vec(),
+        [a, b]
+    );
+
+    world.remove(a, network_id()).unwrap();
+    assert_eq!(world.lookup(network_id(), &3), Ok(vec![b]));
+
+    world.despawn(b).unwrap();
+    assert_eq!(world.lookup(network_id(), &3), Ok(vec![]));
+
+    let mut cmd = CommandBuffer::new();
+    cmd.set(a, network_id(), 4);
+    cmd.apply(&mut world).unwrap();
+    assert_eq!(world.lookup(network_id(), &4), Ok(vec![a]));
+}
+
+#[test]
+fn lookup_modified() {
+    let mut world = World::new();
+
+    let ids = (0..10)
+        .map(|i| Entity::builder().set(network_id(), i).spawn(&mut world))
+        .collect_vec();
+
+    Query::new(network_id().as_mut())
+        .borrow(&world)
+        .for_each(|v| *v += 100);
+
+    assert_eq!(world.lookup(network_id(), &1), Ok(vec![]));
+    assert_eq!(world.lookup(network_id(), &101), Ok(vec![ids[1]]));
+
+    *world.get_mut(ids[2], network_id()).unwrap() = 1;
+    assert_eq!(world.lookup(network_id(), &102), Ok(vec![]));
+    assert_eq!(world.lookup(network_id(), &1), Ok(vec![ids[2]]));
+}
+
+#[test]
+fn lookup_merge() {
+    let mut world = World::new();
+    let a = Entity::builder()
+        .set(network_id(), 1)
+        .set(guid(), "a".into())
+        .spawn(&mut world);
+
+    let mut other = World::new();
+    let b = Entity::builder()
+        .set(network_id(), 2)
+        .set(guid(), "b".into())
+        .spawn(&mut other);
+
+    let migrated = world.merge_with(&mut other);
+    let b = migrated.get(b);
+
+    assert_eq!(other.lookup(network_id(), &2), Ok(vec![]));
+    assert_eq!(world.lookup(network_id(), &1), Ok(vec![a]));
+    assert_eq!(world.lookup(network_id(), &2), Ok(vec![b]));
+    assert_eq!(world.lookup(guid(), &"b".into()), Ok(vec![b]));
+}
+
+#[test]
+fn eq_indexed() {
+    let mut world = World::new();
+
+    let ids = (0..32)
+        .map(|i| {
+            let mut builder = Entity::builder();
+            builder.set(network_id(), i % 4).set(guid(), i.to_string());
+
+            if i % 3 == 0 {
+                builder.set(health(), i as f32);
+            }
+
+            builder.spawn(&mut world)
+        })
+        .collect_vec();
+
+    let expected = ids
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % 4 == 2)
+        .map(|(_, &id)| id)
+        .sorted()
+        .collect_vec();
+
+    let mut query = Query::new(entity_ids()).with_filter(network_id().eq_indexed(2));
+
+    assert_eq!(
+        query.collect_vec(&world).into_iter().sorted().collect_vec(),
+        expected
+    );
+
+    let mut query =
+        Query::new((entity_ids(), health().copied())).with_filter(guid().eq_indexed("9".into()));
+    assert_eq!(query.collect_vec(&world), [(ids[9], 9.0)]);
+
+    // Entities not yielded by the filter are not marked as modified
+    let mut modified = Query::new(entity_ids()).with_filter(health().modified());
+    modified.borrow(&world).for_each(|_| {});
+
+    Query::new(health().as_mut())
+        .with_filter(network_id().eq_indexed(0))
+        .borrow(&world)
+        .for_each(|v| *v = -1.0);
+
+    assert_eq!(
+        modified
+            .collect_vec(&world)
+            .into_iter()
+            .sorted()
+            .collect_vec(),
+        [ids[0], ids[12], ids[24]]
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_indexed() {
+    component! {
+        player: String => [ metadata::HashIndexed ],
+    }
+
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set(player(), "Alice".into())
+        .spawn(&mut world);
+    let b = Entity::builder()
+        .set(player(), "Bob".into())
+        .spawn(&mut world);
+
+    assert_eq!(world.lookup(player(), &"Alice".into()), Ok(vec![a]));
+
+    world.set(b, player(), "Alice".into()).unwrap();
+    world.despawn(a).unwrap();
+
+    assert_eq!(world.lookup(player(), &"Alice".into()), Ok(vec![b]));
+    assert_eq!(world.lookup(player(), &"Bob".into()), Ok(vec![]));
+}
+
+#[test]
+fn find_by_name() {
+    let mut world = World::new();
+
+    let root = Entity::builder()
+        .set(name(), "Root".into())
+        .spawn(&mut world);
+    let arm = Entity::builder()
+        .set(name(), "Arm".into())
+        .set(child_of(root), ())
+        .spawn(&mut world);
+    let hand = Entity::builder()
+        .set(name(), "Hand".into())
+        .set(child_of(arm), ())
+        .spawn(&mut world);
+
+    // Names are not indexed as a regular component
+    assert!(world.lookup(name(), &"Root".into()).is_err());
+
+    // Components are named, but not found
+    assert_eq!(world.find_by_name("health"), None);
+
+    assert_eq!(world.find_by_name("Root"), Some(root));
+    assert_eq!(world.find_by_name("Hand"), Some(hand));
+    assert_eq!(world.find_by_path("Root/Arm/Hand"), Some(hand));
+    assert_eq!(world.find_by_path("/Root/Arm/"), Some(arm));
+    assert_eq!(world.find_by_path("Arm/Hand"), None);
+    assert_eq!(world.find_by_path("Root/Hand"), None);
+    assert_eq!(world.find_by_path(""), None);
+
+    // Renaming
+    world.set(arm, name(), "LeftArm".into()).unwrap();
+    assert_eq!(world.find_by_name("Arm"), None);
+    assert_eq!(world.find_by_path("Root/Arm/Hand"), None);
+    assert_eq!(world.find_by_path("Root/LeftArm/Hand"), Some(hand));
+
+    *world.get_mut(root, name()).unwrap() = "Body".into();
+    assert_eq!(world.find_by_path("Body/LeftArm/Hand"), Some(hand));
+
+    // Re-parenting
+    let other = Entity::builder()
+        .set(name(), "Other".into())
+        .spawn(&mut world);
+
+    world.remove(hand, child_of(arm)).unwrap();
+    world.set(hand, child_of(other), ()).unwrap();
+    assert_eq!(world.find_by_path("Body/LeftArm/Hand"), None);
+    assert_eq!(world.find_by_path("Other/Hand"), Some(hand));
+
+    // Detached entities become roots
+    world.remove(hand, child_of(other)).unwrap();
+    assert_eq!(world.find_by_path("Hand"), Some(hand));
+
+    world.despawn(hand).unwrap();
+    assert_eq!(world.find_by_name("Hand"), None);
+}
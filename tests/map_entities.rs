@@ -0,0 +1,236 @@
+use flax::{
+    components::{child_of, name},
+    *,
+};
+use pretty_assertions::assert_eq;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Inventory {
+    items: Vec<Entity>,
+    equipped: Option<Entity>,
+}
+
+impl MapEntities for Inventory {
+    fn map_entities(&mut self, map: &dyn Fn(Entity) -> Entity) {
+        self.items.map_entities(map);
+        self.equipped.map_entities(map);
+    }
+}
+
+component! {
+    target: Entity => [ Cloneable, EntityMappable ],
+    inventory: Inventory => [ Cloneable, EntityMappable ],
+    static_target: Entity => [ EntityMappable ],
+}
+
+#[test]
+fn merge_with() {
+    let mut world = World::new();
+    let mut other = World::new();
+
+    // Occupy the ids of `other`
+    for _ in 0..4 {
+        world.spawn();
+    }
+
+    let enemy = Entity::builder()
+        .set(name(), "enemy".into())
+        .spawn(&mut other);
+
+    let sword = Entity::builder()
+        .set(name(), "sword".into())
+        .spawn(&mut other);
+
+    let shield = Entity::builder()
+        .set(name(), "shield".into())
+        .spawn(&mut other);
+
+    let outside = world.spawn();
+
+    let player = Entity::builder()
+        .set(target(), enemy)
+        .set(
+            inventory(),
+            Inventory {
+                items: vec![sword, shield],
+                equipped: Some(outside),
+            },
+        )
+        .spawn(&mut other);
+
+    other
+        .set(static_target().id(), static_target(), player)
+        .unwrap();
+
+    let migrated = world.merge_with(&mut other);
+
+    let player = migrated.get(player);
+    let enemy = world.find_by_name("enemy").unwrap();
+    let sword = world.find_by_name("sword").unwrap();
+    let shield = world.find_by_name("shield").unwrap();
+
+    assert_eq!(world.get_copy(player, target()), Ok(enemy));
+    assert_eq!(
+        *world.get(player, inventory()).unwrap(),
+        Inventory {
+            items: vec![sword, shield],
+            // Ids which were not migrated are kept
+            equipped: Some(outside),
+        }
+    );
+
+    assert_eq!(
+        world.get_copy(static_target().id(), static_target()),
+        Ok(player)
+    );
+}
+
+#[test]
+fn instantiate() {
+    let mut world = World::new();
+
+    let prefab = Entity::builder()
+        .tag(components::prefab())
+        .spawn(&mut world);
+
+    let weapon = Entity::builder()
+        .set(child_of(prefab), ())
+        .spawn(&mut world);
+
+    world
+        .set(
+            prefab,
+            inventory(),
+            Inventory {
+                items: vec![weapon],
+                equipped: Some(weapon),
+            },
+        )
+        .unwrap();
+
+    let outside = world.spawn();
+    world.set(weapon, target(), outside).unwrap();
+
+    let instance = world.instantiate(prefab).unwrap();
+    let instance_weapon = Query::new(entity_ids())
+        .with(child_of(instance))
+        .borrow(&world)
+        .iter()
+        .next()
+        .unwrap();
+
+    assert_ne!(instance_weapon, weapon);
+    assert_eq!(
+        *world.get(instance, inventory()).unwrap(),
+        Inventory {
+            items: vec![instance_weapon],
+            equipped: Some(instance_weapon),
+        }
+    );
+
+    assert_eq!(world.get_copy(instance_weapon, target()), Ok(outside));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn deserialize_into() {
+    use flax::serialize::{SerializationContextBuilder, SerializeFormat};
+    use serde::de::DeserializeSeed;
+
+    let mut scene = World::new();
+
+    let enemy = Entity::builder()
+        .set(name(), "enemy".into())
+        .spawn(&mut scene);
+
+    let player = Entity::builder()
+        .set(name(), "player".into())
+        .set(target(), enemy)
+        .spawn(&mut scene);
+
+    let context = SerializationContextBuilder::new()
+        .with(name())
+        .with(target())
+        .build();
+
+    let json =
+        serde_json::to_string(&context.serialize_world(&scene, SerializeFormat::RowMajor)).unwrap();
+
+    let mut world = World::new();
+    for _ in 0..4 {
+        world.spawn();
+    }
+
+    let migrated = context
+        .deserialize_into(&mut world)
+        .deserialize(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+
+    let player = migrated.get(player);
+    assert_eq!(world.find_by_name("player"), Some(player));
+    assert_eq!(
+        world.get_copy(player, target()),
+        Ok(world.find_by_name("enemy").unwrap())
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn replicate() {
+    use flax::serialize::{
+        replication::{ReplicationClient, ReplicationServer},
+        DeserializeBuilder,
+    };
+    use serde::de::DeserializeSeed;
+
+    component! {
+        replicated_target: Entity => [ Replicated, EntityMappable ],
+    }
+
+    let mut world = World::new();
+    let mut server = ReplicationServer::new(&mut world);
+    let client_id = server.connect();
+
+    let mut client = ReplicationClient::new(
+        DeserializeBuilder::new()
+            .with(name())
+            .with(replicated_target())
+            .build(),
+    );
+
+    // Occupy the ids of the server
+    let mut local = World::new();
+    for _ in 0..4 {
+        local.spawn();
+    }
+
+    let enemy = world.spawn();
+    let player = Entity::builder()
+        .set(replicated_target(), enemy)
+        .spawn(&mut world);
+
+    world.set(enemy, replicated_target(), player).unwrap();
+
+    server.interest_mut(client_id).extend([enemy, player]);
+
+    let packet = serde_json::to_string(&server.packet(&mut world, client_id)).unwrap();
+    client
+        .apply(&mut local)
+        .deserialize(&mut serde_json::Deserializer::from_str(&packet))
+        .unwrap();
+
+    let local_enemy = client.ids().get(enemy);
+    let local_player = client.ids().get(player);
+    assert_ne!(local_enemy, enemy);
+    assert_ne!(local_player, player);
+
+    assert_eq!(
+        local.get_copy(local_player, replicated_target()),
+        Ok(local_enemy)
+    );
+    assert_eq!(
+        local.get_copy(local_enemy, replicated_target()),
+        Ok(local_player)
+    );
+}
@@ -120,6 +120,31 @@ fn merge_empty() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+/// Merge into a world which has never been used, so none of its static entities exist yet
+fn merge_fresh() {
+    component! {
+        static_value: u32,
+    }
+
+    let mut src_world = World::new();
+    src_world
+        .set(static_value().id(), static_value(), 5)
+        .unwrap();
+
+    let id = Entity::builder()
+        .set(name(), "a".into())
+        .set(position(), Vec3::ONE)
+        .spawn(&mut src_world);
+
+    let mut world = World::new();
+    let migrated = world.merge_with(&mut src_world);
+
+    assert_eq!(world.get_copy(static_value().id(), static_value()), Ok(5));
+    assert_eq!(world.get_copy(migrated.get(id), position()), Ok(Vec3::ONE));
+    assert_eq!(world.find_by_name("a"), Some(migrated.get(id)));
+}
+
 #[test]
 fn merge_hierarchy() -> anyhow::Result<()> {
     let mut src_world = World::new();
@@ -0,0 +1,294 @@
+#[cfg(feature = "serde")]
+mod migration {
+    use bincode::Options;
+    use flax::{
+        components::name,
+        serialize::{
+            DeserializeBuilder, SerializationContextBuilder, SerializeBuilder, SerializeContext,
+            SerializeFormat, Value,
+        },
+        *,
+    };
+    use pretty_assertions::assert_eq;
+    use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    component! {
+        // Version 1
+        old_hp: f32,
+        old_position: (f32, f32),
+        old_legacy: u32,
+        old_mod_data: String,
+
+        // Version 2
+        health: Health,
+        position: Position,
+    }
+
+    /// Creates a world as it was serialized by an older build
+    fn old_world(mod_data: bool) -> (World, SerializeContext) {
+        let mut world = World::new();
+
+        let mut builder = Entity::builder();
+        builder
+            .set(name(), "player".into())
+            .set(old_hp(), 50.0)
+            .set(old_position(), (1.0, 2.0))
+            .set(old_legacy(), 5);
+
+        if mod_data {
+            builder.set(old_mod_data(), "modded".into());
+        }
+
+        builder.spawn(&mut world);
+
+        Entity::builder()
+            .set(name(), "legacy".into())
+            .set(old_legacy(), 7)
+            .spawn(&mut world);
+
+        let mut ser = SerializeBuilder::new();
+        ser.with_version(1)
+            .with(name())
+            .with_name("hp", old_hp())
+            .with_name("position", old_position())
+            .with_name("legacy", old_legacy())
+            .with_name("mod_data", old_mod_data());
+
+        (world, ser.build())
+    }
+
+    fn new_context() -> DeserializeBuilder {
+        let mut de = DeserializeBuilder::new();
+        de.with_version(2)
+            .with(name())
+            .with(health())
+            .with(position())
+            .with_rename("hp", 2, "health")
+            .with_migration("health", 2, health(), |hp: f32| Health {
+                current: hp,
+                max: 100.0,
+            })
+            .with_migration("position", 2, position(), |(x, y): (f32, f32)| Position {
+                x,
+                y,
+            })
+            .with_removed::<u32>("legacy", 2);
+
+        de
+    }
+
+    fn check(world: &World) {
+        let player = world.find_by_name("player").unwrap();
+
+        assert_eq!(
+            world.get_copy(player, health()),
+            Ok(Health {
+                current: 50.0,
+                max: 100.0
+            })
+        );
+
+        assert_eq!(
+            world.get_copy(player, position()),
+            Ok(Position { x: 1.0, y: 2.0 })
+        );
+
+        // The removed component is skipped
+        let legacy = world.find_by_name("legacy").unwrap();
+        assert!(world.has(legacy, name()));
+    }
+
+    #[test]
+    fn migrate_row() {
+        let (world, ser) = old_world(false);
+        let json = serde_json::to_string(&ser.serialize_world(
+            &world,
+            SerializeFormat::RowMajor,
+            filter::All,
+        ))
+        .unwrap();
+
+        let world = new_context()
+            .build()
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        check(&world);
+    }
+
+    #[test]
+    fn migrate_column() {
+        let (world, ser) = old_world(false);
+        let bytes = bincode::serialize(&ser.serialize_world(
+            &world,
+            SerializeFormat::ColumnMajor,
+            filter::All,
+        ))
+        .unwrap();
+
+        let world = new_context()
+            .build()
+            .deserialize_world()
+            .deserialize(&mut bincode::Deserializer::from_slice(
+                &bytes,
+                bincode::DefaultOptions::new()
+                    .with_fixint_encoding()
+                    .allow_trailing_bytes(),
+            ))
+            .unwrap();
+
+        check(&world);
+    }
+
+    #[test]
+    fn migrate_value() {
+        let (world, ser) = old_world(true);
+        let json = serde_json::to_string(&ser.serialize_world(
+            &world,
+            SerializeFormat::RowMajor,
+            filter::All,
+        ))
+        .unwrap();
+
+        // Unknown components fail by default
+        assert!(new_context()
+            .build()
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .is_err());
+
+        let world = new_context()
+            .with_migration("position", 2, position(), |value: Value| {
+                let Value::Seq(values) = value else {
+                    panic!("Expected a tuple");
+                };
+
+                let [x, y] = <[f32; 2]>::deserialize(Value::Seq(values)).unwrap();
+                Position { x, y }
+            })
+            .ignore_unknown()
+            .build()
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        check(&world);
+    }
+
+    #[test]
+    fn version_order() {
+        let (world, ser) = old_world(false);
+        let json = serde_json::to_string(&ser.serialize_world(
+            &world,
+            SerializeFormat::RowMajor,
+            filter::All,
+        ))
+        .unwrap();
+
+        let context = new_context().build();
+        let deserialize = |json: &str| {
+            context
+                .deserialize_world()
+                .deserialize(&mut serde_json::Deserializer::from_str(json))
+        };
+
+        assert!(json.starts_with(r#"{"row":{"version":1,"entities""#));
+        check(&deserialize(&json).unwrap());
+
+        // `serde_json::Value` sorts the fields, placing the version after the entities
+        let reordered =
+            serde_json::to_string(&serde_json::from_str::<serde_json::Value>(&json).unwrap())
+                .unwrap();
+        assert!(reordered.ends_with(r#""version":1}}"#));
+
+        let err = deserialize(&reordered).unwrap_err();
+        assert!(err.to_string().contains("must precede"), "{err}");
+
+        let duplicated = json.replacen(r#""version":1,"#, r#""version":1,"version":1,"#, 1);
+        let err = deserialize(&duplicated).unwrap_err();
+        assert!(err.to_string().contains("duplicate field"), "{err}");
+    }
+
+    #[test]
+    fn rename_cycle() {
+        let (world, ser) = old_world(false);
+        let json = serde_json::to_string(&ser.serialize_world(
+            &world,
+            SerializeFormat::RowMajor,
+            filter::All,
+        ))
+        .unwrap();
+
+        let err = DeserializeBuilder::new()
+            .with_version(2)
+            .with(name())
+            .with_rename("hp", 2, "health")
+            .with_rename("health", 2, "hp")
+            .build()
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Cyclic rename"), "{err}");
+    }
+
+    #[test]
+    fn current_version() {
+        let mut world = World::new();
+
+        let player = Entity::builder()
+            .set(name(), "player".into())
+            .set(
+                health(),
+                Health {
+                    current: 10.0,
+                    max: 20.0,
+                },
+            )
+            .spawn(&mut world);
+
+        let mut builder = SerializationContextBuilder::new();
+        builder.with_version(2).with(name()).with(health());
+        builder
+            .deserializer_mut()
+            .with_migration("health", 2, health(), |hp: f32| Health {
+                current: hp,
+                max: 100.0,
+            });
+
+        let context = builder.build();
+        assert_eq!(context.deserializer().version(), Some(2));
+
+        for format in [SerializeFormat::RowMajor, SerializeFormat::ColumnMajor] {
+            let json = serde_json::to_string(&context.serialize_world(&world, format)).unwrap();
+            assert!(json.contains("\"version\":2"));
+
+            let world = context
+                .deserialize_world()
+                .deserialize(&mut serde_json::Deserializer::from_str(&json))
+                .unwrap();
+
+            // Migrations do not apply to the current version
+            assert_eq!(
+                world.get_copy(player, health()),
+                Ok(Health {
+                    current: 10.0,
+                    max: 20.0
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use flax::{
+    components::{child_of, name, prefab},
+    *,
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+component! {
+    health: f32 => [ Cloneable ],
+    weapon_of(owner): () => [ Cloneable ],
+    targets(target): () => [ Cloneable ],
+    handle: std::sync::Arc<()>,
+}
+
+#[test]
+fn instantiate() {
+    let mut world = World::new();
+
+    let player = Entity::builder()
+        .set(name(), "Player".into())
+        .spawn(&mut world);
+
+    let enemy = Entity::builder()
+        .set(name(), "Enemy".into())
+        .set(health(), 100.0)
+        .set(targets(player), ())
+        .tag(prefab())
+        .spawn(&mut world);
+
+    let sword = Entity::builder()
+        .set(name(), "Sword".into())
+        .set(child_of(enemy), ())
+        .set(weapon_of(enemy), ())
+        .tag(prefab())
+        .spawn(&mut world);
+
+    let ids = (0..3)
+        .map(|_| world.instantiate(enemy).unwrap())
+        .collect_vec();
+
+    for &id in &ids {
+        let entity = world.entity(id).unwrap();
+        assert_ne!(id, enemy);
+        assert_eq!(entity.get(name()).as_deref(), Ok(&"Enemy".into()));
+        assert_eq!(entity.get_copy(health()), Ok(100.0));
+        assert!(!entity.has(prefab()));
+
+        // Relations outside the subtree are kept
+        assert!(entity.has(targets(player)));
+
+        let children = Query::new(entity_ids())
+            .with_filter(child_of(id).with())
+            .collect_vec(&world);
+
+        assert_eq!(children.len(), 1);
+
+        // Relations within the subtree are remapped
+        let child = world.entity(children[0]).unwrap();
+        assert_ne!(child.id(), sword);
+        assert_eq!(child.get(name()).as_deref(), Ok(&"Sword".into()));
+        assert!(child.has(weapon_of(id)));
+        assert!(!child.has(weapon_of(enemy)));
+    }
+
+    // The prefab is unchanged
+    assert_eq!(
+        Query::new(entity_ids())
+            .with_prefabs()
+            .with_filter(child_of(enemy).with())
+            .collect_vec(&world),
+        [sword]
+    );
+
+    // Prefabs are not visited by queries
+    assert_eq!(
+        Query::new(entity_ids())
+            .with_filter(health().with())
+            .collect_vec(&world)
+            .into_iter()
+            .sorted()
+            .collect_vec(),
+        ids
+    );
+
+    assert_eq!(
+        Query::new(name())
+            .borrow(&world)
+            .iter()
+            .filter(|v| *v == "Sword")
+            .count(),
+        3
+    );
+
+    // Including components does not include prefabs
+    assert_eq!(
+        Query::new(entity_ids())
+            .with_components()
+            .with_filter(health().with())
+            .borrow(&world)
+            .iter()
+            .count(),
+        3
+    );
+
+    assert_eq!(
+        Query::new(entity_ids())
+            .with_prefabs()
+            .with_filter(health().with())
+            .borrow(&world)
+            .iter()
+            .count(),
+        4
+    );
+
+    world.despawn_recursive(ids[0], child_of).unwrap();
+    assert_eq!(world.instantiate(enemy).map(|_| ()), Ok(()));
+}
+
+#[test]
+fn instantiate_not_cloneable() {
+    let mut world = World::new();
+
+    let prefab = Entity::builder()
+        .set(health(), 100.0)
+        .tag(prefab())
+        .attach(
+            child_of,
+            Entity::builder().set(handle(), Default::default()),
+        )
+        .spawn(&mut world);
+
+    let count = Query::new(entity_ids())
+        .with_prefabs()
+        .borrow(&world)
+        .iter()
+        .count();
+
+    assert_eq!(
+        world.instantiate(prefab),
+        Err(Error::NotCloneable(handle().desc()))
+    );
+
+    // Nothing is spawned
+    assert_eq!(
+        Query::new(entity_ids())
+            .with_prefabs()
+            .borrow(&world)
+            .iter()
+            .count(),
+        count
+    );
+}
+
+#[test]
+fn instantiate_cycle() {
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set(name(), "a".into())
+        .tag(prefab())
+        .spawn(&mut world);
+
+    let b = Entity::builder()
+        .set(name(), "b".into())
+        .set(child_of(a), ())
+        .tag(prefab())
+        .spawn(&mut world);
+
+    world.set(a, child_of(b), ()).unwrap();
+
+    let instance = world.instantiate(a).unwrap();
+
+    let children = Query::new(entity_ids())
+        .with_filter(child_of(instance).with())
+        .collect_vec(&world);
+
+    assert_eq!(children.len(), 1);
+    assert_eq!(world.get(children[0], name()).as_deref(), Ok(&"b".into()));
+    assert!(world.has(instance, child_of(children[0])));
+}
+
+#[test]
+fn attached_children() {
+    let mut world = World::new();
+
+    let enemy = Entity::builder()
+        .set(name(), "Enemy".into())
+        .tag(prefab())
+        .attach(
+            child_of,
+            Entity::builder()
+                .set(name(), "Arm".into())
+                .attach(child_of, Entity::builder().set(name(), "Hand".into())),
+        )
+        .spawn(&mut world);
+
+    // The whole subtree of the prefab is hidden
+    assert_eq!(Query::new(name()).borrow(&world).iter().count(), 0);
+    assert_eq!(
+        Query::new(name())
+            .with_prefabs()
+            .borrow(&world)
+            .iter()
+            .count(),
+        3
+    );
+
+    let instance = world.instantiate(enemy).unwrap();
+
+    let mut names = Query::new(name())
+        .borrow(&world)
+        .iter()
+        .cloned()
+        .collect_vec();
+    names.sort();
+
+    assert_eq!(names, ["Arm", "Enemy", "Hand"]);
+    assert!(!world.has(instance, prefab()));
+
+    // Children attached to instances are not hidden
+    Entity::builder()
+        .attach(child_of, Entity::builder().set(name(), "Weapon".into()))
+        .append_to(&mut world, instance)
+        .unwrap();
+
+    assert_eq!(Query::new(name()).borrow(&world).iter().count(), 4);
+}
@@ -135,3 +135,214 @@ fn query_opt() {
         ]
     );
 }
+
+#[test]
+fn query_sorted() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    component! {
+        depth: i32,
+        visible: (),
+        opacity: f32,
+    }
+
+    let mut world = World::new();
+
+    let ids = [("a", 3), ("b", 1), ("c", 2), ("d", 1)]
+        .into_iter()
+        .map(|(n, v)| {
+            EntityBuilder::new()
+                .set(name(), n.into())
+                .set(depth(), v)
+                .set(opacity(), 1.0)
+                .spawn(&mut world)
+        })
+        .collect_vec();
+
+    let comparisons = Arc::new(AtomicUsize::new(0));
+    let mut query = Query::new(name().cloned()).sort_by(depth(), {
+        let comparisons = comparisons.clone();
+        move |a, b| {
+            comparisons.fetch_add(1, Ordering::Relaxed);
+            a.cmp(b)
+        }
+    });
+
+    // Equal keys are ordered by entity
+    assert_eq!(
+        query.borrow(&world).iter().collect_vec(),
+        ["b", "d", "c", "a"]
+    );
+
+    // Unrelated changes do not cause a re-sort
+    let sorts = comparisons.load(Ordering::Relaxed);
+    Query::new(opacity().as_mut())
+        .borrow(&world)
+        .for_each(|v| *v = 0.5);
+    world.set(ids[0], visible(), ()).unwrap();
+    assert_eq!(
+        query.borrow(&world).iter().collect_vec(),
+        ["b", "d", "c", "a"]
+    );
+    assert_eq!(comparisons.load(Ordering::Relaxed), sorts);
+
+    world.set(ids[0], depth(), 0).unwrap();
+    assert_eq!(
+        query.borrow(&world).iter().collect_vec(),
+        ["a", "b", "d", "c"]
+    );
+
+    *world.get_mut(ids[2], depth()).unwrap() = -1;
+    assert_eq!(
+        query.borrow(&world).iter().collect_vec(),
+        ["c", "a", "b", "d"]
+    );
+
+    world.despawn(ids[1]).unwrap();
+    let e = EntityBuilder::new()
+        .set(name(), "e".into())
+        .set(depth(), 0)
+        .spawn(&mut world);
+    assert_eq!(
+        query.borrow(&world).iter().collect_vec(),
+        ["c", "a", "e", "d"]
+    );
+
+    world.remove(e, depth()).unwrap();
+    assert_eq!(query.borrow(&world).iter().collect_vec(), ["c", "a", "d"]);
+
+    // Mutating the sort key through the query itself
+    let mut query = Query::new(depth().as_mut()).sort_by_key(depth());
+    for v in &mut query.borrow(&world) {
+        *v = -*v;
+    }
+
+    let mut query = query.with_filter(visible().with());
+    assert_eq!(query.borrow(&world).iter().collect_vec(), [&mut 0]);
+
+    let mut query = Query::new(name().cloned()).sort_by_key(depth());
+    assert_eq!(query.borrow(&world).iter().collect_vec(), ["d", "a", "c"]);
+}
+
+#[test]
+fn query_cached() {
+    component! {
+        health: f32,
+        armor: f32,
+        poisoned: (),
+    }
+
+    let mut world = World::new();
+
+    let ids = (0..16)
+        .map(|i| {
+            EntityBuilder::new()
+                .set(health(), i as f32)
+                .set(armor(), 0.0)
+                .spawn(&mut world)
+        })
+        .collect_vec();
+
+    let mut query = Query::new(flax::entity_ids())
+        .with_filter(health().le(1.0))
+        .cached(&mut world);
+
+    let mut matches = || query.borrow(&world).iter().sorted().collect_vec();
+    assert_eq!(matches(), [ids[0], ids[1]]);
+
+    // Modified through another query
+    Query::new((flax::entity_ids(), health().as_mut()))
+        .borrow(&world)
+        .for_each(|(id, v)| {
+            if id == ids[7] {
+                *v = -5.0
+            }
+        });
+
+    // Unrelated components do not affect the filter
+    Query::new(armor().as_mut())
+        .borrow(&world)
+        .for_each(|v| *v += 1.0);
+
+    let mut matches = |world: &World| query.borrow(world).iter().sorted().collect_vec();
+    assert_eq!(matches(&world), [ids[0], ids[1], ids[7]]);
+
+    // Moving to another archetype swaps the last entity into the slot
+    world.set(ids[1], poisoned(), ()).unwrap();
+    world.set(ids[15], health(), 0.0).unwrap();
+    world.remove(ids[0], armor()).unwrap();
+    assert_eq!(matches(&world), [ids[0], ids[1], ids[7], ids[15]]);
+
+    world.despawn(ids[7]).unwrap();
+    world.remove(ids[1], health()).unwrap();
+    *world.get_mut(ids[0], health()).unwrap() = 10.0;
+    let id = EntityBuilder::new().set(health(), -1.0).spawn(&mut world);
+    assert_eq!(
+        matches(&world),
+        [ids[15], id].into_iter().sorted().collect_vec()
+    );
+
+    // Mutable queries
+    let mut query = Query::new(armor().as_mut())
+        .with_filter(health().gt(10.0))
+        .cached(&mut world);
+
+    assert_eq!(query.borrow(&world).iter().count(), 4);
+    query.borrow(&world).iter().for_each(|v| *v = 0.0);
+    world.set(ids[15], health(), 20.0).unwrap();
+    let armors = query.borrow(&world).iter().map(|v| *v).collect_vec();
+    assert_eq!(armors.len(), 5);
+    assert_eq!(armors.iter().sum::<f32>(), 1.0);
+}
+
+#[test]
+fn query_cached_archetype_filters() {
+    component! {
+        health: f32,
+        armor: f32,
+        poisoned: (),
+    }
+
+    let mut world = World::new();
+
+    let a = EntityBuilder::new()
+        .set(health(), 0.0)
+        .set(poisoned(), ())
+        .spawn(&mut world);
+    let b = EntityBuilder::new()
+        .set(health(), 0.0)
+        .set(armor(), 0.0)
+        .set(poisoned(), ())
+        .spawn(&mut world);
+    let c = EntityBuilder::new().set(health(), 0.0).spawn(&mut world);
+    let d = EntityBuilder::new().set(armor(), 0.0).spawn(&mut world);
+
+    let mut query = Query::new(flax::entity_ids())
+        .with_filter(health().le(1.0) & poisoned().without())
+        .cached(&mut world);
+
+    let mut matches = |world: &World| query.borrow(world).iter().sorted().collect_vec();
+    assert_eq!(matches(&world), [c]);
+
+    // Entities move to existing archetypes
+    world.remove(a, poisoned()).unwrap();
+    world.set(c, poisoned(), ()).unwrap();
+    world.set(d, health(), -1.0).unwrap();
+    assert_eq!(matches(&world), [a, d]);
+
+    world.remove(b, poisoned()).unwrap();
+    world.remove(d, armor()).unwrap();
+    assert_eq!(matches(&world), [a, b, d]);
+}
+
+#[test]
+#[should_panic(expected = "different world")]
+fn query_cached_other_world() {
+    let mut world = World::new();
+    let mut query = Query::new(flax::entity_ids()).cached(&mut world);
+
+    query.borrow(&World::new());
+}
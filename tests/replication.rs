@@ -0,0 +1,277 @@
+#[cfg(feature = "serde")]
+mod replication {
+    use std::sync::mpsc;
+
+    use flax::{
+        serialize::{
+            replication::{ClientId, ReplicationClient, ReplicationServer},
+            DeserializeBuilder,
+        },
+        *,
+    };
+    use pretty_assertions::assert_eq;
+    use serde::de::DeserializeSeed;
+
+    component! {
+        position: (f32, f32) => [ Replicated ],
+        health: f32 => [ Replicated ],
+        attached_to(id): () => [ Replicated ],
+        secret: u32,
+    }
+
+    struct Connection {
+        id: ClientId,
+        client: ReplicationClient,
+        world: World,
+        packets: mpsc::Receiver<Vec<u8>>,
+        acks: mpsc::Sender<u32>,
+    }
+
+    impl Connection {
+        /// Applies all received packets
+        fn receive(&mut self) {
+            for bytes in self.packets.try_iter() {
+                let tick = self
+                    .client
+                    .apply(&mut self.world)
+                    .deserialize(&mut bincode::Deserializer::from_slice(
+                        &bytes,
+                        bincode::DefaultOptions::new(),
+                    ))
+                    .unwrap();
+
+                self.acks.send(tick).unwrap();
+            }
+        }
+    }
+
+    struct Server {
+        world: World,
+        server: ReplicationServer,
+        connections: Vec<(ClientId, mpsc::Sender<Vec<u8>>, mpsc::Receiver<u32>)>,
+    }
+
+    impl Server {
+        fn new() -> Self {
+            let mut world = World::new();
+            let server = ReplicationServer::new(&mut world);
+            Self {
+                world,
+                server,
+                connections: Vec::new(),
+            }
+        }
+
+        fn connect(&mut self) -> Connection {
+            let id = self.server.connect();
+            let (packets_tx, packets) = mpsc::channel();
+            let (acks, acks_rx) = mpsc::channel();
+            self.connections.push((id, packets_tx, acks_rx));
+
+            let context = DeserializeBuilder::new()
+                .with(position())
+                .with(health())
+                .with_relation(attached_to)
+                .build();
+
+            Connection {
+                id,
+                client: ReplicationClient::new(context),
+                world: World::new(),
+                packets,
+                acks,
+            }
+        }
+
+        /// Sends a packet to each client, and processes the acknowledgements
+        fn send(&mut self) {
+            for (id, packets, acks) in &self.connections {
+                for tick in acks.try_iter() {
+                    self.server.acknowledge(*id, tick);
+                }
+
+                let packet = self.server.packet(&mut self.world, *id);
+                packets
+                    .send(
+                        bincode::Options::serialize(bincode::DefaultOptions::new(), &packet)
+                            .unwrap(),
+                    )
+                    .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn replicate() {
+        let mut server = Server::new();
+        let mut conn = server.connect();
+
+        let a = Entity::builder()
+            .set(position(), (0.0, 0.0))
+            .set(health(), 100.0)
+            .set(secret(), 5)
+            .spawn(&mut server.world);
+
+        let b = Entity::builder()
+            .set(position(), (1.0, 0.0))
+            .set(attached_to(a), ())
+            .spawn(&mut server.world);
+
+        server.server.interest_mut(conn.id).extend([a, b]);
+
+        server.send();
+        conn.receive();
+
+        let local_a = conn.client.ids().get(a);
+        let local_b = conn.client.ids().get(b);
+
+        assert_eq!(conn.world.get_copy(local_a, position()), Ok((0.0, 0.0)));
+        assert_eq!(conn.world.get_copy(local_a, health()), Ok(100.0));
+        assert!(!conn.world.has(local_a, secret()));
+        assert!(conn.world.has(local_b, attached_to(local_a)));
+
+        // Only the changed columns are sent once acknowledged
+        server.send();
+        *server.world.get_mut(a, health()).unwrap() = 50.0;
+        server.send();
+
+        conn.receive();
+        assert_eq!(conn.world.get_copy(local_a, health()), Ok(50.0));
+
+        server.world.remove(a, position()).unwrap();
+        server.world.despawn(b).unwrap();
+
+        server.send();
+        conn.receive();
+
+        assert!(!conn.world.has(local_a, position()));
+        assert!(!conn.world.is_alive(local_b));
+    }
+
+    #[test]
+    fn relation_interest() {
+        let mut server = Server::new();
+        let mut conn = server.connect();
+
+        let a = Entity::builder()
+            .set(position(), (0.0, 0.0))
+            .spawn(&mut server.world);
+
+        let b = Entity::builder()
+            .set(position(), (1.0, 0.0))
+            .set(attached_to(a), ())
+            .spawn(&mut server.world);
+
+        server.server.interest_mut(conn.id).insert(b);
+
+        server.send();
+        conn.receive();
+
+        // The target is not spawned on the client
+        let local_b = conn.client.ids().get(b);
+        assert_eq!(Query::new(()).borrow(&conn.world).count(), 1);
+        assert_eq!(conn.world.get_copy(local_b, position()), Ok((1.0, 0.0)));
+
+        // The relation is sent once the target enters the interest set
+        server.server.interest_mut(conn.id).insert(a);
+
+        server.send();
+        conn.receive();
+
+        let local_a = conn.client.ids().get(a);
+        assert!(conn.world.has(local_b, attached_to(local_a)));
+
+        server.server.interest_mut(conn.id).remove(&a);
+
+        server.send();
+        conn.receive();
+
+        assert!(!conn.world.is_alive(local_a));
+        assert_eq!(Query::new(()).borrow(&conn.world).count(), 1);
+    }
+
+    #[test]
+    fn shared_deltas() {
+        let mut server = Server::new();
+        let mut conn = server.connect();
+
+        server.world.track_deltas();
+
+        let a = Entity::builder()
+            .set(position(), (0.0, 0.0))
+            .set(health(), 100.0)
+            .spawn(&mut server.world);
+
+        server.server.interest_mut(conn.id).insert(a);
+        server.send();
+        conn.receive();
+
+        let baseline = server.world.diff(0).tick();
+        server.world.remove(a, health()).unwrap();
+
+        // Acknowledged by all clients
+        for _ in 0..3 {
+            server.send();
+            conn.receive();
+        }
+
+        assert!(!conn.world.has(conn.client.ids().get(a), health()));
+
+        // The removal is still available to other consumers of the world deltas
+        let delta = server.world.diff(baseline);
+        assert_eq!(delta.get(a).unwrap().removed(), [health().desc()]);
+    }
+
+    #[test]
+    fn interest() {
+        let mut server = Server::new();
+        let mut conn1 = server.connect();
+        let mut conn2 = server.connect();
+
+        let a = Entity::builder()
+            .set(position(), (0.0, 0.0))
+            .spawn(&mut server.world);
+
+        let b = Entity::builder()
+            .set(position(), (5.0, 0.0))
+            .spawn(&mut server.world);
+
+        server.server.interest_mut(conn1.id).insert(a);
+        server.server.interest_mut(conn2.id).extend([a, b]);
+
+        server.send();
+        conn1.receive();
+        conn2.receive();
+
+        assert_eq!(Query::new(position()).borrow(&conn1.world).count(), 1);
+        assert_eq!(Query::new(position()).borrow(&conn2.world).count(), 2);
+
+        // Leaving the interest set despawns the entity on the client
+        server.server.interest_mut(conn2.id).remove(&a);
+
+        server.send();
+        conn1.receive();
+        conn2.receive();
+
+        assert!(conn1.world.is_alive(conn1.client.ids().get(a)));
+        assert_eq!(
+            Query::new(position())
+                .borrow(&conn2.world)
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            [(5.0, 0.0)]
+        );
+
+        // Entering the interest set sends the entity in full
+        server.server.interest_mut(conn1.id).insert(b);
+
+        server.send();
+        conn1.receive();
+
+        assert_eq!(
+            conn1.world.get_copy(conn1.client.ids().get(b), position()),
+            Ok((5.0, 0.0))
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use flax::{buffer::ComponentBuffer, *};
+use pretty_assertions::assert_eq;
+
+struct RigidBody;
+
+impl RequiredComponents for RigidBody {
+    fn insert(buffer: &mut ComponentBuffer) {
+        buffer.set(velocity(), 0.0);
+        buffer.set(mass(), 1.0);
+    }
+}
+
+struct Mass;
+
+impl RequiredComponents for Mass {
+    fn insert(buffer: &mut ComponentBuffer) {
+        buffer.set(inertia(), 2.0);
+    }
+}
+
+component! {
+    rigid_body: () => [ Requires<RigidBody> ],
+    velocity: f32,
+    mass: f32 => [ Requires<Mass> ],
+    inertia: f32,
+}
+
+#[test]
+fn requires() {
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set_default(rigid_body())
+        .set(mass(), 5.0)
+        .spawn(&mut world);
+
+    assert_eq!(world.get_copy(a, velocity()), Ok(0.0));
+    assert_eq!(world.get_copy(a, mass()), Ok(5.0));
+    assert_eq!(world.get_copy(a, inertia()), Ok(2.0));
+
+    let b = world.spawn();
+    world.set(b, rigid_body(), ()).unwrap();
+
+    assert_eq!(world.get_copy(b, velocity()), Ok(0.0));
+    assert_eq!(world.get_copy(b, mass()), Ok(1.0));
+    assert_eq!(world.get_copy(b, inertia()), Ok(2.0));
+
+    // Existing components do not insert their requirements again
+    world.remove(b, velocity()).unwrap();
+    world.set(b, rigid_body(), ()).unwrap();
+    assert!(!world.has(b, velocity()));
+
+    let c = world.spawn();
+    let mut cmd = CommandBuffer::new();
+    cmd.set(c, rigid_body(), ());
+    cmd.apply(&mut world).unwrap();
+
+    assert_eq!(world.get_copy(c, mass()), Ok(1.0));
+    assert_eq!(world.get_copy(c, inertia()), Ok(2.0));
+}
+
+#[test]
+fn requires_batch() {
+    let mut world = World::new();
+
+    let mut batch = BatchSpawn::new(2);
+    batch.set(rigid_body(), vec![(), ()]).unwrap();
+    batch.set(velocity(), vec![1.0, 2.0]).unwrap();
+    let ids = batch.spawn(&mut world);
+
+    for (&id, v) in ids.iter().zip([1.0, 2.0]) {
+        assert_eq!(world.get_copy(id, velocity()), Ok(v));
+        assert_eq!(world.get_copy(id, mass()), Ok(1.0));
+        assert_eq!(world.get_copy(id, inertia()), Ok(2.0));
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn requires_deserialize() {
+    use flax::serialize::{SerializationContextBuilder, SerializeFormat};
+    use serde::de::DeserializeSeed;
+
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set_default(rigid_body())
+        .set(velocity(), 3.0)
+        .spawn(&mut world);
+
+    let b = Entity::builder().set(mass(), 5.0).spawn(&mut world);
+
+    // The required inertia is not saved, and inserted when deserializing
+    let saved = SerializationContextBuilder::new()
+        .with(rigid_body())
+        .with(velocity())
+        .with(mass())
+        .build();
+
+    let context = SerializationContextBuilder::new()
+        .with(rigid_body())
+        .with(velocity())
+        .with(mass())
+        .with(inertia())
+        .build();
+
+    for format in [SerializeFormat::RowMajor, SerializeFormat::ColumnMajor] {
+        let json = serde_json::to_string(&saved.serialize_world(&world, format)).unwrap();
+        assert!(!json.contains("inertia"));
+
+        let world = context
+            .deserializer()
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        assert_eq!(world.get_copy(a, velocity()), Ok(3.0));
+        assert_eq!(world.get_copy(a, mass()), Ok(1.0));
+        assert_eq!(world.get_copy(a, inertia()), Ok(2.0));
+
+        assert_eq!(world.get_copy(b, mass()), Ok(5.0));
+        assert_eq!(world.get_copy(b, inertia()), Ok(2.0));
+        assert!(!world.has(b, velocity()));
+    }
+}
@@ -0,0 +1,203 @@
+use flax::*;
+use pretty_assertions::assert_eq;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Time {
+    elapsed: f32,
+    delta: f32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Score(u32);
+
+component! {
+    velocity: f32,
+    position: f32,
+}
+
+#[test]
+fn resources() {
+    let mut world = World::new();
+
+    assert!(!world.has_resource::<Time>());
+    assert!(matches!(
+        world.resource::<Time>().as_deref(),
+        Err(Error::MissingResource(_))
+    ));
+
+    assert_eq!(world.insert_resource(Score(1)), None);
+    assert_eq!(world.insert_resource(Score(2)), Some(Score(1)));
+
+    world.resource_mut::<Score>().unwrap().0 += 1;
+    assert_eq!(world.resource::<Score>().as_deref(), Ok(&Score(3)));
+
+    assert_eq!(world.remove_resource::<Score>(), Some(Score(3)));
+    assert!(!world.has_resource::<Score>());
+}
+
+#[test]
+fn resource_systems() {
+    let mut world = World::new();
+
+    world.insert_resource(Time {
+        elapsed: 0.0,
+        delta: 0.5,
+    });
+    world.insert_resource(Score(0));
+
+    Entity::builder()
+        .set(velocity(), 2.0)
+        .set(position(), 0.0)
+        .spawn(&mut world);
+
+    let update_time = System::builder()
+        .with_name("update_time")
+        .with_resource_mut::<Time>()
+        .build(|time: &mut Time| time.elapsed += time.delta);
+
+    let integrate = System::builder()
+        .with_name("integrate")
+        .with_query(Query::new((position().as_mut(), velocity())))
+        .with_resource_ref::<Time>()
+        .build(
+            |mut query: QueryBorrow<(fetch::ComponentMut<f32>, Component<f32>)>, time: &Time| {
+                for (pos, vel) in &mut query {
+                    *pos += vel * time.delta;
+                }
+            },
+        );
+
+    let log_time = System::builder()
+        .with_name("log_time")
+        .with_resource_ref::<Time>()
+        .build(|time: &Time| assert_eq!(time.delta, 0.5));
+
+    let score = System::builder()
+        .with_name("score")
+        .with_resource_mut::<Score>()
+        .build(|score: &mut Score| score.0 += 1);
+
+    let mut schedule = Schedule::from([
+        update_time.boxed(),
+        integrate.boxed(),
+        log_time.boxed(),
+        score.boxed(),
+    ]);
+
+    assert_eq!(
+        schedule.batch_info(&world).to_names(),
+        [&["update_time", "score"][..], &["integrate", "log_time"]]
+    );
+
+    schedule.execute_seq(&mut world).unwrap();
+    schedule.execute_seq(&mut world).unwrap();
+
+    assert_eq!(world.resource::<Time>().unwrap().elapsed, 1.0);
+    assert_eq!(world.resource::<Score>().as_deref(), Ok(&Score(2)));
+    assert_eq!(
+        Query::new(position())
+            .borrow(&world)
+            .iter()
+            .copied()
+            .collect::<Vec<_>>(),
+        [2.0]
+    );
+}
+
+#[test]
+fn resource_world_access() {
+    let mut world = World::new();
+    world.insert_resource(Score(0));
+
+    let reader = System::builder()
+        .with_name("reader")
+        .with_resource_ref::<Score>()
+        .build(|score: &Score| assert_eq!(score.0 % 2, 0));
+
+    let world_mut = System::builder()
+        .with_name("world_mut")
+        .with_world_mut()
+        .build(|world: &mut World| world.resource_mut::<Score>().unwrap().0 += 2);
+
+    let mut schedule = Schedule::from([reader.boxed(), world_mut.boxed()]);
+
+    // Resources are borrowed through the world
+    assert_eq!(
+        schedule.batch_info(&world).to_names(),
+        [&["reader"][..], &["world_mut"]]
+    );
+
+    schedule.execute_seq(&mut world).unwrap();
+    assert_eq!(world.resource::<Score>().as_deref(), Ok(&Score(2)));
+
+    #[cfg(feature = "rayon")]
+    {
+        schedule.execute_par(&mut world).unwrap();
+        assert_eq!(world.resource::<Score>().as_deref(), Ok(&Score(4)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "World does not contain the resource")]
+fn missing_resource_system() {
+    let mut world = World::new();
+
+    System::builder()
+        .with_resource_ref::<Score>()
+        .build(|_: &Score| {})
+        .run(&mut world);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serialize_resources() {
+    use bincode::Options;
+    use flax::serialize::{SerializationContextBuilder, SerializeFormat};
+    use serde::de::DeserializeSeed;
+
+    let mut world = World::new();
+
+    let id = Entity::builder().set(position(), 1.0).spawn(&mut world);
+
+    world.insert_resource(Time {
+        elapsed: 2.0,
+        delta: 0.1,
+    });
+    world.insert_resource(Score(5));
+
+    let context = SerializationContextBuilder::new()
+        .with(position())
+        .with_resource::<Time>()
+        .with_resource_name::<Score>("score")
+        .build();
+
+    for format in [SerializeFormat::RowMajor, SerializeFormat::ColumnMajor] {
+        let json = serde_json::to_string(&context.serialize_world(&world, format.clone())).unwrap();
+
+        let new_world = context
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        assert_eq!(new_world.get_copy(id, position()), Ok(1.0));
+        assert_eq!(
+            new_world.resource::<Time>().as_deref(),
+            world.resource::<Time>().as_deref()
+        );
+        assert_eq!(new_world.resource::<Score>().as_deref(), Ok(&Score(5)));
+
+        let bytes = bincode::serialize(&context.serialize_world(&world, format)).unwrap();
+
+        let new_world = context
+            .deserialize_world()
+            .deserialize(&mut bincode::de::Deserializer::from_slice(
+                &bytes,
+                bincode::DefaultOptions::new().with_fixint_encoding(),
+            ))
+            .unwrap();
+
+        assert_eq!(new_world.resource::<Score>().as_deref(), Ok(&Score(5)));
+    }
+}
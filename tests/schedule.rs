@@ -488,3 +488,480 @@ fn into_anyhow(v: flax::Error) -> anyhow::Error {
     #[cfg(feature = "std")]
     return anyhow::Error::new(v);
 }
+
+#[test]
+fn schedule_conditions() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Default)]
+    struct Paused(bool);
+
+    component! {
+        health: f32,
+        regen: f32,
+    }
+
+    let mut world = World::new();
+    world.insert_resource(Paused(false));
+
+    let id = Entity::builder()
+        .set(health(), 50.0)
+        .set(regen(), 5.0)
+        .spawn(&mut world);
+
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let regen_system = System::builder()
+        .with_name("regen")
+        .with_query(Query::new((health().as_mut(), regen())))
+        .with_condition(
+            System::builder()
+                .with_name("not_paused")
+                .with_resource_ref::<Paused>()
+                .build(|paused: &Paused| !paused.0),
+        )
+        .for_each(|(health, regen)| *health += regen);
+
+    let changed_system = System::builder()
+        .with_name("changed")
+        .with_condition(
+            System::builder()
+                .with_name("health_changed")
+                .with_query(Query::new(health().modified()))
+                .build(|mut q: QueryBorrow<_>| !q.is_empty()),
+        )
+        .build({
+            let runs = runs.clone();
+            move || {
+                runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+    let pause_system = System::builder()
+        .with_name("pause")
+        .with_resource_mut::<Paused>()
+        .build(|paused: &mut Paused| paused.0 = true)
+        .boxed()
+        .run_if(move |world| world.get_copy(id, health()).unwrap() >= 60.0);
+
+    let mut schedule = Schedule::builder()
+        .with_system(regen_system)
+        .with_system(changed_system)
+        .with_system(pause_system)
+        .build();
+
+    // The condition accesses are a part of the dependencies, and the world predicate of `pause`
+    // is a barrier
+    let batches = schedule.batch_info(&world);
+    assert_eq!(
+        batches.to_names(),
+        [vec!["regen"], vec!["changed"], vec!["pause"]]
+    );
+
+    schedule.execute_seq(&mut world).unwrap();
+    assert_eq!(world.get_copy(id, health()), Ok(55.0));
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+    assert!(!world.resource::<Paused>().unwrap().0);
+
+    schedule.execute_seq(&mut world).unwrap();
+    assert_eq!(world.get_copy(id, health()), Ok(60.0));
+    assert!(world.resource::<Paused>().unwrap().0);
+
+    // Paused, so health is no longer modified
+    schedule.execute_seq(&mut world).unwrap();
+    assert_eq!(world.get_copy(id, health()), Ok(60.0));
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn schedule_ordering() {
+    #[derive(Default)]
+    struct Log(Vec<&'static str>);
+
+    fn log_system(name: &'static str) -> BoxedSystem {
+        System::builder()
+            .with_name(name)
+            .with_resource_mut::<Log>()
+            .build(move |log: &mut Log| log.0.push(name))
+            .boxed()
+    }
+
+    fn empty_system(name: &'static str) -> BoxedSystem {
+        System::builder().with_name(name).build(|| {}).boxed()
+    }
+
+    let mut world = World::new();
+    world.insert_resource(Log::default());
+
+    // Added independently, such as by different plugins
+    let mut schedule = Schedule::builder()
+        .with_system(log_system("render").with_label("render"))
+        .with_system(log_system("physics").with_label("physics").before("render"))
+        .with_system(log_system("input").before("physics"))
+        .with_system(log_system("audio"))
+        .with_system(log_system("ui").with_label("render").after("physics"))
+        .build();
+
+    schedule.execute_seq(&mut world).unwrap();
+    assert_eq!(
+        world.resource::<Log>().unwrap().0,
+        ["input", "physics", "render", "audio", "ui"]
+    );
+
+    // Ordering is honored even without conflicting accesses
+    let mut schedule = Schedule::builder()
+        .with_system(empty_system("b").after("a"))
+        .with_system(empty_system("a").with_label("a"))
+        .with_system(empty_system("c"))
+        .build();
+
+    assert_eq!(
+        schedule.batch_info(&world).to_names(),
+        [vec!["a", "c"], vec!["b"]]
+    );
+}
+
+#[test]
+fn schedule_ordering_cycle() {
+    use flax::Error;
+
+    let system = |name: &'static str| System::builder().with_name(name).build(|| {}).boxed();
+
+    let mut world = World::new();
+    let mut schedule = Schedule::builder()
+        .with_system(system("a").with_label("a").after("c"))
+        .with_system(system("b").with_label("b").after("a"))
+        .with_system(system("c").with_label("c").after("b"))
+        .with_system(system("d").after("a"))
+        .build();
+
+    assert_eq!(
+        schedule.try_batch_info(&world).unwrap_err(),
+        Error::CyclicSchedule(vec!["a".into(), "b".into(), "c".into(), "a".into()])
+    );
+
+    let err = schedule.execute_seq(&mut world).unwrap_err();
+    assert_eq!(err.to_string(), "Cyclic system ordering: a -> b -> c -> a");
+}
+
+#[test]
+fn schedule_fixed_timestep() {
+    use flax::{schedule::Timestep, SharedResource};
+    use std::time::Duration;
+
+    component! {
+        position: f32,
+    }
+
+    let mut world = World::new();
+    let id = Entity::builder().set(position(), 0.0).spawn(&mut world);
+
+    let steps = SharedResource::new(Vec::new());
+
+    let physics = System::builder()
+        .with_name("physics")
+        .with_query(Query::new(position().as_mut()))
+        .with_input::<Timestep>()
+        .with_input_mut::<u32>()
+        .with_resource(steps.clone())
+        .build(
+            |mut query: QueryBorrow<flax::fetch::ComponentMut<f32>>,
+             step: &Timestep,
+             frame: &mut u32,
+             steps: &mut Vec<(u32, f32)>| {
+                for pos in &mut query {
+                    *pos += step.delta.as_secs_f32();
+                }
+
+                steps.push((*frame, step.alpha));
+            },
+        );
+
+    let frames = SharedResource::new(0);
+    let render = System::builder()
+        .with_name("render")
+        .with_input_mut::<u32>()
+        .with_resource(frames.clone())
+        .build(|frame: &mut u32, frames: &mut u32| {
+            *frames += 1;
+            *frame += 1;
+        });
+
+    let mut schedule = Schedule::new()
+        .with_system(Schedule::from([physics.boxed()]).fixed_timestep(Duration::from_millis(250)))
+        .with_system(render);
+
+    let mut frame = 0_u32;
+    for delta in [100, 200, 600, 0] {
+        let mut delta = Duration::from_millis(delta);
+        schedule
+            .execute_seq_with(&mut world, (&mut delta, &mut frame))
+            .unwrap();
+    }
+
+    assert_eq!(&*steps.borrow(), &[(1, 0.2), (2, 1.0), (2, 0.6)]);
+    assert_eq!(*frames.borrow(), 4);
+    assert_eq!(world.get_copy(id, position()), Ok(0.75));
+
+    // Discard time which can not be caught up with
+    let runs = SharedResource::new(0);
+    let mut schedule = Schedule::new().with_system(
+        Schedule::from([System::builder()
+            .with_input::<Timestep>()
+            .with_resource(runs.clone())
+            .build(|step: &Timestep, runs: &mut usize| {
+                assert_eq!(step.delta, Duration::from_millis(250));
+                *runs += 1;
+            })
+            .boxed()])
+        .fixed_timestep(Duration::from_millis(250))
+        .with_max_steps(2),
+    );
+
+    schedule
+        .execute_seq_with(&mut world, &mut Duration::from_millis(1100))
+        .unwrap();
+    assert_eq!(*runs.borrow(), 2);
+
+    // The remaining 100ms are kept
+    schedule
+        .execute_seq_with(&mut world, &mut Duration::from_millis(150))
+        .unwrap();
+    assert_eq!(*runs.borrow(), 3);
+
+    // The elapsed time must be supplied by the outer schedule
+    assert!(schedule.execute_seq(&mut world).is_err());
+}
+
+#[test]
+fn schedule_rate_limited() {
+    use flax::schedule::Timestep;
+    use std::time::Duration;
+
+    let runs = flax::SharedResource::new(Vec::new());
+
+    let mut schedule = Schedule::new().with_system(
+        Schedule::from([System::builder()
+            .with_input::<Timestep>()
+            .with_resource(runs.clone())
+            .build(|step: &Timestep, runs: &mut Vec<Duration>| runs.push(step.delta))
+            .boxed()])
+        .rate_limited(Duration::from_millis(100)),
+    );
+
+    let mut world = World::new();
+    let counts = [30, 50, 40, 250, 10]
+        .map(|delta| {
+            let before = runs.borrow().len();
+            schedule
+                .execute_seq_with(&mut world, &mut Duration::from_millis(delta))
+                .unwrap();
+            runs.borrow().len() - before
+        })
+        .to_vec();
+
+    assert_eq!(counts, [0, 0, 1, 1, 0]);
+    assert_eq!(
+        &*runs.borrow(),
+        &[Duration::from_millis(120), Duration::from_millis(250)]
+    );
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn schedule_timestep_par() {
+    use flax::{schedule::Timestep, SharedResource};
+    use std::time::Duration;
+
+    component! {
+        a: f32,
+        b: f32,
+    }
+
+    let mut world = World::new();
+    let id = Entity::builder()
+        .set(a(), 0.0)
+        .set(b(), 0.0)
+        .spawn(&mut world);
+
+    let integrate = |component: flax::Component<f32>| {
+        System::builder()
+            .with_query(Query::new(component.as_mut()))
+            .with_input::<Timestep>()
+            .build(
+                move |mut query: QueryBorrow<flax::fetch::ComponentMut<f32>>, step: &Timestep| {
+                    for v in &mut query {
+                        *v += step.delta.as_secs_f32();
+                    }
+                },
+            )
+            .boxed()
+    };
+
+    let frames = SharedResource::new(0);
+    let mut schedule = Schedule::new()
+        .with_system(
+            Schedule::from([integrate(a()), integrate(b())])
+                .fixed_timestep(Duration::from_millis(500))
+                .with_parallel(),
+        )
+        .with_system(
+            System::builder()
+                .with_input::<Duration>()
+                .with_resource(frames.clone())
+                .build(|_: &Duration, frames: &mut usize| *frames += 1),
+        );
+
+    for _ in 0..3 {
+        schedule
+            .execute_par_with(&mut world, &mut Duration::from_millis(500))
+            .unwrap();
+    }
+
+    assert_eq!(*frames.borrow(), 3);
+    assert_eq!(world.get_copy(id, a()), Ok(1.5));
+    assert_eq!(world.get_copy(id, b()), Ok(1.5));
+}
+
+#[test]
+fn schedule_stats() {
+    component! {
+        health: f32,
+        regen: f32,
+    }
+
+    let mut world = World::new();
+    for i in 0..8 {
+        let mut builder = Entity::builder();
+        builder.set(health(), 0.0);
+        if i % 2 == 0 {
+            builder.set(regen(), 1.0);
+        }
+        builder.spawn(&mut world);
+    }
+
+    let regen_system = System::builder()
+        .with_name("regen")
+        .with_query(Query::new((health().as_mut(), regen())))
+        .for_each(|(health, regen)| *health += regen);
+
+    let skipped = System::builder()
+        .with_name("skipped")
+        .run_if(|_| false)
+        .build(|| {});
+
+    let mut schedule = Schedule::from([regen_system.boxed(), skipped.boxed()]);
+
+    for _ in 0..3 {
+        schedule.execute_seq(&mut world).unwrap();
+    }
+
+    let stats = schedule.stats().collect_vec();
+    assert_eq!(stats[0].0, "regen");
+    assert_eq!(stats[0].1.invocations(), 3);
+    assert_eq!(stats[0].1.batch(), None);
+    assert!(stats[0].1.max() >= stats[0].1.average());
+    assert!(stats[0].1.total() >= stats[0].1.last());
+    assert_eq!(stats[0].1.entities(), 4);
+    assert_eq!(stats[1].1.invocations(), 0);
+
+    let info = schedule.batch_info(&world);
+    let regen_info = info
+        .iter()
+        .flat_map(|v| v.iter())
+        .find(|v| v.name() == "regen")
+        .unwrap();
+    assert_eq!(regen_info.entities(), 4);
+    assert_eq!(regen_info.stats().invocations(), 3);
+
+    #[cfg(feature = "rayon")]
+    {
+        schedule.execute_par(&mut world).unwrap();
+        let (_, stats) = schedule.stats().find(|v| v.0 == "regen").unwrap();
+        assert_eq!(stats.invocations(), 4);
+        assert!(stats.batch().is_some());
+    }
+
+    // The matched entities are recorded for each run
+    Entity::builder()
+        .set(health(), 0.0)
+        .set(regen(), 1.0)
+        .spawn(&mut world);
+    schedule.execute_seq(&mut world).unwrap();
+    let (_, stats) = schedule.stats().find(|v| v.0 == "regen").unwrap();
+    assert_eq!(stats.entities(), 5);
+
+    schedule.reset_stats();
+    assert!(schedule.stats().all(|(_, v)| v.invocations() == 0));
+}
+
+#[test]
+fn schedule_dependency_graph() {
+    component! {
+        health: f32,
+        regen: f32,
+        armor: f32,
+    }
+
+    let mut world = World::new();
+    Entity::builder()
+        .set(health(), 0.0)
+        .set(regen(), 1.0)
+        .set(armor(), 0.0)
+        .spawn(&mut world);
+
+    let regen_system = System::builder()
+        .with_name("regen")
+        .with_query(Query::new((health().as_mut(), regen())))
+        .for_each(|(health, regen)| *health += regen);
+
+    let armor_system = System::builder()
+        .with_name("armor")
+        .with_query(Query::new(armor().as_mut()))
+        .for_each(|armor| *armor += 1.0);
+
+    let log_system = System::builder()
+        .with_name("log")
+        .with_query(Query::new(health()))
+        .for_each(|_| {});
+
+    let mut schedule = Schedule::from([
+        regen_system.boxed(),
+        armor_system.boxed().with_label("armor"),
+        log_system.boxed().after("armor"),
+    ]);
+
+    let graph = schedule.dependency_graph(&world).unwrap();
+    assert_eq!(graph.systems(), ["regen", "armor", "log"]);
+    assert_eq!(graph.batches(), [vec![0, 1], vec![2]]);
+
+    let edge = graph.edge("regen", "log").unwrap();
+    assert!(!edge.ordered());
+    assert!(!edge.conflicts().is_empty());
+    for conflict in edge.conflicts() {
+        assert!(conflict.src_mutable());
+        assert!(!conflict.dst_mutable());
+        assert!(conflict
+            .desc()
+            .starts_with("&mut health -> &health in archetype"));
+    }
+
+    let edge = graph.edge("armor", "log").unwrap();
+    assert!(edge.ordered());
+    assert!(edge.conflicts().is_empty());
+
+    assert!(graph.edge("regen", "armor").is_none());
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph schedule {"));
+    assert!(dot.contains("s0 [label=\"regen\"];"));
+    assert!(dot.contains("s0 -> s2 [label=\"&mut health -> &health in archetype"));
+    assert!(dot.contains("s1 -> s2 [label=\"ordering\"];"));
+
+    let mermaid = graph.to_mermaid();
+    assert!(mermaid.starts_with("flowchart LR"));
+    assert!(mermaid.contains("s0 -->|\"#amp;mut health -> #amp;health in archetype"));
+}
@@ -0,0 +1,170 @@
+use flax::{components::name, *};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+component! {
+    position: (f32, f32) => [ Cloneable ],
+    health: f32 => [ Cloneable ],
+    input: u32 => [ Cloneable, NoRollback ],
+    handle: std::sync::Arc<()>,
+}
+
+#[test]
+fn snapshot_restore() {
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set(name(), "a".into())
+        .set(position(), (0.0, 0.0))
+        .set(health(), 100.0)
+        .spawn(&mut world);
+
+    let b = Entity::builder()
+        .set(name(), "b".into())
+        .set(position(), (1.0, 0.0))
+        .spawn(&mut world);
+
+    let snapshot = world.snapshot();
+    assert_eq!(snapshot.len(), 2);
+
+    let next = world.spawn();
+    *world.get_mut(a, health()).unwrap() = 50.0;
+    world.remove(a, position()).unwrap();
+    world.despawn(b).unwrap();
+    let c = Entity::builder()
+        .set(name(), "c".into())
+        .set(health(), 25.0)
+        .spawn(&mut world);
+
+    world.restore(&snapshot);
+
+    assert_eq!(world.get_copy(a, health()), Ok(100.0));
+    assert_eq!(world.get_copy(a, position()), Ok((0.0, 0.0)));
+    assert_eq!(world.get_copy(b, position()), Ok((1.0, 0.0)));
+    assert!(!world.is_alive(c));
+    assert!(!world.is_alive(next));
+
+    assert_eq!(world.find_by_name("b"), Some(b));
+    assert_eq!(world.find_by_name("c"), None);
+
+    // Ids are allocated as they were when the snapshot was taken
+    let d = world.spawn();
+    assert_eq!(d, next);
+
+    assert_eq!(
+        Query::new((entity_ids(), health()))
+            .borrow(&world)
+            .iter()
+            .map(|(id, &v)| (id, v))
+            .collect_vec(),
+        [(a, 100.0)]
+    );
+
+    // Restoring twice yields the same state
+    world.restore(&snapshot);
+    assert!(!world.is_alive(d));
+    assert_eq!(Query::new(position()).borrow(&world).iter().count(), 2);
+}
+
+#[test]
+fn snapshot_no_rollback() {
+    let mut world = World::new();
+
+    let shared = std::sync::Arc::new(());
+
+    let a = Entity::builder()
+        .set(position(), (0.0, 0.0))
+        .set(input(), 1)
+        .set(handle(), shared.clone())
+        .spawn(&mut world);
+
+    let snapshot = world.snapshot();
+
+    world.set(a, position(), (5.0, 5.0)).unwrap();
+    world.set(a, input(), 2).unwrap();
+    let b = Entity::builder()
+        .set(input(), 3)
+        .set(handle(), shared.clone())
+        .spawn(&mut world);
+
+    assert_eq!(std::sync::Arc::strong_count(&shared), 3);
+
+    world.restore(&snapshot);
+
+    assert_eq!(world.get_copy(a, position()), Ok((0.0, 0.0)));
+    assert_eq!(world.get_copy(a, input()), Ok(2));
+    assert!(world.has(a, handle()));
+    assert!(!world.is_alive(b));
+
+    // The components of `b` were dropped
+    assert_eq!(std::sync::Arc::strong_count(&shared), 2);
+}
+
+#[test]
+fn snapshot_changes() {
+    let mut world = World::new();
+
+    let a = Entity::builder().set(health(), 100.0).spawn(&mut world);
+
+    let snapshot = world.snapshot();
+
+    let mut query = Query::new(entity_ids()).with_filter(health().modified());
+    assert_eq!(query.collect_vec(&world), [a]);
+    assert_eq!(query.collect_vec(&world), []);
+
+    *world.get_mut(a, health()).unwrap() = 0.0;
+    assert_eq!(query.collect_vec(&world), [a]);
+
+    world.restore(&snapshot);
+    assert_eq!(world.get_copy(a, health()), Ok(100.0));
+
+    // The change ticks are restored rather than marking the components as added
+    assert_eq!(query.collect_vec(&world), []);
+    assert_eq!(
+        Query::new(entity_ids())
+            .with_filter(health().added())
+            .collect_vec(&world),
+        [a]
+    );
+}
+
+#[test]
+fn snapshot_change_ticks() {
+    let mut world = World::new();
+
+    let a = Entity::builder()
+        .set(health(), 100.0)
+        .set(input(), 1)
+        .spawn(&mut world);
+
+    let b = Entity::builder().set(health(), 50.0).spawn(&mut world);
+
+    let mut health_query = Query::new(entity_ids()).with_filter(health().modified());
+    let mut input_query = Query::new(entity_ids()).with_filter(input().modified());
+
+    assert_eq!(health_query.collect_vec(&world).len(), 2);
+    assert_eq!(input_query.collect_vec(&world), [a]);
+
+    *world.get_mut(b, health()).unwrap() = 40.0;
+    let snapshot = world.snapshot();
+
+    // Seen before the snapshot was restored
+    assert_eq!(health_query.collect_vec(&world), [b]);
+
+    world.despawn(b).unwrap();
+    *world.get_mut(a, health()).unwrap() = 0.0;
+    world.restore(&snapshot);
+
+    // Neither the restored nor the kept components are marked as changed
+    assert_eq!(health_query.collect_vec(&world), []);
+    assert_eq!(input_query.collect_vec(&world), []);
+    assert_eq!(world.get_copy(b, health()), Ok(40.0));
+
+    // Kept components retain their latest change
+    *world.get_mut(a, input()).unwrap() = 2;
+    world.restore(&snapshot);
+
+    assert_eq!(health_query.collect_vec(&world), []);
+    assert_eq!(input_query.collect_vec(&world), [a]);
+    assert_eq!(world.get_copy(a, input()), Ok(2));
+}
@@ -0,0 +1,174 @@
+#[cfg(feature = "serde")]
+mod unknown_components {
+    use flax::{
+        components::name,
+        serialize::{
+            unknown_components, DeserializeBuilder, SerializationContext,
+            SerializationContextBuilder, SerializeFormat, UnknownReport, Value,
+        },
+        *,
+    };
+    use pretty_assertions::assert_eq;
+    use serde::de::DeserializeSeed;
+
+    component! {
+        health: f32,
+        mod_data: String,
+        mod_link(id): u32,
+    }
+
+    /// The context of the build which knows about the mod
+    fn full_context() -> SerializationContext {
+        SerializationContextBuilder::new()
+            .with(name())
+            .with(health())
+            .with(mod_data())
+            .with_relation(mod_link)
+            .build()
+    }
+
+    /// The context of a build which does not know about the mod
+    fn partial_context() -> SerializationContext {
+        let mut builder = SerializationContextBuilder::new();
+        builder.with(name()).with(health());
+        builder.deserializer_mut().keep_unknown();
+        builder.build()
+    }
+
+    fn serialize(context: &SerializationContext, world: &World, format: SerializeFormat) -> String {
+        serde_json::to_string(&context.serialize_world(world, format)).unwrap()
+    }
+
+    fn deserialize(context: &SerializationContext, json: &str) -> World {
+        context
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(json))
+            .unwrap()
+    }
+
+    fn modded_world() -> (World, [Entity; 4]) {
+        let mut world = World::new();
+
+        let b = Entity::builder()
+            .set(name(), "b".into())
+            .set(health(), 2.0)
+            .spawn(&mut world);
+
+        let a = Entity::builder()
+            .set(name(), "a".into())
+            .set(health(), 1.0)
+            .set(mod_data(), "modded".into())
+            .set(mod_link(b), 5)
+            .spawn(&mut world);
+
+        // Shares an archetype with `a` when loaded without the mod, but has other unknown components
+        let c = Entity::builder()
+            .set(name(), "c".into())
+            .set(health(), 3.0)
+            .set(mod_data(), "also modded".into())
+            .spawn(&mut world);
+
+        // Only contains unknown components
+        let d = Entity::builder()
+            .set(mod_data(), "only modded".into())
+            .spawn(&mut world);
+
+        (world, [a, b, c, d])
+    }
+
+    fn check(world: &World, [a, b, c, d]: [Entity; 4]) {
+        assert_eq!(world.get_copy(a, health()), Ok(1.0));
+        assert_eq!(world.get(a, mod_data()).as_deref(), Ok(&"modded".into()));
+        assert_eq!(world.get_copy(a, mod_link(b)), Ok(5));
+
+        assert_eq!(world.get_copy(b, health()), Ok(2.0));
+        assert!(!world.has(b, mod_data()));
+
+        assert_eq!(world.get_copy(c, health()), Ok(3.0));
+        assert_eq!(
+            world.get(c, mod_data()).as_deref(),
+            Ok(&"also modded".into())
+        );
+
+        assert_eq!(
+            world.get(d, mod_data()).as_deref(),
+            Ok(&"only modded".into())
+        );
+    }
+
+    #[test]
+    fn report() {
+        let (world, [a, b, c, d]) = modded_world();
+        let json = serialize(&full_context(), &world, SerializeFormat::RowMajor);
+
+        // Unknown components fail by default
+        assert!(DeserializeBuilder::new()
+            .with(name())
+            .with(health())
+            .build()
+            .deserialize_world()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .is_err());
+
+        let (world, report) = partial_context()
+            .deserializer()
+            .deserialize_world_with_report()
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        assert_eq!(report.keys(), ["mod_data", "mod_link"]);
+        assert_eq!(report, UnknownReport::from_world(&world));
+
+        let mut entries = report
+            .entries()
+            .iter()
+            .map(|(id, v)| (*id, v.key(), v.data().to_vec()))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|v| (v.0, v.1));
+
+        let b_value = serde_json::from_str::<Value>(&serde_json::to_string(&b).unwrap()).unwrap();
+
+        assert_eq!(
+            entries,
+            [
+                (a, "mod_data", vec![Value::String("modded".into())]),
+                (a, "mod_link", vec![b_value, Value::U64(5)]),
+                (c, "mod_data", vec![Value::String("also modded".into())]),
+                (d, "mod_data", vec![Value::String("only modded".into())]),
+            ]
+        );
+
+        // Known components are deserialized as usual
+        assert_eq!(world.get_copy(a, health()), Ok(1.0));
+        assert!(!world.has(b, unknown_components()));
+        assert_eq!(
+            world
+                .get(a, unknown_components())
+                .unwrap()
+                .get("mod_data")
+                .map(|v| v.value().clone()),
+            Some(Value::String("modded".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let (world, ids) = modded_world();
+        let full = full_context();
+        let partial = partial_context();
+
+        let formats = [SerializeFormat::RowMajor, SerializeFormat::ColumnMajor];
+        for load in formats.clone() {
+            for save in formats.clone() {
+                let json = serialize(&full, &world, load.clone());
+                let loaded = deserialize(&partial, &json);
+
+                // Saved by the build which does not know about the mod
+                let json = serialize(&partial, &loaded, save.clone());
+
+                let world = deserialize(&full, &json);
+                check(&world, ids);
+            }
+        }
+    }
+}